@@ -19,4 +19,24 @@ async fn should_verify() {
         }))
         .verify()
         .await;
-}
\ No newline at end of file
+}
+#[actix_web::test]
+async fn should_report_every_stub_not_honored() {
+    let report = App::new()
+        .app_data(fake_pet_repository())
+        .service(pet::find_all)
+        .service(pet::find_by_id)
+        .wrap(ActixVerifyLifecycle::<PetRepository>(|repo| {
+            repo.delete_all()
+                .and_then(|_| repo.insert_all(fake_pets()))
+                .unwrap()
+        }))
+        .verify_report()
+        .await;
+    let mut failed = report.mismatches().iter()
+        .filter(|m| m.kind == MismatchKind::Status)
+        .map(|m| m.stub.as_str())
+        .collect::<Vec<_>>();
+    failed.sort_unstable();
+    assert_eq!(failed, vec!["create", "create-conflict-name"]);
+}
@@ -0,0 +1,65 @@
+use std::{env::{current_dir, var}, path::PathBuf};
+
+use clap::{Parser, ValueHint};
+
+use stubr::AnyBroker;
+
+#[derive(Parser, Debug, Eq, PartialEq)]
+pub struct CliPublishConfig {
+    /// broker location, either a directory or an http(s) uri
+    ///
+    /// Defaults to 'STUBR_BROKER' environment variable
+    #[clap(short, long, value_parser)]
+    pub broker: Option<String>,
+    /// name of the producer app the stubs belong to
+    #[clap(short, long, value_parser)]
+    pub app: String,
+    /// version of the producer app e.g. a semver or a git sha
+    #[clap(long = "app-version", value_parser)]
+    pub app_version: String,
+    /// tag to attach to this version e.g. a branch name
+    ///
+    /// Consumers fetch the latest version bearing a tag
+    #[clap(short, long = "tag", value_parser)]
+    pub tags: Vec<String>,
+    /// stub files directory
+    ///
+    /// Defaults to current directory when not present
+    #[clap(value_parser, value_hint = ValueHint::DirPath)]
+    pub dir: Option<PathBuf>,
+}
+
+impl CliPublishConfig {
+    pub fn broker(&self) -> anyhow::Result<AnyBroker> {
+        self.broker.clone()
+            .or_else(|| var(AnyBroker::BROKER_ENV).ok())
+            .map(|b| AnyBroker::from(b.as_str()))
+            .ok_or_else(|| anyhow::anyhow!("Missing broker, use '--broker' or '{}'", AnyBroker::BROKER_ENV))
+    }
+
+    pub fn dir(&self) -> anyhow::Result<PathBuf> {
+        let current = current_dir()?;
+        Ok(self.dir.as_ref().map(|d| current.join(d)).unwrap_or(current))
+    }
+}
+
+#[derive(Parser, Debug, Eq, PartialEq)]
+pub struct CliBrokerConfig {
+    /// port number the broker is listening on
+    ///
+    /// Defaults to a random one
+    #[clap(short, long, value_parser)]
+    pub port: Option<u16>,
+    /// directory where published stubs are stored
+    ///
+    /// Defaults to current directory when not present
+    #[clap(value_parser, value_hint = ValueHint::DirPath)]
+    pub dir: Option<PathBuf>,
+}
+
+impl CliBrokerConfig {
+    pub fn dir(&self) -> anyhow::Result<PathBuf> {
+        let current = current_dir()?;
+        Ok(self.dir.as_ref().map(|d| current.join(d)).unwrap_or(current))
+    }
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use log::info;
+
+use config::{CliBrokerConfig, CliPublishConfig};
+use stubr::{Broker, BrokerServer, Contract};
+
+pub mod config;
+
+pub struct Publish;
+
+impl Publish {
+    pub async fn publish(config: CliPublishConfig) -> anyhow::Result<()> {
+        let broker = config.broker()?;
+        let contract = Contract::from_dir(&config.app, &config.app_version, config.tags.clone(), &config.dir()?)?;
+        broker.publish(&contract).await?;
+        info!("Published {} stub(s) and {} body file(s) of version '{}' of app '{}'", contract.stubs.len(), contract.files.len(), contract.version, contract.app);
+        Ok(())
+    }
+}
+
+pub struct Serve;
+
+impl Serve {
+    const SLEEP_DURATION: Duration = Duration::from_millis(1000);
+
+    pub async fn serve(config: CliBrokerConfig) -> anyhow::Result<()> {
+        let _broker = BrokerServer::start_on(config.dir()?, config.port);
+        loop { async_std::task::sleep(Self::SLEEP_DURATION).await; }
+    }
+}
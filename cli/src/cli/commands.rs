@@ -1,9 +1,9 @@
 use clap::Parser;
 
-use crate::record::config::CliRecordConfig;
+use crate::{broker::config::{CliBrokerConfig, CliPublishConfig}, record::config::CliRecordConfig};
 
 use super::completion::Shell;
-use super::super::record::Record;
+use super::super::{broker::{Publish, Serve}, record::Record};
 
 #[derive(Parser, Debug, Eq, PartialEq)]
 pub enum Commands {
@@ -17,6 +17,16 @@ pub enum Commands {
         #[clap(flatten)]
         config: CliRecordConfig
     },
+    /// Publishes stubs to a broker for consumers to fetch them
+    Publish {
+        #[clap(flatten)]
+        config: CliPublishConfig
+    },
+    /// Serves a broker storing stubs published by producers
+    Broker {
+        #[clap(flatten)]
+        config: CliBrokerConfig
+    },
 }
 
 impl Commands {
@@ -24,7 +34,9 @@ impl Commands {
     pub async fn exec(self) -> anyhow::Result<()> {
         match self {
             Commands::Completion { shell } => shell.generate_and_install(),
-            Commands::Record { config } => Record::record(config).await,
+            Commands::Record { config } => return Record::record(config).await,
+            Commands::Publish { config } => return Publish::publish(config).await,
+            Commands::Broker { config } => return Serve::serve(config).await,
         }
         Ok(())
     }
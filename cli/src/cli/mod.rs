@@ -11,7 +11,7 @@ use colored::Colorize;
 use log::info;
 
 use commands::Commands;
-use stubr::{Config, Stubr};
+use stubr::{Config, Helpers, Stubr};
 
 mod commands;
 mod completion;
@@ -47,6 +47,11 @@ pub struct Cli {
     /// adds this delay to any locally defined delay. Simulates network delays.
     #[clap(short, long, value_parser)]
     latency: Option<String>,
+    /// directory of Rhai or Lua scripts defining custom handlebars helpers
+    ///
+    /// each '*.rhai' or '*.lua' file is a helper named after the file e.g. 'iban.rhai' is called with '{{iban}}'
+    #[clap(long, value_parser, value_hint = ValueHint::DirPath)]
+    helpers: Option<PathBuf>,
     #[clap(subcommand)]
     cmd: Option<Commands>,
 }
@@ -60,7 +65,8 @@ impl Cli {
         if let Some(cmd) = self.cmd {
             cmd.exec().await
         } else {
-            Self::run_server(self.stubs_dir(), self.into(), start_time).await
+            let stubs = self.stubs_dir();
+            Self::run_server(stubs, self.try_into()?, start_time).await
         }
     }
 
@@ -116,16 +122,27 @@ impl Cli {
             ?.as_millis()
             .try_into().ok()
     }
+
+    fn helpers(&self) -> anyhow::Result<Helpers> {
+        self.helpers.as_ref()
+            .map(Helpers::from_scripts_dir)
+            .unwrap_or_else(|| Ok(Helpers::default()))
+    }
 }
 
-impl From<Cli> for Config {
-    fn from(cli: Cli) -> Self {
-        Self {
+impl TryFrom<Cli> for Config {
+    type Error = anyhow::Error;
+
+    fn try_from(cli: Cli) -> anyhow::Result<Self> {
+        Ok(Self {
             port: cli.port,
             verbose: Some(true),
             global_delay: cli.global_delay_milliseconds(),
             latency: cli.latency_milliseconds(),
-        }
+            helpers: cli.helpers()?,
+            jwt_key: None,
+            root_dir: cli.root_dir,
+        })
     }
 }
 
@@ -160,6 +177,15 @@ mod cli_tests {
         assert_eq!(cli.root_dir().unwrap(), current_dir().unwrap().join(root_dir).join("mappings"))
     }
 
+    #[test]
+    fn helpers_should_be_loaded_from_dir() {
+        let cli = Cli { helpers: Some(PathBuf::from("tests/helpers")), ..Default::default() };
+        assert!(!cli.helpers().unwrap().is_empty());
+        assert!(Cli::default().helpers().unwrap().is_empty());
+        let cli = Cli { helpers: Some(PathBuf::from("tests/unknown")), ..Default::default() };
+        assert!(cli.helpers().is_err());
+    }
+
     #[test]
     fn root_dir_should_have_precedence_over_dir() {
         let dir = PathBuf::from("tests/stubs");
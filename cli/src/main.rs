@@ -6,6 +6,7 @@ use cli::{Cli, logger::Logger};
 
 mod cli;
 mod record;
+mod broker;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -16,6 +16,25 @@ pub struct CliRecordConfig {
     /// Defaults to current directory
     #[clap(short, long, value_parser)]
     pub output: Option<PathBuf>,
+    /// base url every request is forwarded to, the recorder then acting as a reverse proxy
+    ///
+    /// Defaults to forwarding requests to the host they address
+    #[clap(short, long, value_parser)]
+    pub target: Option<String>,
+    /// replaces uuids, numeric ids, timestamps and emails by matchers and templates
+    #[clap(short, long, value_parser)]
+    pub generalize: bool,
+    /// replays a request recorded with different responses in the order they were recorded
+    #[clap(short, long, value_parser)]
+    pub scenarios: bool,
+    /// PEM certificate authority issuing certificates for recorded https hosts
+    ///
+    /// Defaults to a new one written in the output directory
+    #[clap(long, value_parser, requires = "ca-key")]
+    pub ca_cert: Option<PathBuf>,
+    /// PEM private key of the certificate authority
+    #[clap(long, value_parser, requires = "ca-cert")]
+    pub ca_key: Option<PathBuf>,
 }
 
 impl Default for CliRecordConfig {
@@ -23,6 +42,11 @@ impl Default for CliRecordConfig {
         Self {
             port: Some(3030),
             output: current_dir().ok(),
+            target: None,
+            generalize: false,
+            scenarios: false,
+            ca_cert: None,
+            ca_key: None,
         }
     }
 }
@@ -32,6 +56,11 @@ impl From<CliRecordConfig> for RecordConfig {
         Self {
             port: cli_cfg.port,
             output: cli_cfg.output.or_else(|| current_dir().ok()),
+            target: cli_cfg.target,
+            generalize: cli_cfg.generalize,
+            scenarios: cli_cfg.scenarios,
+            ca_cert: cli_cfg.ca_cert,
+            ca_key: cli_cfg.ca_key,
             ..Default::default()
         }
     }
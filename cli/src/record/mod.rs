@@ -1,7 +1,9 @@
-use std::time::Duration;
+use std::{env::current_dir, fs::write, time::Duration};
+
+use log::{error, info};
 
 use config::CliRecordConfig;
-use stubr::Stubr;
+use stubr::{RecordConfig, Stubr};
 
 pub mod config;
 
@@ -9,9 +11,20 @@ pub struct Record;
 
 impl Record {
     const SLEEP_DURATION: Duration = Duration::from_millis(1000);
+    const CA_FILE: &'static str = "stubr-ca.pem";
 
-    pub async fn record(config: CliRecordConfig) {
-        let _recorder = Stubr::record_with(config.into());
+    pub async fn record(config: CliRecordConfig) -> anyhow::Result<()> {
+        let config = RecordConfig::from(config);
+        let generated_ca = config.ca_cert.is_none();
+        let output = config.output.clone().or_else(|| current_dir().ok()).unwrap_or_default();
+        let recorder = Stubr::try_record_with(config)?;
+        if generated_ca {
+            let ca = output.join(Self::CA_FILE);
+            match write(&ca, recorder.ca_cert()) {
+                Ok(_) => info!("Trust {:?} in your http client to record https traffic", ca),
+                Err(e) => error!("Failed writing recorder certificate authority in {:?} because {:?}", ca, e),
+            }
+        }
         loop { async_std::task::sleep(Self::SLEEP_DURATION).await; }
     }
 }
\ No newline at end of file
@@ -0,0 +1,31 @@
+use std::{env::temp_dir, process::Command, time::{SystemTime, UNIX_EPOCH}};
+
+use assert_cmd::prelude::*;
+
+use stubr::{Broker, FsBroker, Selector};
+
+fn broker_dir(name: &str) -> String {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+    temp_dir().join(format!("stubr-cli-broker-{}-{}", name, nanos)).to_str().unwrap().to_string()
+}
+
+#[async_std::test]
+async fn should_publish_stubs_to_broker() {
+    let broker = broker_dir("publish");
+    Command::cargo_bin("stubr").unwrap()
+        .args(&["publish", "--broker", &broker, "--app", "ping", "--app-version", "1.0.0", "-t", "main", "tests/stubs/mappings"])
+        .assert()
+        .success();
+    let contract = FsBroker::new(&broker).fetch("ping", &Selector::Tag(String::from("main"))).await.unwrap();
+    assert_eq!(contract.version, "1.0.0");
+    assert_eq!(contract.stubs.keys().collect::<Vec<_>>(), vec!["ping.json"]);
+}
+
+#[test]
+fn publish_should_fail_without_broker() {
+    Command::cargo_bin("stubr").unwrap()
+        .args(&["publish", "--app", "ping", "--app-version", "1.0.0", "tests/stubs"])
+        .env_remove("STUBR_BROKER")
+        .assert()
+        .failure();
+}
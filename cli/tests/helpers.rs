@@ -0,0 +1,22 @@
+use asserhttp::*;
+use surf::get;
+
+use utils::StubrCli;
+
+mod utils;
+
+#[async_std::test]
+async fn should_render_with_rhai_helpers() {
+    let stubr = StubrCli::new(&["tests/helpers/iban.json", "--helpers", "tests/helpers"]);
+    get(&stubr.addr).await
+        .expect_status_ok()
+        .expect_body_text_eq(r#"{"iban":"DE7630006000011234567890189"}"#);
+}
+
+#[async_std::test]
+async fn should_render_with_lua_helpers() {
+    let stubr = StubrCli::new(&["tests/helpers/bic.json", "--helpers", "tests/helpers"]);
+    get(&stubr.addr).await
+        .expect_status_ok()
+        .expect_body_text_eq(r#"{"bic":"AGRIFRPPXXX"}"#);
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+function generate(params, hash)
+    return (params[1] or "BNPA") .. "FRPPXXX"
+end
+
+function verify(params, hash, actual)
+    return #actual == 8 or #actual == 11
+end
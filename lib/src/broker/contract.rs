@@ -0,0 +1,274 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, ensure};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{gen::hash::FnvHasher, model::response::body_file::BodyFile, server::stub_finder::StubFinder};
+
+/// Stubs of one version of an app as exchanged with a broker
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Contract {
+    pub app: String,
+    pub version: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Stubs indexed by their path relative to the stubs directory e.g. `pets/find-by-id.json`
+    pub stubs: BTreeMap<String, Value>,
+    /// Base64 encoded files the stubs reference with 'bodyFileName', indexed by their path relative
+    /// to Wiremock's '__files' folder e.g. `pets/1.json`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, String>,
+}
+
+impl Contract {
+    const CACHE_DIR: &'static str = "stubr-broker";
+
+    /// Collects all json stubs under `dir`, recursively, along with the body files they reference.
+    /// Those are looked up in the '__files' folder of `dir` or else next to the 'mappings' folder holding
+    /// the stub. All the files of the '__files' folder are collected for a templated 'bodyFileName'
+    pub fn from_dir(app: &str, version: &str, tags: Vec<String>, dir: &Path) -> anyhow::Result<Self> {
+        let mut stubs = BTreeMap::new();
+        let mut files = BTreeMap::new();
+        for file in StubFinder::find_all_stubs(dir) {
+            let key = file.strip_prefix(dir).ok()
+                .filter(|k| !k.as_os_str().is_empty())
+                .or_else(|| file.file_name().map(Path::new))
+                .map(Self::key)
+                .ok_or_else(|| anyhow!("invalid stub path '{}'", file.display()))?;
+            let content = fs::read_to_string(&file).with_context(|| format!("failed reading '{}'", file.display()))?;
+            let stub = serde_json::from_str(&content).with_context(|| format!("invalid json stub '{}'", file.display()))?;
+            let files_dir = Some(dir.join(StubFinder::BODY_FILES_DIR)).filter(|d| d.is_dir())
+                .or_else(|| StubFinder::body_files_dir(&file, None))
+                .unwrap_or_else(|| dir.join(StubFinder::BODY_FILES_DIR));
+            Self::collect_body_files(&stub, &files_dir, &mut files)
+                .with_context(|| format!("failed collecting body files of stub '{}'", file.display()))?;
+            stubs.insert(key, stub);
+        }
+        ensure!(!stubs.is_empty(), "no stubs found in '{}'", dir.display());
+        let contract = Self { app: app.to_string(), version: version.to_string(), tags, stubs, files };
+        contract.validate()?;
+        Ok(contract)
+    }
+
+    /// Writes stubs in a directory from which a [crate::Stubr] can be started, laid out like Wiremock's
+    /// root directory: stubs in its 'mappings' folder and body files in its '__files' one.
+    /// The same contract is always written in the same directory.
+    pub fn materialize(&self) -> anyhow::Result<PathBuf> {
+        self.validate()?;
+        let dir = env::temp_dir().join(Self::CACHE_DIR).join(&self.app).join(format!("{}-{}", self.version, self.digest()));
+        if !dir.exists() {
+            let staging = dir.with_extension(format!("tmp{}", rand::random::<u32>()));
+            for (key, stub) in &self.stubs {
+                Self::write(&staging.join(StubFinder::MAPPINGS_DIR).join(key), &serde_json::to_vec_pretty(stub)?)?;
+            }
+            for (key, content) in &self.files {
+                let content = base64::decode(content).with_context(|| format!("invalid body file '{}'", key))?;
+                Self::write(&staging.join(StubFinder::BODY_FILES_DIR).join(key), &content)?;
+            }
+            // another process might have materialized it meanwhile
+            if fs::rename(&staging, &dir).is_err() {
+                fs::remove_dir_all(&staging).ok();
+                ensure!(dir.exists(), "failed writing stubs of app '{}' in '{}'", self.app, dir.display());
+            }
+        }
+        Ok(dir)
+    }
+
+    fn write(file: &Path, content: &[u8]) -> anyhow::Result<()> {
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(fs::write(file, content)?)
+    }
+
+    /// Reads the files referenced by the 'bodyFileName' of every response of `stub`
+    fn collect_body_files(stub: &Value, files_dir: &Path, files: &mut BTreeMap<String, String>) -> anyhow::Result<()> {
+        let variants = stub.get("responses").and_then(Value::as_array).into_iter().flatten().filter_map(|v| v.get("response"));
+        let names = stub.get("response").into_iter().chain(variants)
+            .filter_map(|r| r.get("bodyFileName").and_then(Value::as_str));
+        for name in names {
+            let body_file = BodyFile::locate(name, Some(files_dir));
+            if body_file.is_templated() {
+                for file in Self::find_all_files(files_dir) {
+                    let key = file.strip_prefix(files_dir).map(Self::key)?;
+                    files.insert(key, base64::encode(fs::read(&file)?));
+                }
+            } else {
+                let file = body_file.file.ok_or_else(|| anyhow!("body file '{}' not found", name))?;
+                files.insert(name.to_string(), base64::encode(fs::read(&file)?));
+            }
+        }
+        Ok(())
+    }
+
+    /// Regular files under `dir`, recursively. Symbolic links are not followed
+    fn find_all_files(dir: &Path) -> Vec<PathBuf> {
+        let mut files = vec![];
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                match entry.file_type() {
+                    Ok(t) if t.is_file() => files.push(entry.path()),
+                    Ok(t) if t.is_dir() => files.extend(Self::find_all_files(&entry.path())),
+                    _ => {}
+                }
+            }
+        }
+        files
+    }
+
+    /// Ensures names are usable as path segments and neither stubs nor body files escape their directory
+    pub(crate) fn validate(&self) -> anyhow::Result<()> {
+        Self::validate_name("app", &self.app)?;
+        Self::validate_name("version", &self.version)?;
+        self.tags.iter().try_for_each(|t| Self::validate_name("tag", t))?;
+        for key in self.stubs.keys() {
+            ensure!(Self::is_relative(key) && key.ends_with(".json"), "invalid stub path '{}'", key);
+        }
+        for key in self.files.keys() {
+            ensure!(Self::is_relative(key), "invalid body file path '{}', it must be relative to the '__files' folder", key);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn validate_name(kind: &str, name: &str) -> anyhow::Result<()> {
+        let is_valid = !name.is_empty() && !name.starts_with('.') &&
+            name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '+'));
+        ensure!(is_valid, "invalid {} '{}', only ascii alphanumerics, '.', '_', '-' and '+' are allowed", kind, name);
+        Ok(())
+    }
+
+    fn is_relative(key: &str) -> bool {
+        Path::new(key).components().all(|c| matches!(c, Component::Normal(_)))
+    }
+
+    fn key(path: &Path) -> String {
+        path.components()
+            .filter_map(|c| c.as_os_str().to_str())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn digest(&self) -> String {
+        FnvHasher::hash(&serde_json::to_vec(&(&self.stubs, &self.files)).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod contract_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn contract() -> Contract {
+        let stubs = BTreeMap::from([
+            (String::from("a.json"), json!({"request": {"method": "GET"}, "response": {"status": 200}})),
+            (String::from("b/c.json"), json!({"request": {"method": "POST"}, "response": {"status": 201}})),
+        ]);
+        Contract { app: String::from("app"), version: String::from("1.0.0"), tags: vec![], stubs, files: BTreeMap::new() }
+    }
+
+    #[test]
+    fn should_collect_stubs_recursively() {
+        let contract = Contract::from_dir("app", "1.0.0", vec![String::from("main")], Path::new("tests/stubs/recur")).unwrap();
+        assert_eq!(contract.stubs.len(), 3);
+        assert!(contract.stubs.keys().all(|k| k.ends_with(".json") && !k.starts_with('/')));
+        assert_eq!(contract.tags, vec![String::from("main")]);
+    }
+
+    #[test]
+    fn should_collect_single_file() {
+        let contract = Contract::from_dir("app", "1.0.0", vec![], Path::new("tests/stubs/server/valid.json")).unwrap();
+        assert_eq!(contract.stubs.keys().collect::<Vec<_>>(), vec!["valid.json"]);
+    }
+
+    #[test]
+    fn should_fail_when_no_stubs() {
+        assert!(Contract::from_dir("app", "1.0.0", vec![], Path::new("tests/stubs/server/unknown")).is_err());
+    }
+
+    #[test]
+    fn should_materialize_stubs() {
+        let contract = contract();
+        let dir = contract.materialize().unwrap();
+        assert!(dir.join("mappings").join("a.json").exists());
+        assert!(dir.join("mappings").join("b").join("c.json").exists());
+        let back = Contract::from_dir("app", "1.0.0", vec![], &dir.join("mappings")).unwrap();
+        assert_eq!(back, contract);
+        assert_eq!(contract.materialize().unwrap(), dir);
+    }
+
+    #[test]
+    fn should_collect_body_files() {
+        let contract = Contract::from_dir("app", "1.0.0", vec![], Path::new("tests/stubs/broker/body-files")).unwrap();
+        assert_eq!(contract.stubs.keys().collect::<Vec<_>>(), vec!["mappings/hello.json"]);
+        assert_eq!(contract.files, BTreeMap::from([(String::from("hello.txt"), base64::encode("from a file"))]));
+    }
+
+    #[test]
+    fn should_collect_every_body_file_when_templated() {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir_all(root.path().join("__files").join("pets")).unwrap();
+        fs::write(root.path().join("__files").join("pets").join("1.json"), "{}").unwrap();
+        fs::write(root.path().join("__files").join("pets").join("2.json"), "[]").unwrap();
+        let stub = json!({"request": {"method": "GET"}, "response": {"bodyFileName": "pets/{{request.pathSegments.[1]}}.json"}});
+        fs::write(root.path().join("pet.json"), stub.to_string()).unwrap();
+        let contract = Contract::from_dir("app", "1.0.0", vec![], root.path()).unwrap();
+        assert_eq!(contract.files.keys().collect::<Vec<_>>(), vec!["pets/1.json", "pets/2.json"]);
+    }
+
+    #[test]
+    fn should_fail_when_body_file_not_found() {
+        let root = tempfile::tempdir().unwrap();
+        let stub = json!({"request": {"method": "GET"}, "responses": [{"response": {"bodyFileName": "unknown.json"}}]});
+        fs::write(root.path().join("pet.json"), stub.to_string()).unwrap();
+        let err = Contract::from_dir("app", "1.0.0", vec![], root.path()).unwrap_err();
+        assert!(format!("{:#}", err).contains("body file 'unknown.json' not found"), "{:#}", err);
+    }
+
+    #[test]
+    fn should_materialize_body_files() {
+        let contract = Contract::from_dir("app", "1.0.0", vec![], Path::new("tests/stubs/broker/body-files")).unwrap();
+        let dir = contract.materialize().unwrap();
+        assert_eq!(fs::read_to_string(dir.join("__files").join("hello.txt")).unwrap(), "from a file");
+        let back = Contract::from_dir("app", "1.0.0", vec![], &dir).unwrap();
+        assert_eq!(back.files, contract.files);
+    }
+
+    #[test]
+    fn should_materialize_different_stubs_in_different_dirs() {
+        let mut other = contract();
+        other.stubs.remove("a.json");
+        assert_ne!(contract().materialize().unwrap(), other.materialize().unwrap());
+        let mut other = contract();
+        other.files.insert(String::from("a.txt"), base64::encode("a"));
+        assert_ne!(contract().materialize().unwrap(), other.materialize().unwrap());
+    }
+
+    #[test]
+    fn should_reject_invalid_names() {
+        assert!(Contract::validate_name("app", "pet-store_2.0+1").is_ok());
+        assert!(Contract::validate_name("app", "").is_err());
+        assert!(Contract::validate_name("app", "..").is_err());
+        assert!(Contract::validate_name("app", "a/b").is_err());
+        assert!(Contract::validate_name("app", "a b").is_err());
+    }
+
+    #[test]
+    fn should_reject_stubs_escaping_their_directory() {
+        let mut contract = contract();
+        contract.stubs.insert(String::from("../evil.json"), json!({}));
+        assert!(contract.validate().is_err());
+        let mut contract = self::contract();
+        contract.stubs.insert(String::from("/etc/evil.json"), json!({}));
+        assert!(contract.materialize().is_err());
+        let mut contract = self::contract();
+        contract.files.insert(String::from("../evil.txt"), base64::encode("evil"));
+        assert!(contract.materialize().is_err());
+    }
+}
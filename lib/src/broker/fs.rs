@@ -0,0 +1,147 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+
+use super::{Broker, contract::Contract, Selector};
+
+/// Broker storing contracts in a local directory, shared e.g. through a mounted volume.
+/// Layout is `<root>/<app>/versions/<version>.json`, `<root>/<app>/tags/<tag>` and `<root>/<app>/latest`
+/// the latter two containing the version they point to.
+pub struct FsBroker {
+    root: PathBuf,
+}
+
+impl FsBroker {
+    const VERSIONS_DIR: &'static str = "versions";
+    const TAGS_DIR: &'static str = "tags";
+    const LATEST_FILE: &'static str = "latest";
+
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn version_of(&self, app: &str, selector: &Selector) -> anyhow::Result<String> {
+        let app_dir = self.root.join(app);
+        match selector {
+            Selector::Latest => fs::read_to_string(app_dir.join(Self::LATEST_FILE))
+                .map_err(|_| anyhow!("no version of app '{}' published", app)),
+            Selector::Tag(tag) => {
+                Contract::validate_name("tag", tag)?;
+                fs::read_to_string(app_dir.join(Self::TAGS_DIR).join(tag))
+                    .map_err(|_| anyhow!("no version of app '{}' tagged '{}'", app, tag))
+            }
+            Selector::Version(version) => Ok(version.to_string()),
+        }.map(|v| v.trim().to_string())
+    }
+}
+
+#[async_trait]
+impl Broker for FsBroker {
+    async fn publish(&self, contract: &Contract) -> anyhow::Result<()> {
+        contract.validate()?;
+        let app_dir = self.root.join(&contract.app);
+        let versions = app_dir.join(Self::VERSIONS_DIR);
+        let tags = app_dir.join(Self::TAGS_DIR);
+        fs::create_dir_all(&versions)?;
+        fs::create_dir_all(&tags)?;
+        fs::write(versions.join(format!("{}.json", contract.version)), serde_json::to_vec(contract)?)?;
+        for tag in &contract.tags {
+            fs::write(tags.join(tag), &contract.version)?;
+        }
+        fs::write(app_dir.join(Self::LATEST_FILE), &contract.version)?;
+        Ok(())
+    }
+
+    async fn fetch(&self, app: &str, selector: &Selector) -> anyhow::Result<Contract> {
+        Contract::validate_name("app", app)?;
+        let version = self.version_of(app, selector)?;
+        Contract::validate_name("version", &version)?;
+        let file = self.root.join(app).join(Self::VERSIONS_DIR).join(format!("{}.json", version));
+        let content = fs::read(&file).map_err(|_| anyhow!("version '{}' of app '{}' not found", version, app))?;
+        serde_json::from_slice(&content).with_context(|| format!("invalid contract '{}'", file.display()))
+    }
+}
+
+#[cfg(test)]
+mod fs_broker_tests {
+    use std::collections::BTreeMap;
+
+    use async_std::task::block_on;
+    use serde_json::json;
+
+    use super::*;
+
+    fn contract(version: &str, tags: &[&str]) -> Contract {
+        Contract {
+            app: String::from("app"),
+            version: version.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            stubs: BTreeMap::from([(String::from("a.json"), json!({"response": {"body": version}}))]),
+            files: BTreeMap::new(),
+        }
+    }
+
+    fn broker() -> FsBroker {
+        let root = std::env::temp_dir().join(format!("stubr-fs-broker-{}", rand::random::<u64>()));
+        FsBroker::new(root)
+    }
+
+    #[test]
+    fn should_fetch_published_version() {
+        let broker = broker();
+        block_on(async {
+            broker.publish(&contract("1.0.0", &[])).await.unwrap();
+            broker.publish(&contract("1.1.0", &[])).await.unwrap();
+            let fetched = broker.fetch("app", &Selector::Version(String::from("1.0.0"))).await.unwrap();
+            assert_eq!(fetched, contract("1.0.0", &[]));
+        });
+    }
+
+    #[test]
+    fn should_fetch_latest() {
+        let broker = broker();
+        block_on(async {
+            broker.publish(&contract("1.0.0", &["main"])).await.unwrap();
+            broker.publish(&contract("2.0.0", &["feature"])).await.unwrap();
+            assert_eq!(broker.fetch("app", &Selector::Latest).await.unwrap().version, "2.0.0");
+        });
+    }
+
+    #[test]
+    fn should_fetch_latest_of_tag() {
+        let broker = broker();
+        block_on(async {
+            broker.publish(&contract("1.0.0", &["main"])).await.unwrap();
+            broker.publish(&contract("1.1.0", &["main", "prod"])).await.unwrap();
+            broker.publish(&contract("2.0.0", &["feature"])).await.unwrap();
+            assert_eq!(broker.fetch("app", &Selector::Tag(String::from("main"))).await.unwrap().version, "1.1.0");
+            assert_eq!(broker.fetch("app", &Selector::Tag(String::from("prod"))).await.unwrap().version, "1.1.0");
+            assert_eq!(broker.fetch("app", &Selector::Tag(String::from("feature"))).await.unwrap().version, "2.0.0");
+        });
+    }
+
+    #[test]
+    fn should_fail_when_nothing_published() {
+        let broker = broker();
+        block_on(async {
+            let err = broker.fetch("app", &Selector::Latest).await.unwrap_err();
+            assert_eq!(err.to_string(), "no version of app 'app' published");
+            broker.publish(&contract("1.0.0", &[])).await.unwrap();
+            let err = broker.fetch("app", &Selector::Tag(String::from("main"))).await.unwrap_err();
+            assert_eq!(err.to_string(), "no version of app 'app' tagged 'main'");
+            let err = broker.fetch("app", &Selector::Version(String::from("9.9.9"))).await.unwrap_err();
+            assert_eq!(err.to_string(), "version '9.9.9' of app 'app' not found");
+        });
+    }
+
+    #[test]
+    fn should_reject_paths_as_names() {
+        let broker = broker();
+        block_on(async {
+            assert!(broker.fetch("../app", &Selector::Latest).await.is_err());
+            assert!(broker.fetch("app", &Selector::Version(String::from("../../x"))).await.is_err());
+            assert!(broker.publish(&contract("../1.0.0", &[])).await.is_err());
+        });
+    }
+}
@@ -0,0 +1,72 @@
+use anyhow::{anyhow, ensure};
+use async_trait::async_trait;
+use isahc::{AsyncReadResponseExt, http::Request};
+
+use super::{Broker, contract::Contract, Selector};
+
+/// Client of a remote broker e.g. a [super::server::BrokerServer]
+pub struct HttpBroker {
+    base: String,
+}
+
+impl HttpBroker {
+    pub fn new(uri: &str) -> Self {
+        Self { base: uri.trim_end_matches('/').to_string() }
+    }
+
+    fn version_uri(&self, app: &str, version: &str) -> String {
+        format!("{}/contracts/{}/versions/{}", self.base, app, version)
+    }
+
+    fn fetch_uri(&self, app: &str, selector: &Selector) -> String {
+        match selector {
+            Selector::Latest => format!("{}/contracts/{}/latest", self.base, app),
+            Selector::Tag(tag) => format!("{}/contracts/{}/tags/{}", self.base, app, tag),
+            Selector::Version(version) => self.version_uri(app, version),
+        }
+    }
+}
+
+#[async_trait]
+impl Broker for HttpBroker {
+    async fn publish(&self, contract: &Contract) -> anyhow::Result<()> {
+        contract.validate()?;
+        let uri = self.version_uri(&contract.app, &contract.version);
+        let req = Request::put(&uri)
+            .header("content-type", "application/json")
+            .body(serde_json::to_vec(contract)?)?;
+        let mut resp = isahc::send_async(req).await?;
+        let status = resp.status();
+        let body = resp.bytes().await.unwrap_or_default();
+        ensure!(status.is_success(), "broker answered {} to 'PUT {}': {}", status, uri, String::from_utf8_lossy(&body));
+        Ok(())
+    }
+
+    async fn fetch(&self, app: &str, selector: &Selector) -> anyhow::Result<Contract> {
+        Contract::validate_name("app", app)?;
+        if let Selector::Tag(name) | Selector::Version(name) = selector {
+            Contract::validate_name("selector", name)?;
+        }
+        let uri = self.fetch_uri(app, selector);
+        let mut resp = isahc::get_async(&uri).await?;
+        let status = resp.status();
+        let body = resp.bytes().await?;
+        if !status.is_success() {
+            return Err(anyhow!("broker answered {} to 'GET {}': {}", status, uri, String::from_utf8_lossy(&body)));
+        }
+        Ok(serde_json::from_slice(&body)?)
+    }
+}
+
+#[cfg(test)]
+mod http_broker_tests {
+    use super::*;
+
+    #[test]
+    fn should_build_uris() {
+        let broker = HttpBroker::new("http://localhost:8080/");
+        assert_eq!(broker.fetch_uri("app", &Selector::Latest), "http://localhost:8080/contracts/app/latest");
+        assert_eq!(broker.fetch_uri("app", &Selector::Tag(String::from("main"))), "http://localhost:8080/contracts/app/tags/main");
+        assert_eq!(broker.fetch_uri("app", &Selector::Version(String::from("1.0.0"))), "http://localhost:8080/contracts/app/versions/1.0.0");
+    }
+}
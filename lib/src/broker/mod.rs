@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+
+use contract::Contract;
+use fs::FsBroker;
+use http::HttpBroker;
+
+pub mod contract;
+pub mod fs;
+pub mod http;
+pub mod server;
+
+/// Which published version of an app to fetch from a broker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selector {
+    /// Last published version, whatever its tags
+    Latest,
+    /// Last published version bearing this tag e.g. `main`
+    Tag(String),
+    /// This exact version
+    Version(String),
+}
+
+impl Selector {
+    /// Tag from `STUBR_BROKER_TAG` or else latest
+    pub fn from_env() -> Self {
+        std::env::var(AnyBroker::TAG_ENV).ok()
+            .filter(|t| !t.is_empty())
+            .map(Self::Tag)
+            .unwrap_or(Self::Latest)
+    }
+}
+
+/// Stores stubs published by producers for consumers to fetch them
+#[async_trait]
+pub trait Broker {
+    /// Publishes a version of an app's stubs. Republishing a version overwrites it.
+    async fn publish(&self, contract: &Contract) -> anyhow::Result<()>;
+    /// Fetches an app's stubs
+    async fn fetch(&self, app: &str, selector: &Selector) -> anyhow::Result<Contract>;
+}
+
+/// Either a local directory or a remote http broker
+pub enum AnyBroker {
+    Fs(FsBroker),
+    Http(HttpBroker),
+}
+
+impl AnyBroker {
+    /// Broker location: a directory or an http(s) uri
+    pub const BROKER_ENV: &'static str = "STUBR_BROKER";
+    /// Tag of the version to fetch, latest one when absent
+    pub const TAG_ENV: &'static str = "STUBR_BROKER_TAG";
+
+    /// Broker from `STUBR_BROKER`, if any
+    pub fn from_env() -> Option<Self> {
+        std::env::var(Self::BROKER_ENV).ok()
+            .filter(|b| !b.is_empty())
+            .map(|b| Self::from(b.as_str()))
+    }
+}
+
+impl From<&str> for AnyBroker {
+    fn from(location: &str) -> Self {
+        if location.starts_with("http://") || location.starts_with("https://") {
+            Self::Http(HttpBroker::new(location))
+        } else {
+            Self::Fs(FsBroker::new(location))
+        }
+    }
+}
+
+#[async_trait]
+impl Broker for AnyBroker {
+    async fn publish(&self, contract: &Contract) -> anyhow::Result<()> {
+        match self {
+            Self::Fs(b) => b.publish(contract).await,
+            Self::Http(b) => b.publish(contract).await,
+        }
+    }
+
+    async fn fetch(&self, app: &str, selector: &Selector) -> anyhow::Result<Contract> {
+        match self {
+            Self::Fs(b) => b.fetch(app, selector).await,
+            Self::Http(b) => b.fetch(app, selector).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod broker_tests {
+    use super::*;
+
+    #[test]
+    fn should_pick_http_broker_from_uri() {
+        assert!(matches!(AnyBroker::from("http://localhost:8080"), AnyBroker::Http(_)));
+        assert!(matches!(AnyBroker::from("https://broker.io"), AnyBroker::Http(_)));
+    }
+
+    #[test]
+    fn should_pick_fs_broker_otherwise() {
+        assert!(matches!(AnyBroker::from("/tmp/broker"), AnyBroker::Fs(_)));
+        assert!(matches!(AnyBroker::from("broker"), AnyBroker::Fs(_)));
+    }
+}
@@ -0,0 +1,97 @@
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+use log::{error, info};
+use tokio::sync::mpsc::{channel, Sender};
+use warp::{Filter, http::StatusCode, Rejection, Reply, reply};
+
+use super::{Broker, contract::Contract, fs::FsBroker, Selector};
+
+/// Http broker backed by a [FsBroker], to be used with a [super::http::HttpBroker].
+/// Requires a tokio runtime. Stops when dropped.
+///
+/// * `PUT /contracts/{app}/versions/{version}` publishes a [Contract]
+/// * `GET /contracts/{app}/latest`, `GET /contracts/{app}/tags/{tag}` or `GET /contracts/{app}/versions/{version}` fetches one
+pub struct BrokerServer {
+    addr: SocketAddr,
+    tx: Sender<()>,
+}
+
+impl BrokerServer {
+    #[cfg(feature = "cloud")]
+    const HOST: [u8; 4] = [0, 0, 0, 0];
+
+    #[cfg(not(feature = "cloud"))]
+    const HOST: [u8; 4] = [127, 0, 0, 1];
+
+    /// Serves contracts stored under `root` on a random port
+    pub fn start(root: impl Into<PathBuf>) -> Self {
+        Self::start_on(root, None)
+    }
+
+    /// Serves contracts stored under `root`
+    /// * `port` - random when absent
+    pub fn start_on(root: impl Into<PathBuf>, port: Option<u16>) -> Self {
+        let broker = Arc::new(FsBroker::new(root));
+        let (tx, mut rx) = channel::<()>(1);
+        let server = warp::serve(Self::routes(broker));
+        let (addr, server) = server.bind_with_graceful_shutdown((Self::HOST, port.unwrap_or_default()), async move { rx.recv().await; });
+        tokio::spawn(server);
+        info!("Started stubr broker on {}", addr);
+        Self { addr, tx }
+    }
+
+    /// Get broker base uri
+    pub fn uri(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    fn routes(broker: Arc<FsBroker>) -> impl Filter<Extract=(impl Reply, ), Error=Rejection> + Clone {
+        let with_broker = warp::any().map(move || broker.clone());
+        let publish = warp::put()
+            .and(warp::path!("contracts" / String / "versions" / String))
+            .and(warp::body::json())
+            .and(with_broker.clone())
+            .and_then(Self::publish);
+        let latest = warp::path!("contracts" / String / "latest")
+            .map(|app| (app, Selector::Latest))
+            .untuple_one();
+        let tag = warp::path!("contracts" / String / "tags" / String)
+            .map(|app, tag| (app, Selector::Tag(tag)))
+            .untuple_one();
+        let version = warp::path!("contracts" / String / "versions" / String)
+            .map(|app, version| (app, Selector::Version(version)))
+            .untuple_one();
+        let fetch = warp::get()
+            .and(latest.or(tag).unify().or(version).unify())
+            .and(with_broker)
+            .and_then(Self::fetch);
+        publish.or(fetch)
+    }
+
+    async fn publish(app: String, version: String, mut contract: Contract, broker: Arc<FsBroker>) -> Result<reply::Response, Rejection> {
+        contract.app = app;
+        contract.version = version;
+        Ok(match broker.publish(&contract).await {
+            Ok(()) => {
+                info!("Published version '{}' of app '{}'", contract.version, contract.app);
+                StatusCode::CREATED.into_response()
+            }
+            Err(e) => reply::with_status(format!("{:#}", e), StatusCode::BAD_REQUEST).into_response(),
+        })
+    }
+
+    async fn fetch(app: String, selector: Selector, broker: Arc<FsBroker>) -> Result<reply::Response, Rejection> {
+        Ok(match broker.fetch(&app, &selector).await {
+            Ok(contract) => reply::json(&contract).into_response(),
+            Err(e) => reply::with_status(format!("{:#}", e), StatusCode::NOT_FOUND).into_response(),
+        })
+    }
+}
+
+impl Drop for BrokerServer {
+    fn drop(&mut self) {
+        if let Err(e) = self.tx.try_send(()) {
+            error!("Failed stopping stubr broker on {} because {:?}", self.addr, e)
+        }
+    }
+}
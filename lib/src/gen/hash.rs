@@ -0,0 +1,26 @@
+/// 64 bits FNV-1a. Unlike [std::collections::hash_map::DefaultHasher] it is guaranteed not
+/// to change with the Rust version, so it can name files which outlive a build
+pub struct FnvHasher;
+
+impl FnvHasher {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    /// Hash as 16 hexadecimal digits
+    pub fn hash(bytes: &[u8]) -> String {
+        let hash = bytes.iter().fold(Self::OFFSET, |h, b| (h ^ *b as u64).wrapping_mul(Self::PRIME));
+        format!("{:016x}", hash)
+    }
+}
+
+#[cfg(test)]
+mod fnv_hasher_tests {
+    use super::*;
+
+    #[test]
+    fn should_hash_like_reference_implementation() {
+        assert_eq!(FnvHasher::hash(b""), "cbf29ce484222325");
+        assert_eq!(FnvHasher::hash(b"a"), "af63dc4c8601ec8c");
+        assert_eq!(FnvHasher::hash(b"foobar"), "85944171f73967e8");
+    }
+}
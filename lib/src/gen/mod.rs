@@ -1,2 +1,4 @@
 pub mod string;
-pub mod regex;
\ No newline at end of file
+pub mod regex;
+#[cfg(any(feature = "record-standalone", feature = "record-actix", feature = "record-tower", feature = "record-isahc", feature = "record-reqwest", feature = "record-reqwest-middleware", feature = "record-surf", feature = "broker"))]
+pub mod hash;
\ No newline at end of file
@@ -1,17 +1,23 @@
+use std::cell::Cell;
+
 use rand::{Rng, SeedableRng};
 use rand_xorshift::XorShiftRng;
 use regex_syntax::ParserBuilder;
 
+thread_local! {
+    static CONFIG: Cell<RegexGenConfig> = Cell::new(RegexGenConfig::default());
+}
+
 /// Generates random data given a regex
 pub struct RegexRndGenerator<'a>(pub &'a str);
 
 impl RegexRndGenerator<'_> {
     pub fn try_generate(self) -> anyhow::Result<String> {
-        const MAX_REPEAT: u32 = 10;
-        let mut rng = XorShiftRng::seed_from_u64(42);
+        let RegexGenConfig { seed, max_repeat } = CONFIG.with(Cell::get);
+        let mut rng = XorShiftRng::seed_from_u64(seed);
         let mut parser = ParserBuilder::new().unicode(false).build();
         let hir = parser.parse(self.0)?;
-        let gen = rand_regex::Regex::with_hir(hir, MAX_REPEAT)?;
+        let gen = rand_regex::Regex::with_hir(hir, max_repeat)?;
         (&mut rng).sample_iter(&gen)
             .take(1)
             .next()
@@ -19,6 +25,34 @@ impl RegexRndGenerator<'_> {
     }
 }
 
+/// Tunes [RegexRndGenerator] for the current thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegexGenConfig {
+    pub seed: u64,
+    /// Upper bound for unbounded repetitions e.g. `*` or `+`
+    pub max_repeat: u32,
+}
+
+impl RegexGenConfig {
+    pub const DEFAULT_SEED: u64 = 42;
+    pub const DEFAULT_MAX_REPEAT: u32 = 10;
+
+    /// Generates with this configuration within `f` then restores the previous one
+    #[cfg(any(test, feature = "verify"))]
+    pub fn scope<T>(self, f: impl FnOnce() -> T) -> T {
+        let previous = CONFIG.with(|c| c.replace(self));
+        let out = f();
+        CONFIG.with(|c| c.set(previous));
+        out
+    }
+}
+
+impl Default for RegexGenConfig {
+    fn default() -> Self {
+        Self { seed: Self::DEFAULT_SEED, max_repeat: Self::DEFAULT_MAX_REPEAT }
+    }
+}
+
 #[cfg(test)]
 mod verify_regex_tests {
     use super::*;
@@ -29,4 +63,40 @@ mod verify_regex_tests {
         assert_eq!(sample.len(), 10);
         assert!(sample.chars().all(|c| c.is_ascii()))
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn should_be_deterministic_for_a_seed() {
+        let generate = |seed| RegexGenConfig { seed, ..Default::default() }
+            .scope(|| RegexRndGenerator("[a-z]{20}").try_generate().unwrap());
+        assert_eq!(generate(1), generate(1));
+        assert_ne!(generate(1), generate(2));
+    }
+
+    #[test]
+    fn default_should_be_seeded_with_42() {
+        let default = RegexRndGenerator("[a-z]{20}").try_generate().unwrap();
+        let seeded = RegexGenConfig { seed: 42, ..Default::default() }
+            .scope(|| RegexRndGenerator("[a-z]{20}").try_generate().unwrap());
+        assert_eq!(default, seeded);
+    }
+
+    #[test]
+    fn should_cap_repetitions() {
+        let sample = RegexGenConfig { seed: 3, max_repeat: 2 }
+            .scope(|| RegexRndGenerator("a+").try_generate().unwrap());
+        assert!((1..=3).contains(&sample.len()), "{}", sample);
+        let sample = RegexGenConfig { max_repeat: 0, ..Default::default() }
+            .scope(|| RegexRndGenerator("a*").try_generate().unwrap());
+        assert!(sample.is_empty());
+    }
+
+    #[test]
+    fn scope_should_restore_previous_config() {
+        let before = RegexRndGenerator("[a-z]{20}").try_generate().unwrap();
+        RegexGenConfig { seed: 7, ..Default::default() }.scope(|| {
+            RegexGenConfig { seed: 8, ..Default::default() }.scope(|| ());
+            assert_eq!(CONFIG.with(Cell::get).seed, 7);
+        });
+        assert_eq!(RegexRndGenerator("[a-z]{20}").try_generate().unwrap(), before);
+    }
+}
@@ -4,6 +4,7 @@
 //! [wiremock-rs](https://github.com/LukeMathWalker/wiremock-rs) mocks.
 //!
 //! You can also use [stubr-build](https://crates.io/crates/stubr-build) to share stubs between a producer project and a consumer one.
+//! Or, with the `broker` feature, publish them to a [`Broker`] from which [`Stubr::app`] fetches them.
 //!
 //! Also available as a [cli](https://crates.io/crates/stubr-cli).
 //!
@@ -69,6 +70,12 @@
 //!     global_delay: Some(2000),
 //!     // delay in milliseconds added to any locally defined one. Simulates network latencies.
 //!     latency: Some(2000),
+//!     // user-defined handlebars helpers, see [`StubrHelper`]
+//!     helpers: Default::default(),
+//!     // signs tokens of the `jwt` response template helper, see [`JwtKey`]
+//!     jwt_key: None,
+//!     // Wiremock's root directory, holding the '__files' folder
+//!     root_dir: None,
 //! };
 //! ```
 //!
@@ -78,18 +85,33 @@
 #[macro_use]
 extern crate lazy_static;
 
+#[cfg(feature = "broker")]
+pub use broker::{AnyBroker, Broker, contract::Contract, fs::FsBroker, http::HttpBroker, Selector, server::BrokerServer};
 #[cfg(all(feature = "record-standalone", feature = "record-isahc"))]
 pub use record::client::isahc_client;
 #[cfg(all(feature = "record-standalone", feature = "record-reqwest"))]
 pub use record::client::reqwest_client;
-#[cfg(any(feature = "record-actix", feature = "record-standalone"))]
-pub use record::config::RecordConfig;
+#[cfg(any(feature = "record-actix", feature = "record-tower", feature = "record-standalone", feature = "record-reqwest-middleware", feature = "record-surf"))]
+pub use record::config::{BodyMatching, Dedupe, JsonPaths, MatcherStrategy, PathRegex, RecordConfig, RecordFilter, RedactConfig, StubNaming, UrlMatching};
 #[cfg(feature = "record-standalone")]
 pub use record::core::Record;
 #[cfg(feature = "record-actix")]
 pub use record::record_client::actix::{ActixRecord, ActixRecordMiddleware};
+#[cfg(feature = "record-tower")]
+pub use record::record_client::tower::{TowerRecord, TowerRecordService};
+#[cfg(feature = "record-reqwest-middleware")]
+pub use record::record_client::reqwest_middleware::ReqwestRecordMiddleware;
+#[cfg(feature = "record-surf")]
+pub use record::record_client::surf::SurfRecord;
+#[cfg(feature = "record-hyper")]
+pub use record::record_client::hyper::{HyperRecord, HyperRecordClient};
 #[cfg(feature = "record-standalone")]
 pub use record::standalone::StubrRecord;
+pub use model::response::template::helpers::{custom::{HelperArgs, Helpers, StubrHelper}, jwt::JwtKey};
+#[cfg(feature = "helpers-rhai")]
+pub use model::response::template::helpers::rhai::RhaiHelper;
+#[cfg(feature = "helpers-lua")]
+pub use model::response::template::helpers::lua::LuaHelper;
 pub use server::{config::Config, Stubr};
 #[cfg(feature = "attributes")]
 pub use stubr_attributes::apps;
@@ -100,13 +122,15 @@ pub use stubr_attributes::record;
 #[cfg(feature = "verify-actix")]
 pub use verify::actix::lifecycle::ActixVerifyLifecycle;
 #[cfg(feature = "verify")]
-pub use verify::{StubrVerify, VerifyExcept};
+pub use verify::{generator::GeneratorOptions, report::{Mismatch, MismatchKind, VerifyReport}, states::ProviderStates, StubrVerify, VerifyExcept, VerifyOptions};
 
 mod model;
 mod server;
 mod cloud;
-#[cfg(any(feature = "record-standalone", feature = "record-actix", feature = "record-isahc", feature = "record-reqwest"))]
+#[cfg(any(feature = "record-standalone", feature = "record-actix", feature = "record-tower", feature = "record-isahc", feature = "record-reqwest", feature = "record-reqwest-middleware", feature = "record-surf"))]
 mod record;
 #[cfg(feature = "verify")]
 mod verify;
-mod gen;
\ No newline at end of file
+mod gen;
+#[cfg(feature = "broker")]
+mod broker;
\ No newline at end of file
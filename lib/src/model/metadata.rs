@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+/// Arbitrary informations attached to a stub which do not alter its behaviour
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct MetadataStub {
+    /// Provider state (e.g. 'pet 1 exists') the application has to be in before replaying this stub during verification
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+}
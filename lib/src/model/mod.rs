@@ -1,17 +1,26 @@
-use std::{fs::OpenOptions, path::PathBuf};
+use std::{fs::OpenOptions, hash::{Hash, Hasher}, path::{Path, PathBuf}};
 
+use anyhow::{anyhow, ensure};
 use serde::{Deserialize, Serialize};
-use wiremock::{Mock, MockBuilder, Respond, ResponseTemplate};
+use wiremock::{Mock, MockBuilder, ResponseTemplate};
 
+use metadata::MetadataStub;
 use request::RequestStub;
-use response::{default::WiremockIsoResponse, delay::Delay, ResponseAppender, ResponseStub, template::{HandlebarTemplatable, StubTemplate}};
+use response::{default::WiremockIsoResponse, delay::Delay, ResponseAppender, ResponseStub, template::{HandlebarTemplatable, StubTemplate, TemplateRenderer, Templates}};
+use scenario::{Scenarios, ScenarioStub, Transitioning};
+use sequence::{SequencePolicy, Sequences, StubSequence};
+use variant::ResponseVariantStub;
 
 use crate::Config;
 
 pub mod request;
 pub mod response;
+pub mod metadata;
+pub mod scenario;
+pub mod sequence;
+pub mod variant;
 
-#[derive(Serialize, Deserialize, Debug, Hash)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct JsonStub {
     #[serde(skip_serializing)]
     pub id: Option<String>,
@@ -20,38 +29,123 @@ pub struct JsonStub {
     #[serde(skip_serializing)]
     pub priority: Option<u8>,
     pub request: RequestStub,
-    pub response: ResponseStub,
+    /// Required unless there is a `sequence`, in which case it is not allowed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<ResponseStub>,
+    /// Conditional responses tried in order before falling back to `response`.
+    /// With a `sequence`, they are served in turn instead. Producer verification only checks the default `response`,
+    /// or the first one of a sequence
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub responses: Vec<ResponseVariantStub>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<SequencePolicy>,
+    #[serde(flatten)]
+    pub scenario: ScenarioStub,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<MetadataStub>,
 }
 
 impl JsonStub {
     pub const DEFAULT_PRIORITY: u8 = 5;
 
-    pub(crate) fn try_creating_from(self, config: &Config) -> anyhow::Result<Mock> {
-        Ok(MockBuilder::try_from(&self.request)?.respond_with(self.into_respond(config)))
+    /// Provider state required by this stub, if any
+    #[cfg(feature = "verify")]
+    pub fn state(&self) -> Option<&str> {
+        self.metadata.as_ref().and_then(|m| m.state.as_deref())
     }
 
-    pub fn into_respond<'a>(self, config: &Config) -> impl Respond + 'a {
-        let mut template = ResponseTemplate::new(self.response.status());
-        template = WiremockIsoResponse(&self).add(template);
-        template = Delay(&self, config).add(template);
-        if self.response.requires_response_templating() {
-            self.response.headers.register_template();
-            self.response.body.register_template();
-            StubTemplate { template, response: self.response, requires_templating: true }
+    /// Response a producer is verified against: the default one or, for a sequence, its first one
+    #[cfg(feature = "verify")]
+    pub fn verified_response(&self) -> Option<&ResponseStub> {
+        match self.sequence {
+            Some(_) => self.responses.first().map(|v| &v.response),
+            None => self.response.as_ref(),
+        }
+    }
+
+    /// Also looks up body files of every response in Wiremock's '__files' folder
+    pub(crate) fn with_files_dir(mut self, files_dir: Option<&Path>) -> Self {
+        if let Some(response) = self.response.as_mut() {
+            response.body.with_files_dir(files_dir);
+        }
+        self.responses.iter_mut().for_each(|v| v.response.body.with_files_dir(files_dir));
+        self
+    }
+
+    /// One mock per response variant, in order, then one for the default response.
+    /// A single one serving every response in turn when they are a sequence
+    pub(crate) fn try_creating_from(self, config: &Config, scenarios: &Scenarios, sequences: &Sequences, templates: &Templates) -> anyhow::Result<Vec<Mock>> {
+        let Self { uuid, request, response, responses, sequence, scenario, .. } = self;
+        let base = || -> anyhow::Result<MockBuilder> {
+            let mut mock = MockBuilder::try_from(&request)?;
+            if let Some(matcher) = scenario.matcher(scenarios) {
+                mock = mock.and(matcher);
+            }
+            Ok(mock)
+        };
+        if let Some(policy) = sequence {
+            ensure!(response.is_none(), "A stub with a sequence cannot also have a 'response'");
+            ensure!(!responses.is_empty(), "A stub with a sequence requires 'responses'");
+            ensure!(responses.iter().all(ResponseVariantStub::is_unconditional), "Responses of a sequence cannot have a 'request' or a 'when'");
+            ensure!(policy != SequencePolicy::Weighted || responses.iter().any(|v| v.weight.unwrap_or(StubSequence::DEFAULT_WEIGHT) > 0),
+                "A weighted sequence requires at least one response with a positive 'weight'");
+            let responses = responses.into_iter()
+                .map(|v| (Self::respond(v.response, uuid.clone(), config, templates), v.weight.unwrap_or(StubSequence::DEFAULT_WEIGHT)))
+                .collect();
+            let respond = StubSequence::new(policy, responses, sequences);
+            return Ok(vec![base()?.respond_with(Transitioning { respond, transition: scenario.transition(scenarios) })]);
+        }
+        let respond = |response| Transitioning {
+            respond: Self::respond(response, uuid.clone(), config, templates),
+            transition: scenario.transition(scenarios),
+        };
+        let response = response.ok_or_else(|| anyhow!("A stub requires a 'response' unless it has a sequence"))?;
+        let mut mocks = responses.into_iter()
+            .map(|variant| Ok(variant.register(base()?, templates).respond_with(respond(variant.response))))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        mocks.push(base()?.respond_with(respond(response)));
+        Ok(mocks)
+    }
+
+    fn respond(response: ResponseStub, uuid: Option<String>, config: &Config, templates: &Templates) -> StubTemplate {
+        let delay = Delay::from(config);
+        let mut template = ResponseTemplate::new(response.status());
+        template = WiremockIsoResponse(uuid.as_deref()).add(template);
+        template = delay.add(template, response.fixed_delay());
+        if response.requires_response_templating() {
+            let mut renderer = TemplateRenderer::new(templates.clone());
+            response.headers.register_template(&mut renderer);
+            response.body.register_template(&mut renderer);
+            response.register_template(&mut renderer);
+            StubTemplate { template, response, renderer: Some(renderer), uuid, delay }
         } else {
-            template = self.response.headers.add(template);
-            template = self.response.body.add(template);
-            StubTemplate { template, response: self.response, requires_templating: false }
+            template = response.headers.add(template);
+            template = response.body.add(template);
+            StubTemplate { template, response, renderer: None, uuid, delay }
         }
     }
 }
 
+/// Metadata does not change how a stub matches nor responds, hence is left out
+impl Hash for JsonStub {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.uuid.hash(state);
+        self.priority.hash(state);
+        self.request.hash(state);
+        self.response.hash(state);
+        self.scenario.hash(state);
+    }
+}
+
 impl TryFrom<&PathBuf> for JsonStub {
     type Error = anyhow::Error;
 
     fn try_from(maybe_stub: &PathBuf) -> anyhow::Result<Self> {
         let file = OpenOptions::new().read(true).open(&maybe_stub)?;
-        serde_json::from_reader(file).map_err(anyhow::Error::msg)
+        let stub: Self = serde_json::from_reader(file).map_err(anyhow::Error::msg)?;
+        ensure!(stub.response.is_some() || stub.sequence.is_some(), "A stub requires a 'response' unless it has a sequence");
+        Ok(stub)
     }
 }
 
@@ -62,7 +156,11 @@ impl Default for JsonStub {
             uuid: Option::default(),
             priority: Some(Self::DEFAULT_PRIORITY),
             request: RequestStub::default(),
-            response: ResponseStub::default(),
+            response: Some(ResponseStub::default()),
+            responses: vec![],
+            sequence: None,
+            scenario: ScenarioStub::default(),
+            metadata: None,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,30 @@
+use regex::Regex;
+use serde_json::{from_slice as deserialize, Value};
+use wiremock::{Match, Request};
+
+use super::{
+    BodyPatternStub,
+    super::json::{json_path_matches::JsonPathMatchesMatcher, JsonMatcher},
+};
+
+pub struct JsonBodyPathMatchesMatcher(String, Regex);
+
+impl Match for JsonBodyPathMatchesMatcher {
+    fn matches(&self, req: &Request) -> bool {
+        deserialize::<Value>(&req.body).ok().as_ref()
+            .map(|json| JsonPathMatchesMatcher(&self.0, &self.1).matches(json))
+            .unwrap_or_default()
+    }
+}
+
+impl TryFrom<&BodyPatternStub> for JsonBodyPathMatchesMatcher {
+    type Error = anyhow::Error;
+
+    fn try_from(body: &BodyPatternStub) -> anyhow::Result<Self> {
+        body.expression.as_ref()
+            .filter(|_| body.is_by_json_path_matches())
+            .and_then(|path| body.matches.as_ref().map(|regex| (path, regex)))
+            .map(|(path, regex)| Ok(Self(path.to_string(), Regex::new(regex)?)))
+            .unwrap_or_else(|| Err(anyhow::Error::msg("")))
+    }
+}
@@ -12,7 +12,9 @@ mod eq_relaxed;
 mod json_path;
 mod json_path_eq;
 mod json_path_contains;
+mod json_path_matches;
 mod binary_eq;
+mod text_eq;
 
 #[derive(Serialize, Deserialize, Debug, Default, Eq, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -21,17 +23,23 @@ pub struct BodyPatternStub {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub equal_to_json: Option<Value>,
     /// json path matcher
-    #[serde(skip_serializing)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub matches_json_path: Option<String>,
-    /// json path matcher when combined with 'equal_to_json' or 'contains'
-    #[serde(skip_serializing)]
+    /// json path matcher when combined with 'equal_to_json', 'contains' or 'matches'
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub expression: Option<String>,
     /// if matched json path also contains given string
-    #[serde(skip_serializing)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub contains: Option<String>,
+    /// if matched json path is a string matching given regex
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matches: Option<String>,
     /// strict equality by bytes comparison
     #[serde(skip_serializing_if = "Option::is_none")]
     pub binary_equal_to: Option<String>,
+    /// strict equality with a plain text body
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub equal_to: Option<String>,
     /// used alongside [equalToJson].
     /// Instructs stubr not to fail when extra fields are present in request body.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -66,10 +74,21 @@ impl BodyPatternStub {
             && self.contains.is_some()
     }
 
+    fn is_by_json_path_matches(&self) -> bool {
+        self.expression.is_some()
+            && self.matches.is_some()
+    }
+
     fn is_by_binary_equality(&self) -> bool {
         self.binary_equal_to.is_some()
     }
 
+    fn is_by_text_equality(&self) -> bool {
+        self.equal_to.is_some()
+            && self.binary_equal_to.is_none()
+            && self.expression.is_none()
+    }
+
     fn is_ignore_extra_elements(&self) -> bool {
         self.ignore_extra_elements.unwrap_or_default()
     }
@@ -97,9 +116,15 @@ impl MockRegistrable for Vec<BodyPatternStub> {
             if let Ok(json_path_contains) = json_path_contains::JsonBodyPathContainsMatcher::try_from(body_pattern) {
                 mock = mock.and(json_path_contains)
             }
+            if let Ok(json_path_matches) = json_path_matches::JsonBodyPathMatchesMatcher::try_from(body_pattern) {
+                mock = mock.and(json_path_matches)
+            }
             if let Ok(binary_equal) = binary_eq::BinaryEqualMatcher::try_from(body_pattern) {
                 mock = mock.and(binary_equal)
             }
+            if let Ok(text_equal) = text_eq::TextEqualMatcher::try_from(body_pattern) {
+                mock = mock.and(text_equal)
+            }
         }
         mock
     }
@@ -111,7 +136,9 @@ impl PartialEq for BodyPatternStub {
             self.matches_json_path.as_ref().eq(&other.matches_json_path.as_ref()) &&
             self.expression.as_ref().eq(&other.expression.as_ref()) &&
             self.contains.as_ref().eq(&other.contains.as_ref()) &&
+            self.matches.as_ref().eq(&other.matches.as_ref()) &&
             self.binary_equal_to.as_ref().eq(&other.binary_equal_to.as_ref()) &&
+            self.equal_to.as_ref().eq(&other.equal_to.as_ref()) &&
             self.ignore_extra_elements.as_ref().eq(&other.ignore_extra_elements.as_ref()) &&
             self.ignore_array_order.as_ref().eq(&other.ignore_array_order.as_ref())
     }
@@ -0,0 +1,22 @@
+use wiremock::{Match, Request};
+
+use super::BodyPatternStub;
+
+pub struct TextEqualMatcher(String);
+
+impl Match for TextEqualMatcher {
+    fn matches(&self, req: &Request) -> bool {
+        self.0.as_bytes() == req.body.as_slice()
+    }
+}
+
+impl TryFrom<&BodyPatternStub> for TextEqualMatcher {
+    type Error = anyhow::Error;
+
+    fn try_from(body: &BodyPatternStub) -> anyhow::Result<Self> {
+        body.equal_to.as_ref()
+            .filter(|_| body.is_by_text_equality())
+            .map(|it| Self(it.to_string()))
+            .ok_or_else(|| anyhow::Error::msg(""))
+    }
+}
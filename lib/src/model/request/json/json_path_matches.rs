@@ -0,0 +1,18 @@
+use regex::Regex;
+use serde_json::Value;
+
+use super::JsonMatcher;
+
+pub struct JsonPathMatchesMatcher<'a>(pub &'a String, pub &'a Regex);
+
+impl<'a> JsonMatcher<'a> for JsonPathMatchesMatcher<'a> {
+    fn matches(&self, json: &'a Value) -> bool {
+        jsonpath_lib::select(json, self.0).ok()
+            .filter(|matched| !matched.is_empty())
+            .filter(|matched| {
+                matched.iter()
+                    .all(|it| it.as_str().map(|s| self.1.is_match(s)).unwrap_or_default())
+            })
+            .is_some()
+    }
+}
@@ -4,6 +4,7 @@ pub mod eq;
 pub mod json_path;
 pub mod json_path_eq;
 pub mod json_path_contains;
+pub mod json_path_matches;
 
 pub trait JsonMatcher<'a> {
     fn matches(&self, json: &'a Value) -> bool;
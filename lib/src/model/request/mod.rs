@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use wiremock::MockBuilder;
+use wiremock::{matchers::MethodExactMatcher, MockBuilder};
 
 use body::BodyPatternStub;
 use headers::HttpReqHeadersStub;
@@ -39,13 +39,27 @@ impl TryFrom<&RequestStub> for MockBuilder {
     type Error = anyhow::Error;
 
     fn try_from(request: &RequestStub) -> anyhow::Result<Self> {
-        let mut mock = MockBuilder::from(&request.method);
-        mock = request.url.register(mock);
-        mock = request.headers.register(mock);
-        mock = request.queries.register(mock);
-        mock = request.body_patterns.register(mock);
-        mock = request.auth.register(mock);
-        Ok(mock)
+        Ok(request.register_matchers(MockBuilder::from(&request.method)))
+    }
+}
+
+impl RequestStub {
+    /// Adds its matchers to the ones of a stub e.g. for a response variant
+    pub(crate) fn register(&self, mut mock: MockBuilder) -> MockBuilder {
+        if let Ok(method) = MethodExactMatcher::try_from(&self.method) {
+            mock = mock.and(method);
+        }
+        self.register_matchers(mock)
+    }
+
+    /// Every matcher but the method one
+    fn register_matchers(&self, mut mock: MockBuilder) -> MockBuilder {
+        mock = self.url.register(mock);
+        mock = self.headers.register(mock);
+        mock = self.queries.register(mock);
+        mock = self.body_patterns.register(mock);
+        mock = self.auth.register(mock);
+        mock
     }
 }
 
@@ -1,22 +1,16 @@
-use std::{
-    f64,
-    ffi::OsStr,
-    fs::OpenOptions,
-    io::Read,
-    path::PathBuf,
-    str::{from_utf8, FromStr},
-};
+use std::{f64, path::Path, str::FromStr};
 
 use handlebars::JsonValue;
 use itertools::Itertools;
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::{Map, Value};
 use wiremock::ResponseTemplate;
 
 use super::{
     body_file::BodyFile,
+    generated_body::GeneratedBody,
     ResponseAppender,
-    template::{data::HandlebarsData, HandlebarTemplatable},
+    template::{data::HandlebarsData, HandlebarTemplatable, TemplateRenderer},
 };
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
@@ -32,49 +26,52 @@ pub struct BodyStub {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub base_64_body: Option<String>,
     /// relative path to raw body content
-    #[serde(default, skip_serializing, deserialize_with = "deserialize_body_file")]
+    #[serde(default, skip_serializing_if = "Option::is_none", serialize_with = "serialize_body_file", deserialize_with = "deserialize_body_file")]
     pub body_file_name: Option<BodyFile>,
+    /// body of a given size e.g. `{"bytes": 10485760}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generated_body: Option<GeneratedBody>,
 }
 
 impl BodyStub {
     pub const OBJECT_IDENTIFIER: &'static str = "[object]";
     pub const ARRAY_IDENTIFIER: &'static str = "[array]";
 
-    pub fn register_json_body_template<'a, T>(&self, json_values: T) where T: Iterator<Item=&'a JsonValue> {
-        json_values.into_iter().for_each(|value| self.register_json_value_template(value));
+    pub fn register_json_body_template<'a, T>(&self, json_values: T, renderer: &mut TemplateRenderer) where T: Iterator<Item=&'a JsonValue> {
+        json_values.into_iter().for_each(|value| self.register_json_value_template(value, renderer));
     }
 
-    pub fn register_json_value_template(&self, value: &Value) {
+    pub fn register_json_value_template(&self, value: &Value, renderer: &mut TemplateRenderer) {
         match value {
-            Value::String(s) => self.register(s, s),
-            Value::Object(o) => self.register_json_body_template(o.values()),
-            Value::Array(a) => self.register_json_body_template(a.iter()),
+            Value::String(s) => renderer.register(s),
+            Value::Object(o) => self.register_json_body_template(o.values(), renderer),
+            Value::Array(a) => self.register_json_body_template(a.iter(), renderer),
             _ => {}
         }
     }
 
-    pub fn render_json_body(&self, json_body: Option<&Value>, data: &HandlebarsData) -> Option<Value> {
+    pub fn render_json_body(&self, json_body: Option<&Value>, data: &HandlebarsData, renderer: &TemplateRenderer) -> Option<Value> {
         json_body
-            .and_then(|it| it.as_object().map(|o| self.render_json_obj(o, data)))
-            .or_else(|| json_body.and_then(Value::as_array).map(|a| self.render_json_array(a, data)))
+            .and_then(|it| it.as_object().map(|o| self.render_json_obj(o, data, renderer)))
+            .or_else(|| json_body.and_then(Value::as_array).map(|a| self.render_json_array(a, data, renderer)))
     }
 
-    fn render_json_obj(&self, json_body: &Map<String, Value>, data: &HandlebarsData) -> Value {
+    fn render_json_obj(&self, json_body: &Map<String, Value>, data: &HandlebarsData, renderer: &TemplateRenderer) -> Value {
         let obj = json_body.into_iter()
             .map(|(key, value)| match value {
-                Value::String(s) => (key.to_owned(), Self::cast_to_value(self.render(s, data))),
-                Value::Object(o) => (key.to_owned(), self.render_json_obj(o, data)),
-                Value::Array(a) => (key.to_owned(), self.render_json_array(a, data)),
+                Value::String(s) => (key.to_owned(), Self::cast_to_value(renderer.render(s, data))),
+                Value::Object(o) => (key.to_owned(), self.render_json_obj(o, data, renderer)),
+                Value::Array(a) => (key.to_owned(), self.render_json_array(a, data, renderer)),
                 _ => (key.to_owned(), value.to_owned())
             });
         Value::from(Map::from_iter(obj))
     }
 
-    fn render_json_array(&self, json_body: &[Value], data: &HandlebarsData) -> Value {
+    fn render_json_array(&self, json_body: &[Value], data: &HandlebarsData, renderer: &TemplateRenderer) -> Value {
         Value::Array(json_body.iter().map(|value| match value {
-            Value::String(s) => Self::cast_to_value(self.render(s, data)),
-            Value::Object(o) => self.render_json_obj(o, data),
-            Value::Array(a) => self.render_json_array(a, data),
+            Value::String(s) => Self::cast_to_value(renderer.render(s, data)),
+            Value::Object(o) => self.render_json_obj(o, data, renderer),
+            Value::Array(a) => self.render_json_array(a, data, renderer),
             _ => value.to_owned()
         }).collect_vec())
     }
@@ -105,56 +102,73 @@ impl BodyStub {
         }
     }
 
+    /// Also looks up the body file in Wiremock's '__files' folder
+    pub fn with_files_dir(&mut self, files_dir: Option<&Path>) {
+        self.body_file_name = self.body_file_name.take().map(|file| file.with_files_dir(files_dir));
+    }
+
+    /// Served from a body file or generated rather than set when mounted, see [BodyStub::add_lazy]
+    pub(crate) fn is_lazy(&self) -> bool {
+        self.json_body.is_none() && self.body.is_none() && (self.body_file_name.is_some() || self.generated_body.is_some())
+    }
+
+    /// Reads the body file or generates the body, when there is one
+    pub(crate) fn add_lazy(&self, resp: ResponseTemplate) -> ResponseTemplate {
+        if !self.is_lazy() {
+            resp
+        } else if let Some(body_file) = self.body_file_name.as_ref() {
+            body_file.add(resp)
+        } else if let Some(generated) = self.generated_body.as_ref() {
+            generated.add(resp)
+        } else {
+            resp
+        }
+    }
+
     fn binary_body(&self) -> Option<Vec<u8>> {
         self.base_64_body.as_ref()
             .and_then(|b| base64::decode(b).ok())
     }
 }
 
+fn serialize_body_file<S>(body_file: &Option<BodyFile>, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+    body_file.as_ref().map(|f| f.path.as_str()).serialize(serializer)
+}
+
 fn deserialize_body_file<'de, D>(path: D) -> Result<Option<BodyFile>, D::Error> where D: Deserializer<'de> {
-    let body_file = String::deserialize(path).ok()
-        .map(PathBuf::from)
-        .map(|path| {
-            let path_exists = path.exists();
-            let extension = path.extension().and_then(OsStr::to_str).map(str::to_string);
-            let content = OpenOptions::new().read(true).open(&path).ok()
-                .and_then(|mut file| {
-                    let mut buf = vec![];
-                    file.read_to_end(&mut buf).map(|_| buf).ok()
-                })
-                .and_then(|bytes| from_utf8(bytes.as_slice()).map(str::to_string).ok())
-                .unwrap_or_default();
-            let path = path.to_str().map(str::to_string).unwrap_or_default();
-            BodyFile { path_exists, path, extension, content }
-        });
-    Ok(body_file)
+    Ok(String::deserialize(path).ok().map(|path| BodyFile::locate(&path, None)))
 }
 
 impl HandlebarTemplatable for BodyStub {
-    fn register_template(&self) {
+    fn register_template(&self, renderer: &mut TemplateRenderer) {
         if let Some(body) = self.body.as_ref() {
-            self.register(body, body);
+            renderer.register(body);
         } else if let Some(json_body) = self.json_body.as_ref() {
             if let Some(obj) = json_body.as_object() {
-                self.register_json_body_template(obj.values());
+                self.register_json_body_template(obj.values(), renderer);
             } else if let Some(array) = json_body.as_array() {
-                self.register_json_body_template(array.iter());
+                self.register_json_body_template(array.iter(), renderer);
             }
         } else if let Some(body_file) = self.body_file_name.as_ref() {
-            self.register(body_file.path.as_str(), &body_file.content);
+            if body_file.is_templated() {
+                renderer.register(&body_file.path);
+            } else if let Some(text) = body_file.text_template() {
+                renderer.register(text);
+            }
         }
     }
 
-    fn render_response_template(&self, mut template: ResponseTemplate, data: &HandlebarsData) -> ResponseTemplate {
+    fn render_response_template(&self, mut template: ResponseTemplate, data: &HandlebarsData, renderer: &TemplateRenderer) -> ResponseTemplate {
         if let Some(body) = self.body.as_ref() {
-            template = template.set_body_string(self.render(body, data));
+            template = template.set_body_string(renderer.render(body, data));
         } else if let Some(binary) = self.binary_body() {
             template = template.set_body_bytes(binary);
-        } else if let Some(json_body) = self.render_json_body(self.json_body.as_ref(), data) {
+        } else if let Some(json_body) = self.render_json_body(self.json_body.as_ref(), data, renderer) {
             template = template.set_body_json(json_body);
         } else if let Some(body_file) = self.body_file_name.as_ref() {
-            let rendered = self.render(body_file.path.as_str(), data);
-            template = body_file.render_templated(template, rendered);
+            template = body_file.render_templated(template, data, renderer);
+        } else if let Some(generated) = self.generated_body.as_ref() {
+            template = generated.add(template);
         }
         template
     }
@@ -166,9 +180,7 @@ impl ResponseAppender for BodyStub {
             resp = resp.set_body_json(json)
         } else if let Some(text) = self.body.as_ref() {
             resp = resp.set_body_string(text)
-        } else if let Some(body_file) = self.body_file_name.as_ref() {
-            resp = body_file.add(resp)
-        } else if let Some(binary) = self.binary_body() {
+        } else if let Some(binary) = self.binary_body().filter(|_| !self.is_lazy()) {
             resp = resp.set_body_bytes(binary)
         }
         resp
@@ -1,77 +1,302 @@
+use std::{
+    borrow::Cow,
+    ffi::OsStr,
+    fs::read,
+    fs::metadata,
+    hash::{Hash, Hasher},
+    path::{Component, Path, PathBuf},
+    str::from_utf8,
+    sync::{Arc, OnceLock},
+};
+
 use serde::Serialize;
 use serde_json::Value;
 use wiremock::ResponseTemplate;
 
-use super::ResponseAppender;
+use super::{ResponseAppender, template::{data::HandlebarsData, TemplateRenderer}};
 
+/// A file served as response body, its 'Content-Type' inferred from its extension.
+/// Files up to [BodyFile::MAX_TEMPLATE_SIZE] are read once, when compiled as a template at mount or else at
+/// their first response, then kept in memory. Larger ones are read at every response and not kept.
+/// Either way, the whole body is buffered since wiremock 0.5 does not support streaming responses
 #[derive(Serialize, Debug, Default, Clone, Eq, PartialEq, Hash)]
 pub struct BodyFile {
     pub path_exists: bool,
     pub path: String,
     pub extension: Option<String>,
-    pub content: String,
+    /// Where it was found
+    pub file: Option<PathBuf>,
+    /// Wiremock's '__files' folder, where the path is looked up when it does not exist as is
+    pub files_dir: Option<PathBuf>,
+    #[serde(skip)]
+    content: FileContent,
+}
+
+/// Content of a small body file once read, shared by its clones
+#[derive(Debug, Default, Clone)]
+struct FileContent(Arc<OnceLock<Option<Vec<u8>>>>);
+
+/// Only a cache of the file, it does not tell body files apart
+impl PartialEq for FileContent {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for FileContent {}
+
+impl Hash for FileContent {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
 }
 
 impl BodyFile {
     const JSON_EXT: &'static str = "json";
     const TEXT_EXT: &'static str = "txt";
+    const DEFAULT_MIME: &'static str = "application/octet-stream";
+    /// Largest file kept in memory and rendered as a template, larger ones are served as is
+    pub const MAX_TEMPLATE_SIZE: u64 = 64 * 1024;
+    const MIMES: [(&'static str, &'static str); 17] = [
+        ("html", "text/html"),
+        ("htm", "text/html"),
+        ("xml", "application/xml"),
+        ("csv", "text/csv"),
+        ("css", "text/css"),
+        ("js", "application/javascript"),
+        ("yaml", "application/x-yaml"),
+        ("yml", "application/x-yaml"),
+        ("md", "text/markdown"),
+        ("png", "image/png"),
+        ("jpg", "image/jpeg"),
+        ("jpeg", "image/jpeg"),
+        ("gif", "image/gif"),
+        ("svg", "image/svg+xml"),
+        ("ico", "image/x-icon"),
+        ("pdf", "application/pdf"),
+        ("zip", "application/zip"),
+    ];
 
-    fn maybe_as_json(&self) -> Option<Value> {
-        self.extension.as_deref()
-            .filter(|&ext| ext == Self::JSON_EXT)
-            .and_then(|_| serde_json::from_str::<Value>(self.content.as_str()).ok())
+    /// Finds the file at `path`, or else under `files_dir`, without reading it.
+    /// `path` comes from the stub itself hence is trusted, unlike a rendered one see [BodyFile::locate_rendered]
+    pub fn locate(path: &str, files_dir: Option<&Path>) -> Self {
+        let file = Some(PathBuf::from(path))
+            .filter(|p| p.is_file())
+            .or_else(|| files_dir.map(|dir| dir.join(path)).filter(|p| p.is_file()));
+        Self {
+            path_exists: file.is_some(),
+            path: path.to_string(),
+            extension: Path::new(path).extension().and_then(OsStr::to_str).map(str::to_string),
+            file,
+            files_dir: files_dir.map(Path::to_path_buf),
+            content: FileContent::default(),
+        }
     }
 
-    fn maybe_as_text(&self) -> Option<String> {
-        self.extension.as_deref()
-            .filter(|&ext| ext == Self::TEXT_EXT)
-            .map(|_| self.content.to_owned())
+    /// Looks it up again under `files_dir` unless it was already found
+    pub fn with_files_dir(self, files_dir: Option<&Path>) -> Self {
+        if self.path_exists || files_dir.is_none() {
+            Self { files_dir: files_dir.map(Path::to_path_buf), ..self }
+        } else {
+            Self::locate(&self.path, files_dir)
+        }
     }
 
-    fn is_json(&self) -> bool {
-        self.extension.as_deref()
-            .map(|ext| ext == Self::JSON_EXT)
-            .unwrap_or_default()
+    /// e.g. `pets/{{request.pathSegments.[1]}}.json`
+    pub fn is_templated(&self) -> bool {
+        self.path.contains("{{")
     }
 
-    fn is_text(&self) -> bool {
-        self.extension.as_deref()
-            .map(|ext| ext == Self::TEXT_EXT)
-            .unwrap_or_default()
+    /// Content of a small file, read at the first call only
+    fn cached(&self) -> Option<&[u8]> {
+        if let Some(content) = self.content.0.get() {
+            return content.as_deref();
+        }
+        let file = self.file.as_ref().filter(|f| metadata(f).map(|m| m.len() <= Self::MAX_TEMPLATE_SIZE).unwrap_or_default())?;
+        self.content.0.get_or_init(|| read(file).ok()).as_deref()
     }
-}
 
-impl BodyFile {
-    pub fn render_templated(&self, mut resp: ResponseTemplate, content: String) -> ResponseTemplate {
-        if !self.path_exists {
-            resp = ResponseTemplate::new(500)
-        } else if self.is_json() {
-            let maybe_content: Option<Value> = serde_json::from_str(&content).ok();
-            if let Some(content) = maybe_content {
+    /// Small files from memory, larger ones from disk
+    fn read(&self) -> Option<Cow<'_, [u8]>> {
+        self.cached().map(Cow::Borrowed)
+            .or_else(|| self.file.as_ref().and_then(|file| read(file).ok()).map(Cow::Owned))
+    }
+
+    /// Content of a small text file with a fixed name, to compile once and for all
+    pub(crate) fn text_template(&self) -> Option<&str> {
+        if self.is_templated() {
+            return None;
+        }
+        self.cached().and_then(|content| from_utf8(content).ok())
+    }
+
+    fn extension(&self) -> Option<String> {
+        self.extension.as_deref().map(str::to_lowercase)
+    }
+
+    fn mime(&self) -> &'static str {
+        self.extension()
+            .and_then(|ext| Self::MIMES.iter().find(|(e, _)| *e == ext))
+            .map(|(_, mime)| *mime)
+            .unwrap_or(Self::DEFAULT_MIME)
+    }
+
+    /// Finds a file name rendered from the request, only under `files_dir` and without escaping it
+    /// e.g. through '..', an absolute path or a symlink
+    fn locate_rendered(path: &str, files_dir: Option<&Path>) -> Option<Self> {
+        if !Path::new(path).components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir)) {
+            return None;
+        }
+        let dir = files_dir?.canonicalize().ok()?;
+        let file = dir.join(path).canonicalize().ok().filter(|f| f.starts_with(&dir) && f.is_file())?;
+        Some(Self {
+            path_exists: true,
+            path: path.to_string(),
+            extension: Path::new(path).extension().and_then(OsStr::to_str).map(str::to_string),
+            file: Some(file),
+            files_dir: files_dir.map(Path::to_path_buf),
+            content: FileContent::default(),
+        })
+    }
+
+    fn respond(&self, mut resp: ResponseTemplate, content: &[u8]) -> ResponseTemplate {
+        let extension = self.extension();
+        if extension.as_deref() == Some(Self::JSON_EXT) {
+            if let Ok(content) = serde_json::from_slice::<Value>(content) {
                 resp = resp.set_body_json(content);
             } else {
                 resp = ResponseTemplate::new(500)
             }
-        } else if self.is_text() {
-            resp = resp.set_body_string(content);
+        } else if extension.as_deref() == Some(Self::TEXT_EXT) {
+            resp = resp.set_body_string(String::from_utf8_lossy(content));
         } else {
-            resp = ResponseTemplate::new(500)
+            resp = resp.set_body_raw(content, self.mime());
         }
         resp
     }
+
+    pub fn render_templated(&self, resp: ResponseTemplate, data: &HandlebarsData, renderer: &TemplateRenderer) -> ResponseTemplate {
+        let file = if self.is_templated() {
+            match Self::locate_rendered(&renderer.render(&self.path, data), self.files_dir.as_deref()) {
+                Some(file) => Cow::Owned(file),
+                None => return ResponseTemplate::new(500),
+            }
+        } else {
+            Cow::Borrowed(self)
+        };
+        // precompiled when registered with its text template, otherwise compiled on the fly
+        if let Some(text) = file.cached().and_then(|content| from_utf8(content).ok()) {
+            return file.respond(resp, renderer.render(text, data).as_bytes());
+        }
+        file.add(resp)
+    }
 }
 
 impl ResponseAppender for BodyFile {
-    fn add(&self, mut resp: ResponseTemplate) -> ResponseTemplate {
-        if !self.path_exists {
-            resp = ResponseTemplate::new(500)
-        } else if let Some(json) = self.maybe_as_json() {
-            resp = resp.set_body_json(json);
-        } else if let Some(text) = self.maybe_as_text() {
-            resp = resp.set_body_string(text);
-        } else {
-            resp = ResponseTemplate::new(500)
+    fn add(&self, resp: ResponseTemplate) -> ResponseTemplate {
+        match self.read() {
+            Some(content) => self.respond(resp, &content),
+            None => ResponseTemplate::new(500),
         }
-        resp
+    }
+}
+
+#[cfg(test)]
+mod body_file_tests {
+    use super::*;
+
+    #[test]
+    fn should_infer_mime_from_extension() {
+        let mime = |path: &str| BodyFile { path: path.to_string(), extension: Path::new(path).extension().and_then(OsStr::to_str).map(str::to_string), ..Default::default() }.mime();
+        assert_eq!(mime("a.xml"), "application/xml");
+        assert_eq!(mime("a.HTML"), "text/html");
+        assert_eq!(mime("a.png"), "image/png");
+        assert_eq!(mime("a.pdf"), "application/pdf");
+        assert_eq!(mime("a.unknown"), BodyFile::DEFAULT_MIME);
+        assert_eq!(mime("a"), BodyFile::DEFAULT_MIME);
+    }
+
+    #[test]
+    fn should_locate_under_files_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("pet.json"), "{}").unwrap();
+        assert!(!BodyFile::locate("pet.json", None).path_exists);
+        let file = BodyFile::locate("pet.json", Some(dir.path()));
+        assert!(file.path_exists);
+        assert_eq!(file.file, Some(dir.path().join("pet.json")));
+        assert!(BodyFile::locate("pet.json", None).with_files_dir(Some(dir.path())).path_exists);
+    }
+
+    #[test]
+    fn should_read_small_files_once_at_first_response() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        let file = BodyFile::locate("a.txt", Some(dir.path()));
+        std::fs::write(dir.path().join("a.txt"), "b").unwrap();
+        assert_eq!(file.read().as_deref(), Some(&b"b"[..]));
+        std::fs::write(dir.path().join("a.txt"), "c").unwrap();
+        assert_eq!(file.read().as_deref(), Some(&b"b"[..]));
+        assert_eq!(file.clone().read().as_deref(), Some(&b"b"[..]));
+    }
+
+    #[test]
+    fn should_read_large_files_at_every_response() {
+        let dir = tempfile::tempdir().unwrap();
+        let large = |c: &str| c.repeat(BodyFile::MAX_TEMPLATE_SIZE as usize + 1);
+        std::fs::write(dir.path().join("a.txt"), large("a")).unwrap();
+        let file = BodyFile::locate("a.txt", Some(dir.path()));
+        assert_eq!(file.read().as_deref(), Some(large("a").as_bytes()));
+        std::fs::write(dir.path().join("a.txt"), large("b")).unwrap();
+        assert_eq!(file.read().as_deref(), Some(large("b").as_bytes()));
+        assert!(file.content.0.get().is_none());
+    }
+
+    #[test]
+    fn should_only_precompile_small_text_files_with_fixed_name() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "{{request.path}}").unwrap();
+        std::fs::write(dir.path().join("large.txt"), "a".repeat(BodyFile::MAX_TEMPLATE_SIZE as usize + 1)).unwrap();
+        std::fs::write(dir.path().join("a.png"), [0xff, 0xfe]).unwrap();
+        assert_eq!(BodyFile::locate("a.txt", Some(dir.path())).text_template(), Some("{{request.path}}"));
+        assert_eq!(BodyFile::locate("large.txt", Some(dir.path())).text_template(), None);
+        assert_eq!(BodyFile::locate("a.png", Some(dir.path())).text_template(), None);
+        assert_eq!(BodyFile::locate("{{request.path}}.txt", Some(dir.path())).text_template(), None);
+    }
+
+    #[test]
+    fn should_read_binary_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.png"), [0x89, 0x50, 0x4e, 0x47, 0xff]).unwrap();
+        let file = BodyFile::locate("a.png", Some(dir.path()));
+        assert_eq!(file.read().as_deref(), Some(&[0x89, 0x50, 0x4e, 0x47, 0xff][..]));
+    }
+
+    #[test]
+    fn should_locate_rendered_only_under_files_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("__files");
+        std::fs::create_dir_all(dir.join("pets")).unwrap();
+        std::fs::write(dir.join("pets").join("1.json"), "{}").unwrap();
+        std::fs::write(root.path().join("secret.json"), "{}").unwrap();
+        let secret = root.path().join("secret.json");
+        assert!(BodyFile::locate_rendered("pets/1.json", Some(&dir)).is_some());
+        assert!(BodyFile::locate_rendered("./pets/1.json", Some(&dir)).is_some());
+        assert!(BodyFile::locate_rendered("pets/1.json", None).is_none());
+        assert!(BodyFile::locate_rendered("pets/2.json", Some(&dir)).is_none());
+        assert!(BodyFile::locate_rendered("pets", Some(&dir)).is_none());
+        assert!(BodyFile::locate_rendered("../secret.json", Some(&dir)).is_none());
+        assert!(BodyFile::locate_rendered("pets/../../secret.json", Some(&dir)).is_none());
+        assert!(BodyFile::locate_rendered(secret.to_str().unwrap(), Some(&dir)).is_none());
+        assert!(BodyFile::locate_rendered("/etc/passwd", Some(&dir)).is_none());
+        assert!(BodyFile::locate_rendered("Cargo.toml", Some(&dir)).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn should_not_follow_symlinks_out_of_files_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("__files");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(root.path().join("secret.json"), "{}").unwrap();
+        std::os::unix::fs::symlink(root.path().join("secret.json"), dir.join("link.json")).unwrap();
+        assert!(BodyFile::locate_rendered("link.json", Some(&dir)).is_none());
     }
 }
\ No newline at end of file
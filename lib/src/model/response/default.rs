@@ -2,19 +2,20 @@ use const_format::concatcp;
 use http_types::headers::SERVER;
 use wiremock::ResponseTemplate;
 
-use super::{JsonStub, ResponseAppender};
+use super::ResponseAppender;
 
 const MATCHED_STUB_ID_HEADER: &str = "Matched-Stub-Id";
 
 const STUBR_VERSION: &str = env!("CARGO_PKG_VERSION");
 const SERVER_HEADER: &str = concatcp!("stubr(", STUBR_VERSION, ")");
 
-pub struct WiremockIsoResponse<'a>(pub &'a JsonStub);
+/// Headers Wiremock adds to every response. Holds the stub's uuid, if any
+pub struct WiremockIsoResponse<'a>(pub Option<&'a str>);
 
 impl ResponseAppender for WiremockIsoResponse<'_> {
     fn add(&self, mut resp: ResponseTemplate) -> ResponseTemplate {
         resp = resp.append_header(SERVER, SERVER_HEADER);
-        if let Some(uuid) = self.0.uuid.as_deref() {
+        if let Some(uuid) = self.0 {
             resp = resp.append_header(MATCHED_STUB_ID_HEADER, uuid);
         }
         resp
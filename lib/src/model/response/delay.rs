@@ -4,23 +4,29 @@ use wiremock::ResponseTemplate;
 
 use crate::Config;
 
-use super::{JsonStub, ResponseAppender};
+/// Server-wide delays applied along with a stub's own one
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Delay {
+    global_delay: Option<u64>,
+    latency: Option<u64>,
+}
 
-pub struct Delay<'a>(pub &'a JsonStub, pub &'a Config);
+impl From<&Config> for Delay {
+    fn from(config: &Config) -> Self {
+        Self { global_delay: config.global_delay, latency: config.latency }
+    }
+}
 
-impl ResponseAppender for Delay<'_> {
-    fn add(&self, mut resp: ResponseTemplate) -> ResponseTemplate {
-        if let Some(global_delay) = self.1.global_delay {
-            resp = resp.set_delay(Duration::from_millis(global_delay))
-        } else if let Some(latency) = self.1.latency {
-            if let Some(delay) = self.0.response.fixed_delay_milliseconds {
-                resp = resp.set_delay(Duration::from_millis(latency + delay))
-            } else {
-                resp = resp.set_delay(Duration::from_millis(latency))
-            }
-        } else if let Some(delay) = self.0.response.fixed_delay_milliseconds {
-            resp = resp.set_delay(Duration::from_millis(delay))
+impl Delay {
+    /// * `fixed` - stub's own delay in milliseconds, already rendered when templated
+    pub fn add(&self, resp: ResponseTemplate, fixed: Option<u64>) -> ResponseTemplate {
+        let delay = self.global_delay.or(match (self.latency, fixed) {
+            (Some(latency), Some(fixed)) => Some(latency + fixed),
+            (latency, fixed) => latency.or(fixed),
+        });
+        match delay {
+            Some(delay) => resp.set_delay(Duration::from_millis(delay)),
+            None => resp,
         }
-        resp
     }
 }
\ No newline at end of file
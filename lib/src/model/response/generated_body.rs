@@ -0,0 +1,71 @@
+use std::sync::{Arc, OnceLock};
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use wiremock::ResponseTemplate;
+
+use super::ResponseAppender;
+
+/// Body of a given size e.g. for download or performance tests. It is built at the first response then
+/// shared, but still copied in every response since wiremock 0.5 buffers bodies and does not support streaming them
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct GeneratedBody {
+    /// Size of the body, only made of zeroes
+    #[serde(deserialize_with = "deserialize_bytes")]
+    pub bytes: usize,
+    #[serde(skip)]
+    content: Arc<OnceLock<Vec<u8>>>,
+}
+
+/// Only the size tells generated bodies apart, the content is derived from it
+impl PartialEq for GeneratedBody {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl Eq for GeneratedBody {}
+
+impl GeneratedBody {
+    const MIME: &'static str = "application/octet-stream";
+    /// Bounds the memory a single response can take
+    pub const MAX_BYTES: usize = 100 * 1024 * 1024;
+}
+
+impl ResponseAppender for GeneratedBody {
+    fn add(&self, resp: ResponseTemplate) -> ResponseTemplate {
+        let content = self.content.get_or_init(|| vec![0; self.bytes]);
+        resp.set_body_raw(content.as_slice(), Self::MIME)
+    }
+}
+
+fn deserialize_bytes<'de, D>(bytes: D) -> Result<usize, D::Error> where D: Deserializer<'de> {
+    let bytes = usize::deserialize(bytes)?;
+    if bytes > GeneratedBody::MAX_BYTES {
+        return Err(D::Error::custom(format!("'generatedBody' cannot exceed {} bytes but was {}", GeneratedBody::MAX_BYTES, bytes)));
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod generated_body_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn should_bound_size() {
+        let parse = |bytes| serde_json::from_value::<GeneratedBody>(json!({ "bytes": bytes }));
+        assert_eq!(parse(json!(10)).unwrap().bytes, 10);
+        assert_eq!(parse(json!(GeneratedBody::MAX_BYTES)).unwrap().bytes, GeneratedBody::MAX_BYTES);
+        assert!(parse(json!(GeneratedBody::MAX_BYTES + 1)).is_err());
+        assert!(parse(json!(1e12)).is_err());
+        assert!(parse(json!(-1)).is_err());
+    }
+
+    #[test]
+    fn should_build_content_once() {
+        let body = GeneratedBody { bytes: 3, ..Default::default() };
+        body.clone().add(ResponseTemplate::new(200));
+        assert_eq!(body.content.get(), Some(&vec![0; 3]));
+    }
+}
\ No newline at end of file
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use wiremock::ResponseTemplate;
 
-use super::{ResponseAppender, template::{data::HandlebarsData, HandlebarTemplatable}};
+use super::{ResponseAppender, template::{data::HandlebarsData, HandlebarTemplatable, TemplateRenderer}};
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
 pub struct HttpRespHeadersStub {
@@ -10,13 +10,39 @@ pub struct HttpRespHeadersStub {
     pub headers: Option<Map<String, Value>>,
 }
 
+impl HttpRespHeadersStub {
+    /// Every value of a header e.g. `["a=1", "b=2"]` for a `Set-Cookie`. Numbers and booleans are
+    /// turned into strings, anything else is ignored
+    pub fn values(value: &Value) -> Vec<String> {
+        match value {
+            Value::Array(values) => values.iter().flat_map(Self::scalar).collect(),
+            _ => Self::scalar(value).into_iter().collect(),
+        }
+    }
+
+    fn scalar(value: &Value) -> Option<String> {
+        match value {
+            Value::String(s) => Some(s.to_string()),
+            Value::Number(n) => Some(n.to_string()),
+            Value::Bool(b) => Some(b.to_string()),
+            _ => None,
+        }
+    }
+
+    /// First value replaces any existing header, the following ones are appended to it
+    fn append(mut resp: ResponseTemplate, key: &str, values: impl Iterator<Item=String>) -> ResponseTemplate {
+        for (i, v) in values.enumerate() {
+            resp = if i == 0 { resp.insert_header(key, v.as_str()) } else { resp.append_header(key, v.as_str()) }
+        }
+        resp
+    }
+}
+
 impl ResponseAppender for HttpRespHeadersStub {
     fn add(&self, mut resp: ResponseTemplate) -> ResponseTemplate {
         if let Some(headers) = self.headers.as_ref() {
             for (k, v) in headers {
-                if let Some(v) = v.as_str() {
-                    resp = resp.insert_header(k.as_str(), v)
-                }
+                resp = Self::append(resp, k, Self::values(v).into_iter())
             }
         }
         resp
@@ -24,23 +50,19 @@ impl ResponseAppender for HttpRespHeadersStub {
 }
 
 impl HandlebarTemplatable for HttpRespHeadersStub {
-    fn register_template(&self) {
+    fn register_template(&self, renderer: &mut TemplateRenderer) {
         if let Some(headers) = self.headers.as_ref() {
-            for (_, v) in headers {
-                if let Some(v) = v.as_str() {
-                    self.register(v, v)
-                }
+            for v in headers.values().flat_map(Self::values) {
+                renderer.register(&v)
             }
         }
     }
 
-    fn render_response_template(&self, mut resp: ResponseTemplate, data: &HandlebarsData) -> ResponseTemplate {
+    fn render_response_template(&self, mut resp: ResponseTemplate, data: &HandlebarsData, renderer: &TemplateRenderer) -> ResponseTemplate {
         if let Some(headers) = self.headers.as_ref() {
             for (k, v) in headers {
-                if let Some(v) = v.as_str() {
-                    let rendered = self.render(v, data);
-                    resp = resp.insert_header(k.as_str(), rendered.as_str())
-                }
+                let rendered = Self::values(v).into_iter().map(|v| renderer.render(&v, data));
+                resp = Self::append(resp, k, rendered)
             }
         }
         resp
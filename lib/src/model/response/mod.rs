@@ -5,25 +5,27 @@ use wiremock::ResponseTemplate;
 
 use body::BodyStub;
 use headers::HttpRespHeadersStub;
-
-use super::JsonStub;
+use templatable::Templatable;
+use template::TemplateRenderer;
 
 pub mod body;
-mod body_file;
+pub(crate) mod body_file;
+pub mod generated_body;
 pub mod headers;
 pub mod default;
 pub mod delay;
 pub mod template;
+pub mod templatable;
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ResponseStub {
-    /// HTTP response status
+    /// HTTP response status. When templated and not rendering a valid status, the response is a 500
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub status: Option<u16>,
-    /// delay in milliseconds to apply to the response
+    pub status: Option<Templatable<u16>>,
+    /// delay in milliseconds to apply to the response, also templatable
     #[serde(skip_serializing)]
-    pub fixed_delay_milliseconds: Option<u64>,
+    pub fixed_delay_milliseconds: Option<Templatable<u64>>,
     /// HTTP response body
     #[serde(flatten)]
     pub body: BodyStub,
@@ -31,12 +33,13 @@ pub struct ResponseStub {
     #[serde(flatten)]
     pub headers: HttpRespHeadersStub,
     /// Mostly used for enabling response templating
-    #[serde(default, skip_serializing)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub transformers: Vec<String>,
 }
 
 impl ResponseStub {
-    const RESPONSE_TEMPLATE: &'static str = "response-template";
+    pub(crate) const RESPONSE_TEMPLATE: &'static str = "response-template";
+    pub(crate) const INVALID_STATUS: u16 = 500;
 
     pub(crate) fn requires_response_templating(&self) -> bool {
         self.transformers.iter().any(|it| it == Self::RESPONSE_TEMPLATE)
@@ -47,8 +50,21 @@ impl ResponseStub {
             .map(|headers| headers.keys().map(String::as_str))
     }
 
+    /// Status when not templated
     pub fn status(&self) -> u16 {
-        self.status.unwrap_or(200)
+        self.status.as_ref().and_then(Templatable::value).unwrap_or(200)
+    }
+
+    /// Delay when not templated
+    pub fn fixed_delay(&self) -> Option<u64> {
+        self.fixed_delay_milliseconds.as_ref().and_then(Templatable::value)
+    }
+
+    /// Compiles templated status and delay, body and headers have their own
+    pub(crate) fn register_template(&self, renderer: &mut TemplateRenderer) {
+        let status = self.status.as_ref().and_then(Templatable::template);
+        let delay = self.fixed_delay_milliseconds.as_ref().and_then(Templatable::template);
+        status.into_iter().chain(delay).for_each(|t| renderer.register(t));
     }
 }
 
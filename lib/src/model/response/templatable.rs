@@ -0,0 +1,79 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::template::{data::HandlebarsData, TemplateRenderer};
+
+/// A response field either set to a value or to a template rendering one e.g. `"status": "{{request.query.code}}"`
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum Templatable<T> {
+    Value(T),
+    Template(String),
+}
+
+impl<T: FromStr + Copy> Templatable<T> {
+    /// Value known without rendering anything e.g. `200` or `"200"`
+    pub fn value(&self) -> Option<T> {
+        match self {
+            Self::Value(v) => Some(*v),
+            Self::Template(t) => t.trim().parse().ok(),
+        }
+    }
+
+    /// Only when it actually requires rendering
+    pub fn template(&self) -> Option<&str> {
+        match self {
+            Self::Template(t) if self.value().is_none() => Some(t),
+            _ => None,
+        }
+    }
+
+    pub fn render(&self, data: &HandlebarsData, renderer: &TemplateRenderer) -> Option<T> {
+        self.value().or_else(|| renderer.render(self.template()?, data).trim().parse().ok())
+    }
+}
+
+impl<T> From<T> for Templatable<T> {
+    fn from(value: T) -> Self {
+        Self::Value(value)
+    }
+}
+
+#[cfg(test)]
+mod templatable_tests {
+    use http_types::Request;
+    use serde_json::json;
+
+    use super::{*, super::template::{data::RequestData, Templates}};
+
+    fn status(json: serde_json::Value) -> Templatable<u16> {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn should_deserialize_value_or_template() {
+        assert_eq!(status(json!(404)), Templatable::Value(404));
+        assert_eq!(status(json!("{{request.query.code}}")), Templatable::Template(String::from("{{request.query.code}}")));
+        assert_eq!(serde_json::to_value(Templatable::Value(404_u16)).unwrap(), json!(404));
+    }
+
+    #[test]
+    fn should_not_require_rendering_plain_values() {
+        assert_eq!(status(json!(404)).value(), Some(404));
+        assert_eq!(status(json!(" 404 ")).value(), Some(404));
+        assert!(status(json!("404")).template().is_none());
+        assert_eq!(status(json!("{{request.query.code}}")).template(), Some("{{request.query.code}}"));
+    }
+
+    #[test]
+    fn should_render_template() {
+        let renderer = TemplateRenderer::new(Templates::default());
+        let mut req = Request::get("http://localhost/?code=418");
+        let request = RequestData::from(&mut req);
+        let data = HandlebarsData { request: &request, response: None, stub_name: None, is_verify: false };
+        assert_eq!(status(json!("{{request.query.code}}")).render(&data, &renderer), Some(418));
+        assert_eq!(status(json!("{{request.query.absent}}")).render(&data, &renderer), None);
+        assert_eq!(status(json!(200)).render(&data, &renderer), Some(200));
+    }
+}
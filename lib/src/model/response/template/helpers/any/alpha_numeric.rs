@@ -1,5 +1,6 @@
 use std::str::from_utf8;
 
+use anyhow::ensure;
 use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext};
 
 use crate::gen::regex::RegexRndGenerator;
@@ -19,11 +20,12 @@ impl AnyTemplate for AnyAlphaNumeric {
         RegexRndGenerator(Self::ALPHA_NUMERIC_REGEX).try_generate()
     }
 
-    fn verify<'reg: 'rc, 'rc>(&self, _: &Helper<'reg, 'rc>, ctx: &'rc Context, _: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) {
-        assert!(!response.is_empty() && response.iter().all(|c| c.is_ascii_alphanumeric()),
+    fn verify<'reg: 'rc, 'rc>(&self, _: &Helper<'reg, 'rc>, ctx: &'rc Context, _: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) -> anyhow::Result<()> {
+        ensure!(!response.is_empty() && response.iter().all(|c| c.is_ascii_alphanumeric()),
                 "Verification failed for stub '{}'. Expected response body to {} but was '{}'",
                 ctx.stub_name(), Self::REASON, from_utf8(response.as_slice()).unwrap_or_default()
         );
+        Ok(())
     }
 
     fn expected<'reg: 'rc, 'rc>(&self, _: &Helper<'reg, 'rc>, _: &mut RenderContext<'reg, 'rc>) -> String {
@@ -1,5 +1,6 @@
 use std::str::from_utf8;
 
+use anyhow::ensure;
 use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext};
 
 use super::{AnyTemplate, super::verify::VerifyDetect};
@@ -16,13 +17,14 @@ impl AnyTemplate for AnyBoolean {
         Ok(rand::random::<bool>().to_string())
     }
 
-    fn verify<'reg: 'rc, 'rc>(&self, _: &Helper<'reg, 'rc>, ctx: &'rc Context, _: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) {
+    fn verify<'reg: 'rc, 'rc>(&self, _: &Helper<'reg, 'rc>, ctx: &'rc Context, _: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) -> anyhow::Result<()> {
         let resp = from_utf8(response.as_slice()).ok();
         let is_bool = resp.and_then(|s| s.parse::<bool>().ok()).is_some();
-        assert!(!response.is_empty() && is_bool,
+        ensure!(!response.is_empty() && is_bool,
                 "Verification failed for stub '{}'. Expected response body to {} but was '{}'",
                 ctx.stub_name(), Self::REASON, from_utf8(response.as_slice()).unwrap_or_default()
         );
+        Ok(())
     }
 
     fn expected<'reg: 'rc, 'rc>(&self, _: &Helper<'reg, 'rc>, _: &mut RenderContext<'reg, 'rc>) -> String {
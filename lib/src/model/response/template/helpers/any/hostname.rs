@@ -1,5 +1,6 @@
 use std::str::from_utf8;
 
+use anyhow::ensure;
 use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext};
 use regex::Regex;
 
@@ -25,12 +26,13 @@ impl AnyTemplate for AnyHostname {
         RegexRndGenerator(Self::HOST_RGX_GEN).try_generate()
     }
 
-    fn verify<'reg: 'rc, 'rc>(&self, _: &Helper<'reg, 'rc>, ctx: &'rc Context, _: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) {
+    fn verify<'reg: 'rc, 'rc>(&self, _: &Helper<'reg, 'rc>, ctx: &'rc Context, _: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) -> anyhow::Result<()> {
         if let Ok(resp) = from_utf8(response.as_slice()) {
-            assert!(HOST_REGEX.is_match(resp),
+            ensure!(HOST_REGEX.is_match(resp),
                     "Verification failed for stub '{}'. Expected response body to {} but was '{}'",
                     ctx.stub_name(), Self::REASON, resp)
         }
+        Ok(())
     }
 
     fn expected<'reg: 'rc, 'rc>(&self, _: &Helper<'reg, 'rc>, _: &mut RenderContext<'reg, 'rc>) -> String {
@@ -1,5 +1,6 @@
 use std::str::from_utf8;
 
+use anyhow::ensure;
 use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext};
 use rand::random;
 
@@ -34,7 +35,7 @@ impl AnyTemplate for AnyInteger {
         })
     }
 
-    fn verify<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, ctx: &'rc Context, rc: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) {
+    fn verify<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, ctx: &'rc Context, rc: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) -> anyhow::Result<()> {
         let is_int = from_utf8(response.as_slice()).ok()
             .map(|s| match h.name() {
                 Self::I64 => s.parse::<i64>().is_ok(),
@@ -47,11 +48,12 @@ impl AnyTemplate for AnyInteger {
                 Self::U8 => s.parse::<u8>().is_ok(),
                 _ => false
             }).unwrap_or_default();
-        assert!(!response.is_empty() && is_int,
+        ensure!(!response.is_empty() && is_int,
                 "Verification failed for stub '{}'. Expected response body to {} but was '{}'",
                 ctx.stub_name(), self.expected(h, rc),
                 from_utf8(response.as_slice()).unwrap_or_default()
         );
+        Ok(())
     }
 
     fn expected<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &mut RenderContext<'reg, 'rc>) -> String {
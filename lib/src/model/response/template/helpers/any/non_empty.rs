@@ -1,3 +1,4 @@
+use anyhow::ensure;
 use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext};
 
 use crate::gen::regex::RegexRndGenerator;
@@ -17,10 +18,11 @@ impl AnyTemplate for AnyNonEmpty {
         RegexRndGenerator(Self::NON_EMPTY_REGEX).try_generate()
     }
 
-    fn verify<'reg: 'rc, 'rc>(&self, _: &Helper<'reg, 'rc>, ctx: &'rc Context, _: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) {
-        assert!(!response.is_empty(),
+    fn verify<'reg: 'rc, 'rc>(&self, _: &Helper<'reg, 'rc>, ctx: &'rc Context, _: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) -> anyhow::Result<()> {
+        ensure!(!response.is_empty(),
                 "Verification failed for stub '{}'. Expected response body to {} but was ''",
                 ctx.stub_name(), Self::REASON);
+        Ok(())
     }
 
     fn expected<'reg: 'rc, 'rc>(&self, _: &Helper<'reg, 'rc>, _: &mut RenderContext<'reg, 'rc>) -> String {
@@ -1,5 +1,6 @@
 use std::str::from_utf8;
 
+use anyhow::ensure;
 use anyhow::anyhow;
 use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, PathAndJson, RenderContext};
 use itertools::Itertools;
@@ -30,14 +31,15 @@ impl AnyTemplate for AnyOf {
             .ok_or_else(|| anyhow!("Unexpected error"))
     }
 
-    fn verify<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, ctx: &'rc Context, rc: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) {
+    fn verify<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, ctx: &'rc Context, rc: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) -> anyhow::Result<()> {
         let resp = from_utf8(response.as_slice()).ok();
         let is_contained = resp.map(|r| Self::values(h.params()).contains(&r)).unwrap_or_default();
-        assert!(!response.is_empty() && is_contained,
+        ensure!(!response.is_empty() && is_contained,
                 "Verification failed for stub '{}'. Expected response body to {} but was '{}'",
                 ctx.stub_name(), self.expected(h, rc),
                 from_utf8(response.as_slice()).unwrap_or_default()
         );
+        Ok(())
     }
 
     fn expected<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &mut RenderContext<'reg, 'rc>) -> String {
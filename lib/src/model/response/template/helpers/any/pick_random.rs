@@ -0,0 +1,56 @@
+use std::str::from_utf8;
+
+use anyhow::{anyhow, ensure};
+use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext};
+use itertools::Itertools;
+use rand::prelude::IteratorRandom;
+use serde_json::Value;
+
+use super::{AnyTemplate, super::{utils_str::ParamExt, verify::VerifyDetect}};
+
+/// Picks one of its parameters e.g. `{{pickRandom 'a' 'b'}}` or of an array e.g. `{{pickRandom (array 1 2)}}`
+pub struct PickRandom;
+
+impl PickRandom {
+    pub const NAME: &'static str = "pickRandom";
+
+    fn values(h: &Helper) -> Vec<String> {
+        let params = h.params().iter().map(ParamExt::as_json).collect_vec();
+        let values = match params.as_slice() {
+            [Value::Array(items)] => items.clone(),
+            _ => params,
+        };
+        values.into_iter()
+            .map(|v| match v {
+                Value::String(s) => s,
+                v => v.to_string(),
+            })
+            .collect()
+    }
+}
+
+impl AnyTemplate for PickRandom {
+    fn generate<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &'rc Context, _: &mut RenderContext<'reg, 'rc>) -> anyhow::Result<String> {
+        Self::values(h).into_iter()
+            .choose(&mut rand::thread_rng())
+            .ok_or_else(|| anyhow!("Nothing to pick from in pickRandom response template"))
+    }
+
+    fn verify<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, ctx: &'rc Context, rc: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) -> anyhow::Result<()> {
+        let resp = from_utf8(response.as_slice()).unwrap_or_default();
+        ensure!(Self::values(h).iter().any(|v| v == resp),
+                "Verification failed for stub '{}'. Expected response body to {} but was '{}'",
+                ctx.stub_name(), self.expected(h, rc), resp);
+        Ok(())
+    }
+
+    fn expected<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &mut RenderContext<'reg, 'rc>) -> String {
+        format!("be one of {:?}", Self::values(h))
+    }
+}
+
+impl HelperDef for PickRandom {
+    fn call<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &'reg Handlebars<'reg>, ctx: &'rc Context, rc: &mut RenderContext<'reg, 'rc>, out: &mut dyn Output) -> HelperResult {
+        self.render(h, ctx, rc, out)
+    }
+}
@@ -0,0 +1,49 @@
+use std::str::from_utf8;
+
+use anyhow::ensure;
+use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext};
+use rand::Rng;
+
+use super::{AnyTemplate, super::{utils_str::ParamExt, verify::VerifyDetect}};
+
+/// A random integer between an inclusive lower bound and an exclusive upper one e.g. `{{randomInt lower=5 upper=9}}`
+pub struct RandomInt;
+
+impl RandomInt {
+    pub const NAME: &'static str = "randomInt";
+    const LOWER: &'static str = "lower";
+    const UPPER: &'static str = "upper";
+
+    fn bounds(h: &Helper) -> (i64, i64) {
+        let bound = |name: &str| h.hash_get(name).and_then(|b| b.as_f64()).map(|b| b as i64);
+        (bound(Self::LOWER).unwrap_or(i32::MIN as i64), bound(Self::UPPER).unwrap_or(i32::MAX as i64))
+    }
+}
+
+impl AnyTemplate for RandomInt {
+    fn generate<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &'rc Context, _: &mut RenderContext<'reg, 'rc>) -> anyhow::Result<String> {
+        let (lower, upper) = Self::bounds(h);
+        ensure!(lower < upper, "Lower bound {} should be less than upper bound {} in randomInt response template", lower, upper);
+        Ok(rand::thread_rng().gen_range(lower..upper).to_string())
+    }
+
+    fn verify<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, ctx: &'rc Context, rc: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) -> anyhow::Result<()> {
+        let (lower, upper) = Self::bounds(h);
+        let resp = from_utf8(response.as_slice()).unwrap_or_default();
+        let is_within = resp.parse::<i64>().map(|i| (lower..upper).contains(&i)).unwrap_or_default();
+        ensure!(is_within, "Verification failed for stub '{}'. Expected response body to {} but was '{}'",
+                ctx.stub_name(), self.expected(h, rc), resp);
+        Ok(())
+    }
+
+    fn expected<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &mut RenderContext<'reg, 'rc>) -> String {
+        let (lower, upper) = Self::bounds(h);
+        format!("be an integer in [{}, {})", lower, upper)
+    }
+}
+
+impl HelperDef for RandomInt {
+    fn call<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &'reg Handlebars<'reg>, ctx: &'rc Context, rc: &mut RenderContext<'reg, 'rc>, out: &mut dyn Output) -> HelperResult {
+        self.render(h, ctx, rc, out)
+    }
+}
@@ -1,5 +1,6 @@
 use std::str::from_utf8;
 
+use anyhow::ensure;
 use anyhow::anyhow;
 use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext};
 use regex::Regex;
@@ -25,14 +26,15 @@ impl AnyTemplate for AnyRegex {
             .and_then(|r| RegexRndGenerator(r).try_generate())
     }
 
-    fn verify<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, ctx: &'rc Context, _: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) {
+    fn verify<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, ctx: &'rc Context, _: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) -> anyhow::Result<()> {
         let regex = Self::read_regex(h).map(Regex::new);
         let resp = from_utf8(response.as_slice()).ok();
         if let Some((Ok(regex), resp)) = regex.zip(resp) {
-            assert!(regex.is_match(resp),
+            ensure!(regex.is_match(resp),
                     "Verification failed for stub '{}'. Expected response body to match '{}' but was '{}'",
                     ctx.stub_name(), regex.as_str(), resp)
         }
+        Ok(())
     }
 
     fn expected<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &mut RenderContext<'reg, 'rc>) -> String {
@@ -0,0 +1,123 @@
+use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext, RenderError, ScopedJson};
+use itertools::Itertools;
+use serde_json::Value;
+
+use super::utils_str::ParamExt;
+
+/// Builds arrays e.g. `{{array 1 'two' true}}`, `{{range 1 5}}` and edits them e.g.
+/// `{{arrayAdd (array 1 3) 2 position=1}}` or `{{arrayRemove (array 1 2 3) position='end'}}`.
+/// They are rendered like `[1, two, true]`
+pub struct ArrayHelper;
+
+impl ArrayHelper {
+    pub const ARRAY: &'static str = "array";
+    pub const ADD: &'static str = "arrayAdd";
+    pub const REMOVE: &'static str = "arrayRemove";
+    pub const RANGE: &'static str = "range";
+    const POSITION: &'static str = "position";
+    const START: &'static str = "start";
+    const END: &'static str = "end";
+    /// Bounds `range` so that a template cannot allocate an arbitrarily large array
+    const MAX_RANGE_LEN: i64 = 10_000;
+
+    fn array(h: &Helper) -> Option<Vec<Value>> {
+        Some(h.params().iter().map(ParamExt::as_json).collect())
+    }
+
+    fn source(h: &Helper) -> Option<Vec<Value>> {
+        h.param(0)?.value().as_array().cloned()
+    }
+
+    /// Where to add or remove an item, defaults to the end
+    fn position(h: &Helper, len: usize) -> Option<usize> {
+        match h.hash_get(Self::POSITION).map(ParamExt::as_json) {
+            None => Some(len),
+            Some(Value::String(p)) if p == Self::START => Some(0),
+            Some(Value::String(p)) if p == Self::END => Some(len),
+            Some(p) => p.as_u64().or_else(|| p.as_str()?.parse().ok()).map(|p| p as usize),
+        }
+    }
+
+    fn add(h: &Helper) -> Option<Vec<Value>> {
+        let mut array = Self::source(h)?;
+        let item = h.param(1)?.as_json();
+        let position = Self::position(h, array.len()).filter(|p| *p <= array.len())?;
+        array.insert(position, item);
+        Some(array)
+    }
+
+    fn remove(h: &Helper) -> Option<Vec<Value>> {
+        let mut array = Self::source(h)?;
+        let last = array.len().checked_sub(1)?;
+        let position = Self::position(h, last).filter(|p| *p <= last)?;
+        array.remove(position);
+        Some(array)
+    }
+
+    fn range(h: &Helper) -> Result<Vec<Value>, RenderError> {
+        let bound = |i| h.param(i).and_then(ParamExt::as_f64).map(|b| b as i64).ok_or_else(|| Self::invalid(h));
+        let (from, to) = (bound(0)?, bound(1)?);
+        if to.saturating_sub(from) >= Self::MAX_RANGE_LEN {
+            return Err(RenderError::new(format!("Cannot render a range of more than {} items", Self::MAX_RANGE_LEN)));
+        }
+        Ok((from..=to).map(Value::from).collect())
+    }
+
+    fn value(h: &Helper) -> Result<Vec<Value>, RenderError> {
+        match h.name() {
+            Self::ARRAY => Self::array(h),
+            Self::ADD => Self::add(h),
+            Self::REMOVE => Self::remove(h),
+            Self::RANGE => return Self::range(h),
+            _ => panic!("Unexpected error"),
+        }.ok_or_else(|| Self::invalid(h))
+    }
+
+    fn invalid(h: &Helper) -> RenderError {
+        RenderError::new(format!("Invalid {} response template", h.name()))
+    }
+
+    fn render(array: &[Value]) -> String {
+        let items = array.iter().map(|v| match v {
+            Value::String(s) => s.to_string(),
+            v => v.to_string(),
+        });
+        format!("[{}]", items.format(", "))
+    }
+}
+
+impl HelperDef for ArrayHelper {
+    fn call_inner<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &'reg Handlebars<'reg>, _: &'rc Context, _: &mut RenderContext<'reg, 'rc>) -> Result<ScopedJson<'reg, 'rc>, RenderError> {
+        Self::value(h)
+            .map(Value::Array)
+            .map(ScopedJson::from)
+    }
+
+    fn call<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, r: &'reg Handlebars<'reg>, ctx: &'rc Context, rc: &mut RenderContext<'reg, 'rc>, out: &mut dyn Output) -> HelperResult {
+        let array = self.call_inner(h, r, ctx, rc)?;
+        let rendered = array.as_json().as_array().map(|a| Self::render(a)).unwrap_or_default();
+        out.write(&rendered).map_err(RenderError::from)
+    }
+}
+
+#[cfg(test)]
+mod array_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn should_render_like_wiremock() {
+        assert_eq!(ArrayHelper::render(&[json!(1), json!("two"), json!(true)]), "[1, two, true]");
+        assert_eq!(ArrayHelper::render(&[]), "[]");
+    }
+
+    #[test]
+    fn should_bound_range() {
+        let mut hb = Handlebars::new();
+        hb.register_helper(ArrayHelper::RANGE, Box::new(ArrayHelper));
+        assert_eq!(hb.render_template("{{range 1 3}}", &json!({})).unwrap(), "[1, 2, 3]");
+        assert!(hb.render_template("{{range 0 1000000000}}", &json!({})).is_err());
+        assert!(hb.render_template("{{range 'a' 3}}", &json!({})).is_err());
+    }
+}
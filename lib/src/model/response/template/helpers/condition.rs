@@ -0,0 +1,83 @@
+use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext, Renderable, RenderError, ScopedJson};
+use regex::Regex;
+use serde_json::Value;
+
+use super::utils_str::ParamExt;
+
+/// Tests a value either inline e.g. `{{contains request.body 'abc'}}` rendering `true` or
+/// `false`, or as a block e.g. `{{#matches request.body '[0-9]+'}}digits{{else}}other{{/matches}}`
+pub struct ConditionHelper;
+
+impl ConditionHelper {
+    pub const CONTAINS: &'static str = "contains";
+    pub const MATCHES: &'static str = "matches";
+
+    /// An array contains an item, a string a substring
+    fn contains(value: &Value, expected: &Value) -> bool {
+        match (value, expected) {
+            (Value::Array(a), e) => a.contains(e),
+            (Value::String(s), Value::String(e)) => s.contains(e.as_str()),
+            (Value::String(s), e) => s.contains(&e.to_string()),
+            _ => false,
+        }
+    }
+
+    /// The whole value has to match
+    fn matches(value: &str, regex: &str) -> bool {
+        Regex::new(&format!("^(?:{})$", regex))
+            .map(|r| r.is_match(value))
+            .unwrap_or_default()
+    }
+
+    fn test(h: &Helper) -> Option<bool> {
+        let value = h.param(0)?.as_json();
+        let expected = h.param(1)?.as_json();
+        Some(match h.name() {
+            Self::CONTAINS => Self::contains(&value, &expected),
+            Self::MATCHES => Self::matches(&h.param(0)?.as_string()?, &h.param(1)?.as_string()?),
+            _ => panic!("Unexpected error"),
+        })
+    }
+}
+
+impl HelperDef for ConditionHelper {
+    fn call_inner<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &'reg Handlebars<'reg>, _: &'rc Context, _: &mut RenderContext<'reg, 'rc>) -> Result<ScopedJson<'reg, 'rc>, RenderError> {
+        Self::test(h)
+            .map(Value::from)
+            .map(ScopedJson::from)
+            .ok_or_else(|| RenderError::new(format!("Invalid {} response template", h.name())))
+    }
+
+    fn call<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, r: &'reg Handlebars<'reg>, ctx: &'rc Context, rc: &mut RenderContext<'reg, 'rc>, out: &mut dyn Output) -> HelperResult {
+        if h.is_block() {
+            let branch = if Self::test(h).unwrap_or_default() { h.template() } else { h.inverse() };
+            return branch.map(|t| t.render(r, ctx, rc, out)).unwrap_or(Ok(()));
+        }
+        let result = self.call_inner(h, r, ctx, rc)?;
+        out.write(&result.as_json().to_string()).map_err(RenderError::from)
+    }
+}
+
+#[cfg(test)]
+mod condition_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn should_contain() {
+        assert!(ConditionHelper::contains(&json!("abcde"), &json!("bcd")));
+        assert!(!ConditionHelper::contains(&json!("abcde"), &json!("x")));
+        assert!(ConditionHelper::contains(&json!("a1"), &json!(1)));
+        assert!(ConditionHelper::contains(&json!([1, "a"]), &json!("a")));
+        assert!(!ConditionHelper::contains(&json!([1, "a"]), &json!(2)));
+        assert!(!ConditionHelper::contains(&json!(null), &json!("a")));
+    }
+
+    #[test]
+    fn should_match_whole_value() {
+        assert!(ConditionHelper::matches("123", "[0-9]+"));
+        assert!(!ConditionHelper::matches("a123", "[0-9]+"));
+        assert!(!ConditionHelper::matches("123", "[0-9"));
+    }
+}
@@ -0,0 +1,221 @@
+#[cfg(any(feature = "helpers-rhai", feature = "helpers-lua"))]
+use std::path::Path;
+use std::{str::from_utf8, sync::Arc};
+
+use anyhow::anyhow;
+use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext, RenderError};
+use serde_json::{Map, Value};
+
+use super::{any::AnyTemplate, utils_str::ParamExt, verify::VerifyDetect};
+
+/// A user-defined handlebars helper e.g. one generating an IBAN or signing a payload.
+/// Register it on [crate::Config] with [crate::Config::helper] then call it from a stub
+/// response template like any builtin one e.g. `{{iban 'FR'}}`.
+///
+/// ```
+/// use stubr::{Config, HelperArgs, StubrHelper};
+///
+/// struct Shout;
+///
+/// impl StubrHelper for Shout {
+///     fn generate(&self, args: &HelperArgs) -> anyhow::Result<String> {
+///         Ok(format!("{}!", args.param(0).and_then(|v| v.as_str()).unwrap_or_default().to_uppercase()))
+///     }
+/// }
+///
+/// let config = Config::default().helper("shout", Shout);
+/// ```
+pub trait StubrHelper: Send + Sync {
+    /// Value written in the response
+    fn generate(&self, args: &HelperArgs) -> anyhow::Result<String>;
+
+    /// Whether the generated value differs at every call, like `anyEmail`. When it does, the
+    /// helper is not rendered during verification and [StubrHelper::verify] checks the
+    /// producer's actual value instead
+    fn is_random(&self) -> bool {
+        false
+    }
+
+    /// Checks the value a producer actually returned for a random helper
+    fn verify(&self, _args: &HelperArgs, _actual: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Arguments a [StubrHelper] is called with e.g. `{{iban 'FR' length=27}}`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct HelperArgs {
+    /// Positional parameters, here `["FR"]`
+    pub params: Vec<Value>,
+    /// Named parameters, here `{"length": 27}`
+    pub hash: Map<String, Value>,
+}
+
+impl HelperArgs {
+    pub fn param(&self, index: usize) -> Option<&Value> {
+        self.params.get(index)
+    }
+
+    pub fn hash(&self, name: &str) -> Option<&Value> {
+        self.hash.get(name)
+    }
+}
+
+impl From<&Helper<'_, '_>> for HelperArgs {
+    fn from(h: &Helper<'_, '_>) -> Self {
+        Self {
+            params: h.params().iter().map(ParamExt::as_json).collect(),
+            hash: h.hash().iter().map(|(k, v)| (k.to_string(), v.as_json())).collect(),
+        }
+    }
+}
+
+/// User-defined helpers of a server, keyed by the name templates call them with
+#[derive(Default, Clone)]
+pub struct Helpers(Vec<(String, Arc<dyn StubrHelper>)>);
+
+impl Helpers {
+    /// Registers a helper, replacing any previous one with the same name
+    pub fn register(&mut self, name: &str, helper: impl StubrHelper + 'static) {
+        self.0.retain(|(n, _)| n != name);
+        self.0.push((name.to_string(), Arc::new(helper)));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item=(&str, &Arc<dyn StubrHelper>)> {
+        self.0.iter().map(|(n, h)| (n.as_str(), h))
+    }
+
+    #[cfg(feature = "verify")]
+    pub(crate) fn is_random(&self, name: &str) -> bool {
+        self.iter().any(|(n, h)| n == name && h.is_random())
+    }
+
+    /// Loads every script of a directory in any enabled language (`*.rhai` with `helpers-rhai`,
+    /// `*.lua` with `helpers-lua`) as a helper named after the file e.g. `iban.lua` is called with `{{iban}}`.
+    /// Each call of a script is bounded in time and memory so that a runaway one fails rather than
+    /// hanging or exhausting the server
+    #[cfg(any(feature = "helpers-rhai", feature = "helpers-lua"))]
+    pub fn from_scripts_dir(dir: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let helpers = Self::default();
+        #[cfg(feature = "helpers-rhai")]
+        let helpers = helpers.load_scripts(dir.as_ref(), super::rhai::RhaiHelper::EXTENSION, super::rhai::RhaiHelper::from_file)?;
+        #[cfg(feature = "helpers-lua")]
+        let helpers = helpers.load_scripts(dir.as_ref(), super::lua::LuaHelper::EXTENSION, super::lua::LuaHelper::from_file)?;
+        Ok(helpers)
+    }
+
+    #[cfg(any(feature = "helpers-rhai", feature = "helpers-lua"))]
+    pub(crate) fn load_scripts<H: StubrHelper + 'static>(mut self, dir: &Path, extension: &str, load: fn(&Path) -> anyhow::Result<H>) -> anyhow::Result<Self> {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().and_then(|e| e.to_str()) == Some(extension) {
+                if let Some(name) = path.file_stem().and_then(|n| n.to_str()) {
+                    self.register(name, load(&path)?);
+                }
+            }
+        }
+        Ok(self)
+    }
+}
+
+/// Adapts a [StubrHelper] to handlebars
+pub struct CustomHelper(pub Arc<dyn StubrHelper>);
+
+impl AnyTemplate for CustomHelper {
+    fn generate<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &'rc Context, _: &mut RenderContext<'reg, 'rc>) -> anyhow::Result<String> {
+        self.0.generate(&HelperArgs::from(h))
+    }
+
+    fn verify<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, ctx: &'rc Context, _: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) -> anyhow::Result<()> {
+        self.0.verify(&HelperArgs::from(h), from_utf8(response.as_slice())?)
+            .map_err(|e| anyhow!("Verification failed for stub '{}'. {}", ctx.stub_name(), e))
+    }
+}
+
+impl HelperDef for CustomHelper {
+    fn call<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &'reg Handlebars<'reg>, ctx: &'rc Context, rc: &mut RenderContext<'reg, 'rc>, out: &mut dyn Output) -> HelperResult {
+        if self.0.is_random() {
+            return self.render(h, ctx, rc, out);
+        }
+        let value = self.generate(h, ctx, rc).map_err(|e| RenderError::new(e.to_string()))?;
+        out.write(&value).map_err(RenderError::from)
+    }
+}
+
+#[cfg(test)]
+mod custom_helper_tests {
+    use anyhow::ensure;
+    use serde_json::json;
+
+    use super::{*, super::super::{TemplateRenderer, Templates}};
+
+    struct Upper;
+
+    impl StubrHelper for Upper {
+        fn generate(&self, args: &HelperArgs) -> anyhow::Result<String> {
+            let suffix = args.hash("suffix").and_then(Value::as_str).unwrap_or_default();
+            Ok(format!("{}{}", args.param(0).and_then(Value::as_str).unwrap_or_default().to_uppercase(), suffix))
+        }
+    }
+
+    struct Digits;
+
+    impl StubrHelper for Digits {
+        fn generate(&self, _: &HelperArgs) -> anyhow::Result<String> {
+            Ok(String::from("42"))
+        }
+
+        fn is_random(&self) -> bool {
+            true
+        }
+
+        fn verify(&self, _: &HelperArgs, actual: &str) -> anyhow::Result<()> {
+            ensure!(actual.chars().all(|c| c.is_ascii_digit()), "'{}' is not made of digits", actual);
+            Ok(())
+        }
+    }
+
+    fn renderer() -> TemplateRenderer {
+        let mut helpers = Helpers::default();
+        helpers.register("up", Upper);
+        helpers.register("digits", Digits);
+        TemplateRenderer::new(Templates::new(&helpers))
+    }
+
+    #[test]
+    fn should_pass_params_and_hash() {
+        let rendered = renderer().render("{{up name suffix=\"!\"}}", &json!({"name": "john"}));
+        assert_eq!(rendered, "JOHN!");
+    }
+
+    #[test]
+    fn should_read_single_quoted_literals() {
+        assert_eq!(renderer().render("{{up 'fr' suffix='x'}}", &json!({})), "FRx");
+    }
+
+    #[test]
+    fn should_generate_random_helper() {
+        assert_eq!(renderer().render("{{digits}}", &json!({})), "42");
+    }
+
+    #[test]
+    fn should_verify_random_helper() {
+        let data = |actual: &str| json!({"is_verify": true, "response": actual.as_bytes(), "stub_name": "a"});
+        assert!(renderer().try_render("{{digits}}", &data("123")).is_ok());
+        assert!(renderer().try_render("{{digits}}", &data("abc")).is_err());
+    }
+
+    #[test]
+    fn should_replace_helper_with_same_name() {
+        let mut helpers = Helpers::default();
+        helpers.register("digits", Upper);
+        helpers.register("digits", Digits);
+        assert_eq!(helpers.iter().count(), 1);
+        assert!(helpers.is_random("digits"));
+        assert!(!helpers.is_random("up"));
+    }
+}
@@ -0,0 +1,208 @@
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Timelike, Utc};
+use handlebars::{Context, Handlebars, Helper, HelperDef, RenderContext, RenderError, ScopedJson};
+use serde_json::Value;
+
+use super::utils_str::ParamExt;
+
+/// Parses e.g. `{{parseDate '10/11/2021' format='dd/MM/yyyy'}}`, formats e.g.
+/// `{{dateFormat (parseDate request.body) 'yyyy-MM-dd'}}` and truncates e.g.
+/// `{{truncateDate (parseDate request.body) 'first day of month'}}` dates.
+/// Parsed and truncated dates are ISO 8601 strings.
+pub struct DateHelper;
+
+impl DateHelper {
+    pub const PARSE: &'static str = "parseDate";
+    pub const FORMAT: &'static str = "dateFormat";
+    pub const TRUNCATE: &'static str = "truncateDate";
+    const FORMAT_HASH: &'static str = "format";
+    const EPOCH: &'static str = "epoch";
+    const UNIX: &'static str = "unix";
+
+    fn parse(value: &str, format: Option<&str>) -> Option<DateTime<Utc>> {
+        let value = value.trim();
+        match format {
+            Some(Self::EPOCH) => value.parse().ok().and_then(|ms: i64| Utc.timestamp_millis_opt(ms).single()),
+            Some(Self::UNIX) => value.parse().ok().and_then(|s: i64| Utc.timestamp_opt(s, 0).single()),
+            Some(format) => Self::parse_with(value, &Self::to_strftime(format)),
+            None => DateTime::parse_from_rfc3339(value)
+                .or_else(|_| DateTime::parse_from_rfc2822(value))
+                .map(|d| d.with_timezone(&Utc)).ok()
+                .or_else(|| Self::parse_with(value, "%Y-%m-%dT%H:%M:%S"))
+                .or_else(|| Self::parse_with(value, "%Y-%m-%d")),
+        }
+    }
+
+    fn parse_with(value: &str, format: &str) -> Option<DateTime<Utc>> {
+        DateTime::<FixedOffset>::parse_from_str(value, format).map(|d| d.with_timezone(&Utc)).ok()
+            .or_else(|| NaiveDateTime::parse_from_str(value, format).ok().map(|d| Utc.from_utc_datetime(&d)))
+            .or_else(|| NaiveDate::parse_from_str(value, format).ok().and_then(|d| d.and_hms_opt(0, 0, 0)).map(|d| Utc.from_utc_datetime(&d)))
+    }
+
+    /// Converts a [Java SimpleDateFormat](https://docs.oracle.com/javase/7/docs/api/java/text/SimpleDateFormat.html)
+    /// pattern, the one Wiremock uses, into a strftime one
+    fn to_strftime(pattern: &str) -> String {
+        let mut out = String::new();
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                while let Some(l) = chars.next() {
+                    match l {
+                        '\'' if chars.peek() == Some(&'\'') => { chars.next(); out.push('\''); }
+                        '\'' => break,
+                        '%' => out.push_str("%%"),
+                        l => out.push(l),
+                    }
+                }
+                continue;
+            }
+            let mut count = 1;
+            while chars.peek() == Some(&c) {
+                chars.next();
+                count += 1;
+            }
+            let token = match (c, count) {
+                ('y', 2) => "%y",
+                ('y', _) => "%Y",
+                ('M', 3) => "%b",
+                ('M', c) if c > 3 => "%B",
+                ('M', _) => "%m",
+                ('d', _) => "%d",
+                ('H', _) => "%H",
+                ('h', _) => "%I",
+                ('m', _) => "%M",
+                ('s', _) => "%S",
+                ('S', _) => "%3f",
+                ('a', _) => "%p",
+                ('E', c) if c > 3 => "%A",
+                ('E', _) => "%a",
+                ('Z', _) => "%z",
+                ('X', _) => "%:z",
+                ('%', _) => "%%",
+                _ => {
+                    (0..count).for_each(|_| out.push(c));
+                    continue;
+                }
+            };
+            out.push_str(token);
+        }
+        out
+    }
+
+    fn format(date: &DateTime<Utc>, format: &str) -> Option<String> {
+        match format {
+            Self::EPOCH => Some(date.timestamp_millis().to_string()),
+            Self::UNIX => Some(date.timestamp().to_string()),
+            _ => simpledateformat::fmt(format).ok().map(|f| f.format(date)),
+        }
+    }
+
+    fn truncate(date: DateTime<Utc>, truncation: &str) -> Option<DateTime<Utc>> {
+        let day = |d: &DateTime<Utc>| d.date_naive().and_hms_opt(0, 0, 0).map(|d| Utc.from_utc_datetime(&d));
+        let first_of_month = |d: &DateTime<Utc>| d.with_day(1).and_then(|d| day(&d));
+        let first_of_next_month = |d: &DateTime<Utc>| {
+            let (y, m) = if d.month() == 12 { (d.year() + 1, 1) } else { (d.year(), d.month() + 1) };
+            Utc.with_ymd_and_hms(y, m, 1, 0, 0, 0).single()
+        };
+        match truncation.trim() {
+            "first minute of hour" => date.with_minute(0).and_then(|d| d.with_second(0)).and_then(|d| d.with_nanosecond(0)),
+            "first hour of day" => day(&date),
+            "first day of month" => first_of_month(&date),
+            "first day of next month" => first_of_next_month(&date),
+            "last day of month" => first_of_next_month(&date).map(|d| d - Duration::days(1)),
+            "first day of year" => Utc.with_ymd_and_hms(date.year(), 1, 1, 0, 0, 0).single(),
+            "first day of next year" => Utc.with_ymd_and_hms(date.year() + 1, 1, 1, 0, 0, 0).single(),
+            "last day of year" => Utc.with_ymd_and_hms(date.year(), 12, 31, 0, 0, 0).single(),
+            _ => None,
+        }
+    }
+
+    /// Format either as second parameter or as 'format' hash
+    fn format_param(h: &Helper) -> Option<String> {
+        h.param(1).or_else(|| h.hash_get(Self::FORMAT_HASH)).and_then(|p| p.as_string())
+    }
+
+    fn date_param(h: &Helper) -> Option<DateTime<Utc>> {
+        h.param(0).and_then(|p| p.as_string()).and_then(|d| Self::parse(&d, None))
+    }
+
+    fn iso(date: DateTime<Utc>) -> String {
+        date.to_rfc3339_opts(SecondsFormat::Secs, true)
+    }
+
+    fn value(h: &Helper) -> Option<String> {
+        match h.name() {
+            Self::PARSE => {
+                let value = h.param(0)?.as_string()?;
+                let format = h.hash_get(Self::FORMAT_HASH).and_then(|p| p.as_string());
+                Self::parse(&value, format.as_deref()).map(Self::iso)
+            }
+            Self::FORMAT => Self::format(&Self::date_param(h)?, &Self::format_param(h)?),
+            Self::TRUNCATE => Self::truncate(Self::date_param(h)?, &h.param(1)?.as_string()?).map(Self::iso),
+            _ => panic!("Unexpected error"),
+        }
+    }
+}
+
+impl HelperDef for DateHelper {
+    fn call_inner<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &'reg Handlebars<'reg>, _: &'rc Context, _: &mut RenderContext<'reg, 'rc>) -> Result<ScopedJson<'reg, 'rc>, RenderError> {
+        Self::value(h)
+            .map(Value::from)
+            .map(ScopedJson::from)
+            .ok_or_else(|| RenderError::new(format!("Invalid {} response template", h.name())))
+    }
+}
+
+#[cfg(test)]
+mod date_tests {
+    use super::*;
+
+    fn date(iso: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(iso).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn should_convert_java_pattern() {
+        assert_eq!(DateHelper::to_strftime("dd/MM/yyyy"), "%d/%m/%Y");
+        assert_eq!(DateHelper::to_strftime("yyyy-MM-dd'T'HH:mm:ss.SSSXXX"), "%Y-%m-%dT%H:%M:%S.%3f%:z");
+        assert_eq!(DateHelper::to_strftime("EEE, d MMM yy 'o''clock' h a"), "%a, %d %b %y o'clock %I %p");
+    }
+
+    #[test]
+    fn should_parse_with_format() {
+        assert_eq!(DateHelper::parse("10/11/2021", Some("dd/MM/yyyy")), Some(date("2021-11-10T00:00:00Z")));
+        assert_eq!(DateHelper::parse("2021-11-10 12:30:15", Some("yyyy-MM-dd HH:mm:ss")), Some(date("2021-11-10T12:30:15Z")));
+        assert_eq!(DateHelper::parse("1636502400", Some("unix")), Some(date("2021-11-10T00:00:00Z")));
+        assert_eq!(DateHelper::parse("1636502400000", Some("epoch")), Some(date("2021-11-10T00:00:00Z")));
+        assert!(DateHelper::parse("10/11/2021", Some("yyyy-MM-dd")).is_none());
+    }
+
+    #[test]
+    fn should_parse_without_format() {
+        assert_eq!(DateHelper::parse("2021-11-10T12:30:15+01:00", None), Some(date("2021-11-10T11:30:15Z")));
+        assert_eq!(DateHelper::parse("Wed, 10 Nov 2021 12:30:15 GMT", None), Some(date("2021-11-10T12:30:15Z")));
+        assert_eq!(DateHelper::parse("2021-11-10", None), Some(date("2021-11-10T00:00:00Z")));
+        assert!(DateHelper::parse("not a date", None).is_none());
+    }
+
+    #[test]
+    fn should_truncate() {
+        let d = date("2021-12-10T12:30:15Z");
+        let truncate = |t: &str| DateHelper::truncate(d, t).map(DateHelper::iso);
+        assert_eq!(truncate("first minute of hour").unwrap(), "2021-12-10T12:00:00Z");
+        assert_eq!(truncate("first hour of day").unwrap(), "2021-12-10T00:00:00Z");
+        assert_eq!(truncate("first day of month").unwrap(), "2021-12-01T00:00:00Z");
+        assert_eq!(truncate("first day of next month").unwrap(), "2022-01-01T00:00:00Z");
+        assert_eq!(truncate("last day of month").unwrap(), "2021-12-31T00:00:00Z");
+        assert_eq!(truncate("first day of year").unwrap(), "2021-01-01T00:00:00Z");
+        assert_eq!(truncate("first day of next year").unwrap(), "2022-01-01T00:00:00Z");
+        assert_eq!(truncate("last day of year").unwrap(), "2021-12-31T00:00:00Z");
+        assert!(truncate("unknown").is_none());
+    }
+
+    #[test]
+    fn should_format() {
+        let d = date("2021-11-10T12:30:15Z");
+        assert_eq!(DateHelper::format(&d, "yyyy-MM-dd").unwrap(), "2021-11-10");
+        assert_eq!(DateHelper::format(&d, "unix").unwrap(), "1636547415");
+    }
+}
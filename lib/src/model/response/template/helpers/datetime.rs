@@ -47,7 +47,7 @@ impl NowHelper {
             .unwrap_or(now)
     }
 
-    fn compute_offset(now: DateTime<Utc>, offset: String) -> Option<DateTime<Utc>> {
+    pub(crate) fn compute_offset(now: DateTime<Utc>, offset: String) -> Option<DateTime<Utc>> {
         let is_negative = offset.starts_with('-');
         let offset = if is_negative { offset.trim_start_matches('-') } else { offset.as_str() };
         parse_duration(offset).ok()
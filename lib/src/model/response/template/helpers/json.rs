@@ -0,0 +1,76 @@
+use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext, Renderable, RenderError, StringOutput};
+use serde_json::Value;
+
+use super::utils_str::ParamExt;
+
+/// Pretty prints json e.g. `{{formatJson request.body}}`, `{{formatJson request.body format='compact'}}`
+/// or `{{#formatJson}}{"a": 1}{{/formatJson}}` and serializes any value e.g. `{{toJson (array 1 2)}}`
+pub struct JsonHelper;
+
+impl JsonHelper {
+    pub const FORMAT_JSON: &'static str = "formatJson";
+    pub const TO_JSON: &'static str = "toJson";
+    const FORMAT: &'static str = "format";
+    const COMPACT: &'static str = "compact";
+
+    /// A string holding json is parsed, otherwise the value is taken as is
+    fn parse(value: Value) -> Value {
+        match value {
+            Value::String(s) => serde_json::from_str(&s).unwrap_or(Value::String(s)),
+            v => v,
+        }
+    }
+
+    fn format(value: &Value, is_compact: bool) -> Option<String> {
+        if is_compact {
+            serde_json::to_string(value).ok()
+        } else {
+            serde_json::to_string_pretty(value).ok()
+        }
+    }
+}
+
+impl HelperDef for JsonHelper {
+    fn call<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, r: &'reg Handlebars<'reg>, ctx: &'rc Context, rc: &mut RenderContext<'reg, 'rc>, out: &mut dyn Output) -> HelperResult {
+        let value = match (h.param(0), h.template()) {
+            (Some(p), _) => p.as_json(),
+            (None, Some(t)) => {
+                let mut block = StringOutput::new();
+                t.render(r, ctx, rc, &mut block)?;
+                Value::String(block.into_string()?)
+            }
+            (None, None) => return Err(RenderError::new(format!("Missing value to format in {} response template", h.name()))),
+        };
+        let formatted = match h.name() {
+            Self::FORMAT_JSON => {
+                let is_compact = h.hash_get(Self::FORMAT).and_then(|f| f.as_string()).as_deref() == Some(Self::COMPACT);
+                Self::format(&Self::parse(value), is_compact)
+            }
+            Self::TO_JSON => Self::format(&value, true),
+            _ => panic!("Unexpected error"),
+        };
+        formatted
+            .ok_or_else(|| RenderError::new(format!("Invalid {} response template", h.name())))
+            .and_then(|f| out.write(&f).map_err(RenderError::from))
+    }
+}
+
+#[cfg(test)]
+mod json_helper_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_json_string() {
+        assert_eq!(JsonHelper::parse(json!("{\"a\": 1}")), json!({"a": 1}));
+        assert_eq!(JsonHelper::parse(json!("not json")), json!("not json"));
+        assert_eq!(JsonHelper::parse(json!([1])), json!([1]));
+    }
+
+    #[test]
+    fn should_format() {
+        assert_eq!(JsonHelper::format(&json!({"a": 1}), false).unwrap(), "{\n  \"a\": 1\n}");
+        assert_eq!(JsonHelper::format(&json!({"a": 1}), true).unwrap(), "{\"a\":1}");
+    }
+}
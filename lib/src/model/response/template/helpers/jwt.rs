@@ -0,0 +1,275 @@
+use std::str::from_utf8;
+
+use anyhow::{anyhow, ensure};
+use chrono::Utc;
+use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use ring::{
+    digest::{digest, SHA256},
+    rand::SystemRandom,
+    signature::{ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair, KeyPair},
+};
+use serde_json::{json, Map, Value};
+use wiremock::{matchers::{method, path}, Mock, ResponseTemplate};
+
+use super::{any::AnyTemplate, datetime::NowHelper, utils_str::ParamExt, verify::VerifyDetect};
+
+/// Key signing the tokens of the `jwt` response template helper. Unless one is set in
+/// [crate::Config], every server generates its own
+#[derive(Clone)]
+pub struct JwtKey {
+    alg: Algorithm,
+    encoding: EncodingKey,
+    /// Public part published in the JWKS, none for symmetric keys
+    jwk: Option<Value>,
+}
+
+impl JwtKey {
+    pub const JWKS_PATH: &'static str = "/.well-known/jwks.json";
+    const KID_LEN: usize = 16;
+
+    /// A fresh ECDSA P-256 key signing with ES256
+    pub fn generate() -> Self {
+        EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &SystemRandom::new()).ok()
+            .and_then(|pkcs8| Self::es256(pkcs8.as_ref()).ok())
+            .expect("Failed generating a JWT signing key")
+    }
+
+    /// An ECDSA P-256 private key in PKCS#8 DER e.g. converted from a PEM one with
+    /// `openssl pkcs8 -topk8 -nocrypt -outform der -in key.pem -out key.der`
+    pub fn es256(pkcs8: &[u8]) -> anyhow::Result<Self> {
+        let pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8)
+            .map_err(|e| anyhow!("Invalid ES256 key because {}", e))?;
+        let public = pair.public_key().as_ref();
+        // uncompressed point i.e. 0x04 | x | y
+        let (x, y) = public[1..].split_at(32);
+        let kid = base64::encode_config(digest(&SHA256, public), base64::URL_SAFE_NO_PAD);
+        let jwk = json!({
+            "kty": "EC",
+            "crv": "P-256",
+            "alg": "ES256",
+            "use": "sig",
+            "kid": &kid[..Self::KID_LEN],
+            "x": base64::encode_config(x, base64::URL_SAFE_NO_PAD),
+            "y": base64::encode_config(y, base64::URL_SAFE_NO_PAD),
+        });
+        Ok(Self { alg: Algorithm::ES256, encoding: EncodingKey::from_ec_der(pkcs8), jwk: Some(jwk) })
+    }
+
+    /// An HMAC secret signing with HS256. Being symmetric, it is not published in the JWKS
+    pub fn hs256(secret: &[u8]) -> Self {
+        Self { alg: Algorithm::HS256, encoding: EncodingKey::from_secret(secret), jwk: None }
+    }
+
+    pub fn sign(&self, claims: &Map<String, Value>) -> anyhow::Result<String> {
+        let kid = self.jwk.as_ref().and_then(|k| k["kid"].as_str()).map(str::to_string);
+        let header = Header { kid, ..Header::new(self.alg) };
+        Ok(jsonwebtoken::encode(&header, claims, &self.encoding)?)
+    }
+
+    /// JSON Web Key Set consumers verify tokens with
+    pub fn jwks(&self) -> Value {
+        json!({ "keys": self.jwk.iter().collect::<Vec<_>>() })
+    }
+
+    /// Serves [JwtKey::jwks] unless a stub already does
+    pub(crate) fn jwks_mock(&self) -> Mock {
+        Mock::given(method("GET"))
+            .and(path(Self::JWKS_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_json(self.jwks()))
+            .with_priority(u8::MAX)
+    }
+}
+
+impl Default for JwtKey {
+    fn default() -> Self {
+        Self::generate()
+    }
+}
+
+/// Signs a token from claims e.g. `{{jwt sub=request.query.user exp='1h'}}` and renders the
+/// JWKS to verify it with e.g. `{{jwks}}`.
+/// Time claims accept offsets from now like the `now` helper does
+pub struct JwtHelper(pub JwtKey);
+
+impl JwtHelper {
+    pub const JWT: &'static str = "jwt";
+    pub const JWKS: &'static str = "jwks";
+    const ISSUED_AT: &'static str = "iat";
+    const TIME_CLAIMS: [&'static str; 3] = ["exp", "nbf", Self::ISSUED_AT];
+
+    /// Claims of an optional object parameter overridden by the hash ones
+    fn claims(h: &Helper) -> Map<String, Value> {
+        let mut claims = h.param(0).and_then(|p| p.as_json().as_object().cloned()).unwrap_or_default();
+        claims.extend(h.hash().iter().map(|(k, v)| (k.to_string(), v.as_json())));
+        claims
+    }
+
+    fn with_times(mut claims: Map<String, Value>) -> anyhow::Result<Map<String, Value>> {
+        let now = Utc::now();
+        claims.entry(Self::ISSUED_AT.to_string()).or_insert_with(|| now.timestamp().into());
+        for name in Self::TIME_CLAIMS {
+            if let Some(Value::String(offset)) = claims.get(name) {
+                let at = NowHelper::compute_offset(now, offset.replace(' ', ""))
+                    .ok_or_else(|| anyhow!("Invalid offset '{}' for claim '{}' in jwt response template", offset, name))?;
+                claims.insert(name.to_string(), at.timestamp().into());
+            }
+        }
+        Ok(claims)
+    }
+
+    fn decode_claims(token: &str) -> Option<Map<String, Value>> {
+        let parts = token.split('.').collect::<Vec<_>>();
+        if parts.len() != 3 {
+            return None;
+        }
+        base64::decode_config(parts[1], base64::URL_SAFE_NO_PAD).ok()
+            .and_then(|payload| serde_json::from_slice::<Value>(&payload).ok())
+            .and_then(|payload| payload.as_object().cloned())
+    }
+
+    fn verify_jwt(h: &Helper, resp: &str) -> anyhow::Result<()> {
+        let actual = Self::decode_claims(resp).ok_or_else(|| anyhow!("'{}' is not a JWT", resp))?;
+        for (name, expected) in Self::claims(h) {
+            let claim = actual.get(&name);
+            if Self::TIME_CLAIMS.contains(&name.as_str()) {
+                ensure!(claim.map(Value::is_i64).unwrap_or_default(), "JWT claim '{}' should be a timestamp", name);
+            } else {
+                ensure!(claim == Some(&expected), "JWT claim '{}' should be '{}' but was '{}'",
+                        name, expected, claim.unwrap_or(&Value::Null));
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_jwks(resp: &str) -> anyhow::Result<()> {
+        let is_jwks = serde_json::from_str::<Value>(resp).ok()
+            .map(|jwks| jwks["keys"].is_array())
+            .unwrap_or_default();
+        ensure!(is_jwks, "'{}' is not a JSON Web Key Set", resp);
+        Ok(())
+    }
+}
+
+impl AnyTemplate for JwtHelper {
+    fn generate<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &'rc Context, _: &mut RenderContext<'reg, 'rc>) -> anyhow::Result<String> {
+        match h.name() {
+            Self::JWKS => Ok(self.0.jwks().to_string()),
+            _ => self.0.sign(&Self::with_times(Self::claims(h))?),
+        }
+    }
+
+    fn verify<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, ctx: &'rc Context, _: &mut RenderContext<'reg, 'rc>, response: Vec<u8>) -> anyhow::Result<()> {
+        let resp = from_utf8(response.as_slice()).unwrap_or_default();
+        match h.name() {
+            Self::JWKS => Self::verify_jwks(resp),
+            _ => Self::verify_jwt(h, resp),
+        }.map_err(|e| anyhow!("Verification failed for stub '{}'. {}", ctx.stub_name(), e))
+    }
+
+    fn expected<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &mut RenderContext<'reg, 'rc>) -> String {
+        match h.name() {
+            Self::JWKS => String::from("be a JSON Web Key Set"),
+            _ => format!("be a JWT with claims {}", Value::from(Self::claims(h))),
+        }
+    }
+}
+
+impl HelperDef for JwtHelper {
+    fn call<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &'reg Handlebars<'reg>, ctx: &'rc Context, rc: &mut RenderContext<'reg, 'rc>, out: &mut dyn Output) -> HelperResult {
+        self.render(h, ctx, rc, out)
+    }
+}
+
+#[cfg(test)]
+mod jwt_helper_tests {
+    use jsonwebtoken::{decode, decode_header, DecodingKey, Validation};
+    use serde_json::json;
+
+    use super::{*, super::super::{TemplateRenderer, Templates}};
+
+    fn renderer(key: JwtKey) -> TemplateRenderer {
+        TemplateRenderer::new(Templates::with_jwt_key(&Default::default(), key))
+    }
+
+    #[test]
+    fn should_sign_claims_with_hmac_secret() {
+        let token = renderer(JwtKey::hs256(b"secret")).render("{{jwt sub='john' admin=true}}", &json!({}));
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.required_spec_claims.clear();
+        let claims = decode::<Value>(&token, &DecodingKey::from_secret(b"secret"), &validation).unwrap().claims;
+        assert_eq!(claims["sub"], json!("john"));
+        assert_eq!(claims["admin"], json!(true));
+        assert!(claims["iat"].is_i64());
+    }
+
+    #[test]
+    fn should_compute_time_claims_from_offsets() {
+        let token = renderer(JwtKey::hs256(b"secret")).render("{{jwt exp='1h' nbf='-5m'}}", &json!({}));
+        let claims = JwtHelper::decode_claims(&token).unwrap();
+        let now = Utc::now().timestamp();
+        assert!((claims["exp"].as_i64().unwrap() - now - 3600).abs() < 5);
+        assert!((claims["nbf"].as_i64().unwrap() - now + 300).abs() < 5);
+        let validation = Validation::new(Algorithm::HS256);
+        assert!(decode::<Value>(&token, &DecodingKey::from_secret(b"secret"), &validation).is_ok());
+    }
+
+    #[test]
+    fn should_fail_when_invalid_offset() {
+        assert!(renderer(JwtKey::hs256(b"secret")).try_render("{{jwt exp='tomorrow'}}", &json!({})).is_err());
+    }
+
+    #[test]
+    fn should_read_claims_from_request() {
+        let data = json!({"request": {"query": {"user": "alice"}}});
+        let token = renderer(JwtKey::hs256(b"secret")).render("{{jwt sub=request.query.user}}", &data);
+        assert_eq!(JwtHelper::decode_claims(&token).unwrap()["sub"], json!("alice"));
+    }
+
+    #[test]
+    fn should_sign_with_generated_key_published_in_jwks() {
+        let key = JwtKey::generate();
+        let renderer = renderer(key.clone());
+        let token = renderer.render("{{jwt sub='john'}}", &json!({}));
+        let header = decode_header(&token).unwrap();
+        assert_eq!(header.alg, Algorithm::ES256);
+        let jwks: Value = serde_json::from_str(&renderer.render("{{jwks}}", &json!({}))).unwrap();
+        assert_eq!(jwks, key.jwks());
+        let jwk = &jwks["keys"][0];
+        assert_eq!(header.kid.as_deref(), jwk["kid"].as_str());
+        let coordinate = |c: &str| base64::decode_config(jwk[c].as_str().unwrap(), base64::URL_SAFE_NO_PAD).unwrap();
+        let decoding = DecodingKey::from_ec_der(&[vec![4], coordinate("x"), coordinate("y")].concat());
+        let mut validation = Validation::new(Algorithm::ES256);
+        validation.required_spec_claims.clear();
+        assert!(decode::<Value>(&token, &decoding, &validation).is_ok());
+    }
+
+    #[test]
+    fn should_not_publish_symmetric_key() {
+        assert_eq!(JwtKey::hs256(b"secret").jwks(), json!({"keys": []}));
+    }
+
+    #[test]
+    fn should_reject_invalid_es256_key() {
+        assert!(JwtKey::es256(b"not a key").is_err());
+    }
+
+    #[test]
+    fn should_verify_jwt() {
+        let renderer = renderer(JwtKey::hs256(b"secret"));
+        let data = |actual: &str| json!({"is_verify": true, "response": actual.as_bytes(), "stub_name": "a"});
+        let token = JwtKey::generate().sign(json!({"sub": "john", "exp": 1}).as_object().unwrap()).unwrap();
+        assert!(renderer.try_render("{{jwt sub='john' exp='1h'}}", &data(&token)).is_ok());
+        assert!(renderer.try_render("{{jwt sub='alice'}}", &data(&token)).is_err());
+        assert!(renderer.try_render("{{jwt sub='john' nbf='1h'}}", &data(&token)).is_err());
+        assert!(renderer.try_render("{{jwt}}", &data("a.b")).is_err());
+    }
+
+    #[test]
+    fn should_verify_jwks() {
+        let renderer = renderer(JwtKey::hs256(b"secret"));
+        let data = |actual: &str| json!({"is_verify": true, "response": actual.as_bytes(), "stub_name": "a"});
+        assert!(renderer.try_render("{{jwks}}", &data(&JwtKey::generate().jwks().to_string())).is_ok());
+        assert!(renderer.try_render("{{jwks}}", &data("{}")).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use std::{
+    fs,
+    path::Path,
+    sync::{Arc, atomic::{AtomicU64, Ordering}, Mutex},
+};
+
+use anyhow::{anyhow, ensure};
+use mlua::{Function, HookTriggers, Lua, LuaOptions, LuaSerdeExt, StdLib, Value};
+
+use super::custom::{HelperArgs, Helpers, StubrHelper};
+
+/// A [StubrHelper] written as a [Lua](https://www.lua.org) script.
+/// The script defines `function generate(params, hash)` returning the rendered value. It is a random
+/// helper when it also defines `function verify(params, hash, actual)` returning whether the value a
+/// producer actually returned is valid. Only the `string`, `table`, `math` and `utf8` libraries are available.
+///
+/// ```lua
+/// function generate(params, hash)
+///     return (params[1] or "FR") .. "7630006000011234567890189"
+/// end
+///
+/// function verify(params, hash, actual)
+///     return #actual > 4
+/// end
+/// ```
+pub struct LuaHelper {
+    lua: Mutex<Lua>,
+    instructions: Arc<AtomicU64>,
+    random: bool,
+}
+
+impl LuaHelper {
+    pub(crate) const EXTENSION: &'static str = "lua";
+    const GENERATE: &'static str = "generate";
+    const VERIFY: &'static str = "verify";
+    /// Instructions a single call may run
+    const MAX_INSTRUCTIONS: u64 = 10_000_000;
+    /// Instructions run between two checks of [LuaHelper::MAX_INSTRUCTIONS]
+    const HOOK_INSTRUCTIONS: u32 = 1_000;
+    /// Bytes the script may allocate
+    const MAX_MEMORY: usize = 16 * 1024 * 1024;
+
+    pub fn from_script(script: &str) -> anyhow::Result<Self> {
+        let libs = StdLib::STRING | StdLib::TABLE | StdLib::MATH | StdLib::UTF8;
+        let lua = Lua::new_with(libs, LuaOptions::default()).map_err(|e| anyhow!("{}", e))?;
+        lua.set_memory_limit(Self::MAX_MEMORY).map_err(|e| anyhow!("{}", e))?;
+        let instructions = Arc::new(AtomicU64::new(0));
+        let counter = instructions.clone();
+        lua.set_hook(HookTriggers::new().every_nth_instruction(Self::HOOK_INSTRUCTIONS), move |_, _| {
+            if counter.fetch_add(Self::HOOK_INSTRUCTIONS as u64, Ordering::Relaxed) >= Self::MAX_INSTRUCTIONS {
+                return Err(mlua::Error::RuntimeError(format!("exceeded {} instructions", Self::MAX_INSTRUCTIONS)));
+            }
+            Ok(())
+        });
+        lua.load(script).exec().map_err(|e| anyhow!("Invalid lua script because {}", e))?;
+        ensure!(Self::defines(&lua, Self::GENERATE), "Lua helper script must define a 'generate(params, hash)' function");
+        let random = Self::defines(&lua, Self::VERIFY);
+        Ok(Self { lua: Mutex::new(lua), instructions, random })
+    }
+
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        Self::from_script(&fs::read_to_string(path)?)
+            .map_err(|e| anyhow!("Failed loading helper '{:?}': {}", path, e))
+    }
+
+    fn defines(lua: &Lua, name: &str) -> bool {
+        lua.globals().get::<_, Function>(name).is_ok()
+    }
+
+    fn call<R>(&self, name: &str, args: &HelperArgs, actual: Option<&str>, convert: impl FnOnce(&Lua, Value) -> anyhow::Result<R>) -> anyhow::Result<R> {
+        let lua = self.lua.lock().map_err(|_| anyhow!("Lua helper is poisoned"))?;
+        self.instructions.store(0, Ordering::Relaxed);
+        let result = lua.globals().get::<_, Function>(name)
+            .and_then(|f| {
+                let params = lua.to_value(&args.params)?;
+                let hash = lua.to_value(&args.hash)?;
+                f.call::<_, Value>((params, hash, actual))
+            })
+            .map_err(|e| anyhow!("Lua helper failed in '{}' because {}", name, e))?;
+        convert(&lua, result)
+    }
+}
+
+impl StubrHelper for LuaHelper {
+    fn generate(&self, args: &HelperArgs) -> anyhow::Result<String> {
+        self.call(Self::GENERATE, args, None, |lua, value| {
+            let type_name = value.type_name();
+            lua.coerce_string(value).ok().flatten()
+                .map(|s| s.to_string_lossy().to_string())
+                .ok_or_else(|| anyhow!("Lua helper 'generate' must return a string or a number but returned a '{}'", type_name))
+        })
+    }
+
+    fn is_random(&self) -> bool {
+        self.random
+    }
+
+    fn verify(&self, args: &HelperArgs, actual: &str) -> anyhow::Result<()> {
+        let valid = self.call(Self::VERIFY, args, Some(actual), |_, value| match value {
+            Value::Boolean(valid) => Ok(valid),
+            other => Err(anyhow!("Lua helper 'verify' must return a bool but returned a '{}'", other.type_name())),
+        })?;
+        ensure!(valid, "Expected response body to be verified by the helper but was '{}'", actual);
+        Ok(())
+    }
+}
+
+impl Helpers {
+    /// Loads every `*.lua` script of a directory as a [LuaHelper] named after the file
+    /// e.g. `iban.lua` is called with `{{iban}}`
+    pub fn from_lua_dir(dir: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Self::default().load_scripts(dir.as_ref(), LuaHelper::EXTENSION, LuaHelper::from_file)
+    }
+}
+
+#[cfg(test)]
+mod lua_helper_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn args(params: Vec<serde_json::Value>) -> HelperArgs {
+        HelperArgs { params, ..Default::default() }
+    }
+
+    #[test]
+    fn should_generate() {
+        let helper = LuaHelper::from_script("function generate(params, hash) return params[1] .. hash.suffix end").unwrap();
+        let args = HelperArgs { params: vec![json!("a")], hash: json!({"suffix": "b"}).as_object().unwrap().clone() };
+        assert_eq!(helper.generate(&args).unwrap(), "ab");
+        assert!(!helper.is_random());
+    }
+
+    #[test]
+    fn should_generate_numbers() {
+        let helper = LuaHelper::from_script("function generate(params, hash) return params[1] * 2 end").unwrap();
+        assert_eq!(helper.generate(&args(vec![json!(21)])).unwrap(), "42");
+    }
+
+    #[test]
+    fn should_fail_generating_other_types() {
+        let helper = LuaHelper::from_script("function generate(params, hash) return {} end").unwrap();
+        assert!(helper.generate(&args(vec![])).is_err());
+    }
+
+    #[test]
+    fn should_verify() {
+        let helper = LuaHelper::from_script(r#"
+            function generate(params, hash) return "123" end
+            function verify(params, hash, actual) return #actual == 3 end
+        "#).unwrap();
+        assert!(helper.is_random());
+        assert!(helper.verify(&args(vec![]), "456").is_ok());
+        assert!(helper.verify(&args(vec![]), "4567").is_err());
+    }
+
+    #[test]
+    fn should_stop_runaway_scripts() {
+        let endless = LuaHelper::from_script("function generate(params, hash) while true do end end").unwrap();
+        assert!(endless.generate(&args(vec![])).is_err());
+        let recursive = LuaHelper::from_script("local function f(n) return 1 + f(n + 1) end function generate(params, hash) return f(0) end").unwrap();
+        assert!(recursive.generate(&args(vec![])).is_err());
+        let huge = LuaHelper::from_script(r#"function generate(params, hash) local s = "a" while true do s = s .. s end end"#).unwrap();
+        assert!(huge.generate(&args(vec![])).is_err());
+    }
+
+    #[test]
+    fn should_not_expose_host_libraries() {
+        let helper = LuaHelper::from_script("function generate(params, hash) return os.getenv('HOME') end").unwrap();
+        assert!(helper.generate(&args(vec![])).is_err());
+        assert!(LuaHelper::from_script("io.open('/etc/passwd') function generate(params, hash) return '' end").is_err());
+    }
+
+    #[test]
+    fn should_fail_when_no_generate_function() {
+        assert!(LuaHelper::from_script("function verify(params, hash, actual) return true end").is_err());
+        assert!(LuaHelper::from_script("function generate(").is_err());
+    }
+
+    #[test]
+    fn should_load_scripts_from_dir() {
+        let helpers = Helpers::from_lua_dir("tests/helpers").unwrap();
+        assert!(helpers.iter().any(|(n, _)| n == "bic"));
+        assert!(helpers.is_random("bic"));
+    }
+
+    #[test]
+    fn should_load_scripts_of_any_language_from_dir() {
+        let helpers = Helpers::from_scripts_dir("tests/helpers").unwrap();
+        assert!(helpers.iter().any(|(n, _)| n == "iban"));
+        assert!(helpers.iter().any(|(n, _)| n == "bic"));
+    }
+}
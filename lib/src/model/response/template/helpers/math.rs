@@ -0,0 +1,77 @@
+use handlebars::{Context, Handlebars, Helper, HelperDef, RenderContext, RenderError, ScopedJson};
+use serde_json::{Number, Value};
+
+use super::utils_str::ParamExt;
+
+/// e.g. `{{math 1 '+' 2}}`. Integers stay integers except when a division has a remainder
+pub struct MathHelper;
+
+impl MathHelper {
+    pub const NAME: &'static str = "math";
+
+    fn compute(lhs: f64, op: &str, rhs: f64) -> Option<f64> {
+        match op {
+            "+" => Some(lhs + rhs),
+            "-" => Some(lhs - rhs),
+            "*" => Some(lhs * rhs),
+            "/" if rhs != 0.0 => Some(lhs / rhs),
+            "%" if rhs != 0.0 => Some(lhs % rhs),
+            _ => None,
+        }
+    }
+
+    fn to_json(result: f64, are_integers: bool) -> Value {
+        if are_integers && result.fract() == 0.0 {
+            Value::from(result as i64)
+        } else {
+            Number::from_f64(result).map(Value::Number).unwrap_or_default()
+        }
+    }
+
+    fn is_integer(value: f64) -> bool {
+        value.fract() == 0.0
+    }
+}
+
+impl HelperDef for MathHelper {
+    fn call_inner<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &'reg Handlebars<'reg>, _: &'rc Context, _: &mut RenderContext<'reg, 'rc>) -> Result<ScopedJson<'reg, 'rc>, RenderError> {
+        let lhs = h.param(0).and_then(|p| p.as_f64());
+        let op = h.param(1).and_then(|p| p.as_string());
+        let rhs = h.param(2).and_then(|p| p.as_f64());
+        lhs.zip(op).zip(rhs)
+            .and_then(|((lhs, op), rhs)| Self::compute(lhs, op.trim(), rhs).map(|r| (r, Self::is_integer(lhs) && Self::is_integer(rhs))))
+            .map(|(result, are_integers)| Self::to_json(result, are_integers))
+            .map(ScopedJson::from)
+            .ok_or_else(|| RenderError::new("Invalid math response template, expected e.g. {{math 1 '+' 2}}"))
+    }
+}
+
+#[cfg(test)]
+mod math_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn should_compute() {
+        assert_eq!(MathHelper::compute(1.0, "+", 2.0), Some(3.0));
+        assert_eq!(MathHelper::compute(1.0, "-", 2.0), Some(-1.0));
+        assert_eq!(MathHelper::compute(3.0, "*", 2.0), Some(6.0));
+        assert_eq!(MathHelper::compute(3.0, "/", 2.0), Some(1.5));
+        assert_eq!(MathHelper::compute(7.0, "%", 4.0), Some(3.0));
+    }
+
+    #[test]
+    fn should_fail_when_unknown_operator_or_division_by_zero() {
+        assert!(MathHelper::compute(1.0, "^", 2.0).is_none());
+        assert!(MathHelper::compute(1.0, "/", 0.0).is_none());
+        assert!(MathHelper::compute(1.0, "%", 0.0).is_none());
+    }
+
+    #[test]
+    fn integers_should_stay_integers() {
+        assert_eq!(MathHelper::to_json(3.0, true), json!(3));
+        assert_eq!(MathHelper::to_json(1.5, true), json!(1.5));
+        assert_eq!(MathHelper::to_json(3.0, false), json!(3.0));
+    }
+}
@@ -8,4 +8,17 @@ pub mod url_encode;
 pub mod string;
 pub mod size;
 pub mod verify;
-pub mod any;
\ No newline at end of file
+pub mod any;
+pub mod math;
+pub mod regex_extract;
+pub mod array;
+pub mod date;
+pub mod json;
+pub mod condition;
+pub mod val;
+pub mod custom;
+#[cfg(feature = "helpers-rhai")]
+pub mod rhai;
+#[cfg(feature = "helpers-lua")]
+pub mod lua;
+pub mod jwt;
\ No newline at end of file
@@ -0,0 +1,72 @@
+use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext, RenderError};
+use regex::Regex;
+use serde_json::Value;
+
+use super::utils_str::ParamExt;
+
+/// Extracts the first match of a regex e.g. `{{regexExtract request.body '[A-Z]+'}}`.
+/// Given a variable name e.g. `{{regexExtract request.body '([a-z]+)-([0-9]+)' 'parts'}}` it
+/// renders nothing but exposes the captured groups to the rest of the template e.g. `{{parts.[1]}}`
+pub struct RegexExtractHelper;
+
+impl RegexExtractHelper {
+    pub const NAME: &'static str = "regexExtract";
+    const DEFAULT: &'static str = "default";
+
+    fn first_match(value: &str, regex: &Regex) -> Option<String> {
+        regex.find(value).map(|m| m.as_str().to_string())
+    }
+
+    fn groups(value: &str, regex: &Regex) -> Option<Vec<Value>> {
+        regex.captures(value).map(|c| {
+            c.iter().skip(1)
+                .map(|g| g.map(|m| Value::from(m.as_str())).unwrap_or_default())
+                .collect()
+        })
+    }
+
+    fn default(h: &Helper) -> Option<String> {
+        h.hash_get(Self::DEFAULT).and_then(|d| d.as_string())
+    }
+}
+
+impl HelperDef for RegexExtractHelper {
+    fn call<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &'reg Handlebars<'reg>, ctx: &'rc Context, rc: &mut RenderContext<'reg, 'rc>, out: &mut dyn Output) -> HelperResult {
+        let value = h.param(0).and_then(|p| p.as_string()).unwrap_or_default();
+        let regex = h.param(1).and_then(|p| p.as_string())
+            .ok_or_else(|| RenderError::new("Missing regex in regexExtract response template"))?;
+        let regex = Regex::new(&regex).map_err(|e| RenderError::new(format!("Invalid regex in regexExtract response template: {}", e)))?;
+        if let Some(var) = h.param(2).and_then(|p| p.as_string()) {
+            let groups = Self::groups(&value, &regex).unwrap_or_default();
+            let mut data = rc.context().map(|c| c.data().clone()).unwrap_or_else(|| ctx.data().clone());
+            if let Some(data) = data.as_object_mut() {
+                data.insert(var, Value::Array(groups));
+            }
+            rc.set_context(Context::wraps(data)?);
+            return Ok(());
+        }
+        let extracted = Self::first_match(&value, &regex)
+            .or_else(|| Self::default(h))
+            .ok_or_else(|| RenderError::new(format!("Nothing matched '{}' in regexExtract response template", regex)))?;
+        out.write(&extracted).map_err(RenderError::from)
+    }
+}
+
+#[cfg(test)]
+mod regex_extract_tests {
+    use super::*;
+
+    #[test]
+    fn should_extract_first_match() {
+        let regex = Regex::new("[A-Z]+").unwrap();
+        assert_eq!(RegexExtractHelper::first_match("abc DEF GHI", &regex), Some(String::from("DEF")));
+        assert_eq!(RegexExtractHelper::first_match("abc", &regex), None);
+    }
+
+    #[test]
+    fn should_extract_groups() {
+        let regex = Regex::new("([a-z]+)-([0-9]+)(x)?").unwrap();
+        let groups = RegexExtractHelper::groups("id: abc-123", &regex).unwrap();
+        assert_eq!(groups, vec![Value::from("abc"), Value::from("123"), Value::Null]);
+    }
+}
@@ -0,0 +1,157 @@
+use std::{fs, path::Path};
+
+use anyhow::{anyhow, ensure};
+use rhai::{AST, Dynamic, Engine, Scope, serde::to_dynamic};
+
+use super::custom::{HelperArgs, Helpers, StubrHelper};
+
+/// A [StubrHelper] written as a [Rhai](https://rhai.rs) script.
+/// The script defines `fn generate(params, hash)` returning the rendered value. It is a random
+/// helper when it also defines `fn verify(params, hash, actual)` returning whether the value a
+/// producer actually returned is valid.
+///
+/// ```rhai
+/// fn generate(params, hash) {
+///     let country = if params.is_empty() { "FR" } else { params[0] };
+///     country + "7630006000011234567890189"
+/// }
+///
+/// fn verify(params, hash, actual) {
+///     actual.len() > 4
+/// }
+/// ```
+pub struct RhaiHelper {
+    engine: Engine,
+    ast: AST,
+}
+
+impl RhaiHelper {
+    pub(crate) const EXTENSION: &'static str = "rhai";
+    const GENERATE: &'static str = "generate";
+    const VERIFY: &'static str = "verify";
+    /// Operations a single call may run
+    const MAX_OPERATIONS: u64 = 1_000_000;
+    /// Depth of nested function calls
+    const MAX_CALL_LEVELS: usize = 64;
+    /// Depth of nested expressions
+    const MAX_EXPR_DEPTH: usize = 64;
+    /// Length of a string, array or map
+    const MAX_SIZE: usize = 1_000_000;
+
+    pub fn from_script(script: &str) -> anyhow::Result<Self> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(Self::MAX_OPERATIONS)
+            .set_max_call_levels(Self::MAX_CALL_LEVELS)
+            .set_max_expr_depths(Self::MAX_EXPR_DEPTH, Self::MAX_EXPR_DEPTH)
+            .set_max_string_size(Self::MAX_SIZE)
+            .set_max_array_size(Self::MAX_SIZE)
+            .set_max_map_size(Self::MAX_SIZE);
+        let ast = engine.compile(script).map_err(|e| anyhow!("Invalid rhai script because {}", e))?;
+        ensure!(Self::defines(&ast, Self::GENERATE), "Rhai helper script must define a 'generate(params, hash)' function");
+        Ok(Self { engine, ast })
+    }
+
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        Self::from_script(&fs::read_to_string(path)?)
+            .map_err(|e| anyhow!("Failed loading helper '{:?}': {}", path, e))
+    }
+
+    fn defines(ast: &AST, name: &str) -> bool {
+        ast.iter_functions().any(|f| f.name == name)
+    }
+
+    fn call(&self, name: &str, args: &HelperArgs, actual: Option<&str>) -> anyhow::Result<Dynamic> {
+        let params = to_dynamic(&args.params).map_err(|e| anyhow!("{}", e))?;
+        let hash = to_dynamic(&args.hash).map_err(|e| anyhow!("{}", e))?;
+        let mut scope = Scope::new();
+        let result = match actual {
+            Some(actual) => self.engine.call_fn::<Dynamic>(&mut scope, &self.ast, name, (params, hash, actual.to_string())),
+            None => self.engine.call_fn::<Dynamic>(&mut scope, &self.ast, name, (params, hash)),
+        };
+        result.map_err(|e| anyhow!("Rhai helper failed in '{}' because {}", name, e))
+    }
+}
+
+impl StubrHelper for RhaiHelper {
+    fn generate(&self, args: &HelperArgs) -> anyhow::Result<String> {
+        self.call(Self::GENERATE, args, None).map(|v| v.to_string())
+    }
+
+    fn is_random(&self) -> bool {
+        Self::defines(&self.ast, Self::VERIFY)
+    }
+
+    fn verify(&self, args: &HelperArgs, actual: &str) -> anyhow::Result<()> {
+        let valid = self.call(Self::VERIFY, args, Some(actual))?
+            .as_bool().map_err(|t| anyhow!("Rhai helper 'verify' must return a bool but returned a '{}'", t))?;
+        ensure!(valid, "Expected response body to be verified by the helper but was '{}'", actual);
+        Ok(())
+    }
+}
+
+impl Helpers {
+    /// Loads every `*.rhai` script of a directory as a [RhaiHelper] named after the file
+    /// e.g. `iban.rhai` is called with `{{iban}}`
+    pub fn from_rhai_dir(dir: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Self::default().load_scripts(dir.as_ref(), RhaiHelper::EXTENSION, RhaiHelper::from_file)
+    }
+}
+
+#[cfg(test)]
+mod rhai_helper_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn args(params: Vec<serde_json::Value>) -> HelperArgs {
+        HelperArgs { params, ..Default::default() }
+    }
+
+    #[test]
+    fn should_generate() {
+        let helper = RhaiHelper::from_script("fn generate(params, hash) { params[0] + hash.suffix }").unwrap();
+        let args = HelperArgs { params: vec![json!("a")], hash: json!({"suffix": "b"}).as_object().unwrap().clone() };
+        assert_eq!(helper.generate(&args).unwrap(), "ab");
+        assert!(!helper.is_random());
+    }
+
+    #[test]
+    fn should_generate_numbers() {
+        let helper = RhaiHelper::from_script("fn generate(params, hash) { params[0] * 2 }").unwrap();
+        assert_eq!(helper.generate(&args(vec![json!(21)])).unwrap(), "42");
+    }
+
+    #[test]
+    fn should_verify() {
+        let helper = RhaiHelper::from_script(r#"
+            fn generate(params, hash) { "123" }
+            fn verify(params, hash, actual) { actual.len() == 3 }
+        "#).unwrap();
+        assert!(helper.is_random());
+        assert!(helper.verify(&args(vec![]), "456").is_ok());
+        assert!(helper.verify(&args(vec![]), "4567").is_err());
+    }
+
+    #[test]
+    fn should_stop_runaway_scripts() {
+        let endless = RhaiHelper::from_script("fn generate(params, hash) { loop {} }").unwrap();
+        assert!(endless.generate(&args(vec![])).is_err());
+        let recursive = RhaiHelper::from_script("fn f(n) { f(n + 1) } fn generate(params, hash) { f(0) }").unwrap();
+        assert!(recursive.generate(&args(vec![])).is_err());
+        let huge = RhaiHelper::from_script(r#"fn generate(params, hash) { let s = "a"; loop { s += s; } }"#).unwrap();
+        assert!(huge.generate(&args(vec![])).is_err());
+    }
+
+    #[test]
+    fn should_fail_when_no_generate_function() {
+        assert!(RhaiHelper::from_script("fn verify(params, hash, actual) { true }").is_err());
+        assert!(RhaiHelper::from_script("fn generate(").is_err());
+    }
+
+    #[test]
+    fn should_load_scripts_from_dir() {
+        let helpers = Helpers::from_rhai_dir("tests/helpers").unwrap();
+        assert!(helpers.iter().any(|(n, _)| n == "iban"));
+        assert!(helpers.is_random("iban"));
+    }
+}
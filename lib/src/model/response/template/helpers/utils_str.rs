@@ -1,3 +1,6 @@
+use handlebars::PathAndJson;
+use serde_json::Value;
+
 pub trait ValueExt {
     const QUOTE: char = '\'';
 
@@ -14,4 +17,35 @@ impl ValueExt for str {
     fn escape_single_quotes(&self) -> &str {
         self.trim_start_matches(Self::QUOTE).trim_end_matches(Self::QUOTE)
     }
-}
\ No newline at end of file
+}
+
+/// Reads a helper parameter whether it was resolved from the request or written as a literal
+pub trait ParamExt {
+    /// Single quoted literals e.g. `'abc'` are not resolved by handlebars hence read from their path
+    fn as_json(&self) -> Value;
+
+    fn as_string(&self) -> Option<String> {
+        match self.as_json() {
+            Value::String(s) => Some(s),
+            Value::Null => None,
+            v => Some(v.to_string()),
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self.as_json() {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+impl ParamExt for PathAndJson<'_, '_> {
+    fn as_json(&self) -> Value {
+        match self.relative_path() {
+            Some(path) if self.value().is_null() && path.starts_with(<str as ValueExt>::QUOTE) => Value::String(path.escape_single_quotes().to_string()),
+            _ => self.value().clone(),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use handlebars::{Context, Handlebars, Helper, HelperDef, RenderContext, RenderError, ScopedJson};
+use serde_json::Value;
+
+use super::utils_str::ParamExt;
+
+/// A value or a default one when missing e.g. `{{val request.query.page default=1}}`
+pub struct ValHelper;
+
+impl ValHelper {
+    pub const NAME: &'static str = "val";
+    const DEFAULT: &'static str = "default";
+
+    fn value(h: &Helper) -> Value {
+        h.param(0).map(ParamExt::as_json)
+            .filter(|v| !v.is_null())
+            .or_else(|| h.hash_get(Self::DEFAULT).map(ParamExt::as_json))
+            .unwrap_or_default()
+    }
+}
+
+impl HelperDef for ValHelper {
+    fn call_inner<'reg: 'rc, 'rc>(&self, h: &Helper<'reg, 'rc>, _: &'reg Handlebars<'reg>, _: &'rc Context, _: &mut RenderContext<'reg, 'rc>) -> Result<ScopedJson<'reg, 'rc>, RenderError> {
+        Ok(ScopedJson::from(Self::value(h)))
+    }
+}
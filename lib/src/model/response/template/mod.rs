@@ -1,6 +1,9 @@
-use std::sync::RwLock;
+use std::{collections::HashMap, sync::Arc};
 
-use handlebars::Handlebars;
+use handlebars::{Context, Handlebars, RenderContext, Renderable, RenderError, StringOutput, Template};
+#[cfg(feature = "verify")]
+use handlebars::template::TemplateElement;
+use http_types::StatusCode;
 use serde::Serialize;
 use wiremock::{Request, Respond, ResponseTemplate};
 
@@ -21,33 +24,64 @@ use helpers::{
         non_empty::AnyNonEmpty,
         number::AnyNumber,
         of::AnyOf,
+        pick_random::PickRandom,
+        random_int::RandomInt,
         regex::AnyRegex,
         time::AnyTime,
         uuid::AnyUuid,
     },
+    array::ArrayHelper,
     base64::Base64Helper,
+    condition::ConditionHelper,
+    custom::{CustomHelper, Helpers},
+    date::DateHelper,
     datetime::NowHelper,
+    json::JsonHelper,
     json_path::JsonPathHelper,
+    jwt::{JwtHelper, JwtKey},
+    math::MathHelper,
     numbers::NumberHelper,
+    regex_extract::RegexExtractHelper,
     size::SizeHelper,
     string::StringHelper,
     trim::TrimHelper,
     url_encode::UrlEncodingHelper,
+    val::ValHelper,
 };
+#[cfg(feature = "verify")]
+use verify::Predictable;
 
 use crate::{
     cloud::opentracing::OpenTracing,
-    model::response::{ResponseStub, template::data::RequestData},
+    model::response::{default::WiremockIsoResponse, delay::Delay, ResponseAppender, ResponseStub, template::data::RequestData},
 };
 
 pub mod data;
 pub mod verify;
 pub mod utils;
 mod req_ext;
-mod helpers;
+pub(crate) mod helpers;
 
-lazy_static! {
-    pub(crate) static ref HANDLEBARS: RwLock<Handlebars<'static>> = {
+/// Handlebars registry of a [crate::Stubr] holding every helper. Templates are not registered in
+/// it but compiled once per stub, so that rendering one never takes a lock
+#[derive(Clone)]
+pub struct Templates {
+    registry: Arc<Handlebars<'static>>,
+    /// user-defined ones, also registered in the registry
+    #[cfg(feature = "verify")]
+    helpers: Helpers,
+    /// signs the tokens of the `jwt` helper
+    jwt_key: JwtKey,
+}
+
+impl Templates {
+    /// Builtin helpers along with user-defined ones. The latter can override the former
+    pub fn new(helpers: &Helpers) -> Self {
+        Self::with_jwt_key(helpers, JwtKey::generate())
+    }
+
+    /// Same as [Templates::new] with the key the `jwt` helper signs tokens with
+    pub fn with_jwt_key(helpers: &Helpers, jwt_key: JwtKey) -> Self {
         let mut handlebars = Handlebars::new();
         handlebars.register_helper(JsonPathHelper::NAME, Box::new(JsonPathHelper));
         handlebars.register_helper(NowHelper::NAME, Box::new(NowHelper));
@@ -86,57 +120,193 @@ lazy_static! {
         handlebars.register_helper(AnyDatetime::NAME, Box::new(AnyDatetime));
         handlebars.register_helper(AnyIso8601Datetime::NAME, Box::new(AnyIso8601Datetime));
         handlebars.register_helper(AnyOf::NAME, Box::new(AnyOf));
-        RwLock::new(handlebars)
-    };
+        handlebars.register_helper(PickRandom::NAME, Box::new(PickRandom));
+        handlebars.register_helper(RandomInt::NAME, Box::new(RandomInt));
+        handlebars.register_helper(MathHelper::NAME, Box::new(MathHelper));
+        handlebars.register_helper(RegexExtractHelper::NAME, Box::new(RegexExtractHelper));
+        handlebars.register_helper(ArrayHelper::ARRAY, Box::new(ArrayHelper));
+        handlebars.register_helper(ArrayHelper::ADD, Box::new(ArrayHelper));
+        handlebars.register_helper(ArrayHelper::REMOVE, Box::new(ArrayHelper));
+        handlebars.register_helper(ArrayHelper::RANGE, Box::new(ArrayHelper));
+        handlebars.register_helper(DateHelper::PARSE, Box::new(DateHelper));
+        handlebars.register_helper(DateHelper::FORMAT, Box::new(DateHelper));
+        handlebars.register_helper(DateHelper::TRUNCATE, Box::new(DateHelper));
+        handlebars.register_helper(JsonHelper::FORMAT_JSON, Box::new(JsonHelper));
+        handlebars.register_helper(JsonHelper::TO_JSON, Box::new(JsonHelper));
+        handlebars.register_helper(ConditionHelper::CONTAINS, Box::new(ConditionHelper));
+        handlebars.register_helper(ConditionHelper::MATCHES, Box::new(ConditionHelper));
+        handlebars.register_helper(ValHelper::NAME, Box::new(ValHelper));
+        handlebars.register_helper(JwtHelper::JWT, Box::new(JwtHelper(jwt_key.clone())));
+        handlebars.register_helper(JwtHelper::JWKS, Box::new(JwtHelper(jwt_key.clone())));
+        for (name, helper) in helpers.iter() {
+            handlebars.register_helper(name, Box::new(CustomHelper(helper.clone())));
+        }
+        Self {
+            registry: Arc::new(handlebars),
+            #[cfg(feature = "verify")]
+            helpers: helpers.clone(),
+            jwt_key,
+        }
+    }
+
+    pub fn jwt_key(&self) -> &JwtKey {
+        &self.jwt_key
+    }
+
+    /// Whether a template always renders the same given the same request, knowing about
+    /// random user-defined helpers contrary to [Predictable]
+    #[cfg(feature = "verify")]
+    pub fn is_predictable(&self, template: &str) -> bool {
+        Template::compile(template)
+            .map(|t| t.elements.iter().all(|e| e.is_predictable() && !self.is_random(e)))
+            .unwrap_or_default()
+    }
+
+    #[cfg(feature = "verify")]
+    fn is_random(&self, element: &TemplateElement) -> bool {
+        match element {
+            TemplateElement::Expression(e) => e.name.as_name().map(|n| self.helpers.is_random(n)).unwrap_or_default(),
+            _ => false,
+        }
+    }
+}
+
+impl Default for Templates {
+    fn default() -> Self {
+        Self::new(&Helpers::default())
+    }
+}
+
+/// Renders the templates of a stub's response with its server's [Templates]
+pub struct TemplateRenderer {
+    registry: Templates,
+    /// keyed by their source
+    compiled: HashMap<String, Template>,
+}
+
+impl TemplateRenderer {
+    pub fn new(registry: Templates) -> Self {
+        Self { registry, compiled: HashMap::new() }
+    }
+
+    /// Compiles a template once and for all. Invalid ones are ignored and render nothing
+    pub fn register(&mut self, content: &str) {
+        if !self.compiled.contains_key(content) {
+            if let Ok(template) = Template::compile(content) {
+                self.compiled.insert(content.to_string(), template);
+            }
+        }
+    }
+
+    #[cfg(feature = "verify")]
+    pub fn templates(&self) -> &Templates {
+        &self.registry
+    }
+
+    pub fn render<T: Serialize>(&self, content: &str, data: &T) -> String {
+        self.try_render(content, data).unwrap_or_default()
+    }
+
+    /// Same as [`TemplateRenderer::render`] but does not swallow errors. A template not
+    /// registered first gets compiled on the fly
+    pub fn try_render<T: Serialize>(&self, content: &str, data: &T) -> Result<String, RenderError> {
+        let ctx = Context::wraps(data)?;
+        let mut out = StringOutput::new();
+        let handlebars = self.registry.registry.as_ref();
+        // helpers read the template source as the root template name
+        if let Some((name, template)) = self.compiled.get_key_value(content) {
+            template.render(handlebars, &ctx, &mut RenderContext::new(Some(name)), &mut out)?;
+        } else {
+            let name = content.to_string();
+            Template::compile(content)?.render(handlebars, &ctx, &mut RenderContext::new(Some(&name)), &mut out)?;
+        }
+        out.into_string().map_err(RenderError::from)
+    }
 }
 
 pub struct StubTemplate {
     pub(crate) template: ResponseTemplate,
     pub(crate) response: ResponseStub,
-    pub(crate) requires_templating: bool,
+    /// Only when the response requires templating
+    pub(crate) renderer: Option<TemplateRenderer>,
+    /// Along with [StubTemplate::delay], rebuilds the response when its status is templated
+    pub(crate) uuid: Option<String>,
+    pub(crate) delay: Delay,
+}
+
+impl StubTemplate {
+    /// Response with its templated status and delay rendered, when they are
+    fn render_base(&self, data: &HandlebarsData, renderer: &TemplateRenderer) -> ResponseTemplate {
+        let mut resp = match self.response.status.as_ref().filter(|s| s.template().is_some()) {
+            Some(status) => {
+                let status = status.render(data, renderer)
+                    .filter(|s| StatusCode::try_from(*s).is_ok())
+                    .unwrap_or(ResponseStub::INVALID_STATUS);
+                let resp = WiremockIsoResponse(self.uuid.as_deref()).add(ResponseTemplate::new(status));
+                self.delay.add(resp, self.response.fixed_delay())
+            }
+            None => self.template.clone(),
+        };
+        if let Some(delay) = self.response.fixed_delay_milliseconds.as_ref().filter(|d| d.template().is_some()) {
+            resp = self.delay.add(resp, delay.render(data, renderer));
+        }
+        resp
+    }
 }
 
 impl Respond for StubTemplate {
     fn respond(&self, req: &Request) -> ResponseTemplate {
-        let mut resp = self.template.clone();
-        resp = OpenTracing(req).add_opentracing_header(resp, self.response.defined_header_keys());
-        if self.requires_templating {
+        if let Some(renderer) = self.renderer.as_ref() {
             let data = HandlebarsData {
                 request: &RequestData::from(req),
                 response: None,
                 stub_name: None,
                 is_verify: false,
             };
-            resp = self.response.body.render_response_template(resp, &data);
-            resp = self.response.headers.render_response_template(resp, &data);
+            let mut resp = self.render_base(&data, renderer);
+            resp = OpenTracing(req).add_opentracing_header(resp, self.response.defined_header_keys());
+            resp = self.response.body.render_response_template(resp, &data, renderer);
+            self.response.headers.render_response_template(resp, &data, renderer)
+        } else {
+            let resp = self.response.body.add_lazy(self.template.clone());
+            OpenTracing(req).add_opentracing_header(resp, self.response.defined_header_keys())
         }
-        resp
     }
 }
 
 pub trait HandlebarTemplatable {
-    fn register_template(&self);
-    fn render_response_template(&self, template: ResponseTemplate, data: &HandlebarsData) -> ResponseTemplate;
+    fn register_template(&self, renderer: &mut TemplateRenderer);
+    fn render_response_template(&self, template: ResponseTemplate, data: &HandlebarsData, renderer: &TemplateRenderer) -> ResponseTemplate;
+}
 
-    fn register<S: AsRef<str>>(&self, name: &str, content: S) {
-        if let Ok(mut handlebars) = HANDLEBARS.write() {
-            handlebars.register_template_string(name, content).unwrap_or_default();
-        }
+#[cfg(test)]
+mod template_renderer_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn should_render_registered_template() {
+        let mut renderer = TemplateRenderer::new(Templates::default());
+        renderer.register("{{upper name}}");
+        assert_eq!(renderer.compiled.len(), 1);
+        renderer.register("{{upper name}}");
+        assert_eq!(renderer.compiled.len(), 1);
+        assert_eq!(renderer.render("{{upper name}}", &json!({"name": "john"})), "JOHN");
     }
 
-    /// Template has to be registered first before being rendered here
-    /// Better for performances
-    fn render<T: Serialize>(&self, name: &str, data: &T) -> String {
-        HANDLEBARS.read().ok()
-            .and_then(|it| it.render(name, data).ok())
-            .unwrap_or_default()
+    #[test]
+    fn should_render_unregistered_template() {
+        let renderer = TemplateRenderer::new(Templates::default());
+        assert_eq!(renderer.render("{{lower name}}", &json!({"name": "JOHN"})), "john");
     }
 
-    /// Template does not have to be registered first
-    /// Simpler
-    fn render_template<T: Serialize>(&self, name: &str, data: &T) -> String {
-        HANDLEBARS.read().ok()
-            .and_then(|it| it.render_template(name, data).ok())
-            .unwrap_or_default()
+    #[test]
+    fn should_render_nothing_when_invalid() {
+        let mut renderer = TemplateRenderer::new(Templates::default());
+        renderer.register("{{#if}}");
+        assert!(renderer.compiled.is_empty());
+        assert!(renderer.try_render("{{#if}}", &json!({})).is_err());
+        assert_eq!(renderer.render("{{#if}}", &json!({})), "");
     }
 }
\ No newline at end of file
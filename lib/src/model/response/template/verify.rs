@@ -1,4 +1,6 @@
-use handlebars::{RenderContext, Template, template::TemplateElement};
+use std::{error::Error, fmt::{Display, Formatter}};
+
+use handlebars::{RenderContext, RenderError, Template, template::TemplateElement};
 
 use super::{
     AnyAlphaNumeric,
@@ -18,6 +20,9 @@ use super::{
     AnyHostname,
     AnyIp,
     AnyOf,
+    PickRandom,
+    RandomInt,
+    JwtHelper,
 };
 
 /// Some templates can be made of many elements e.g. '{{anyNonBlankString}}{{anyNonEmptyString}}'.
@@ -55,7 +60,7 @@ impl Verifiable for RenderContext<'_, '_> {
 }
 
 pub trait Predictable {
-    const RND_NAMES: [&'static str; 24] = [
+    const RND_NAMES: [&'static str; 28] = [
         AnyRegex::NAME,
         AnyNonBlank::NAME,
         AnyNonEmpty::NAME,
@@ -80,6 +85,10 @@ pub trait Predictable {
         AnyHostname::NAME,
         AnyIp::NAME,
         AnyOf::NAME,
+        PickRandom::NAME,
+        RandomInt::NAME,
+        JwtHelper::JWT,
+        JwtHelper::JWKS,
     ];
 
     fn is_predictable(&self) -> bool;
@@ -109,6 +118,37 @@ impl Predictable for str {
     }
 }
 
+/// A random template helper rejected the actual response value.
+/// Travels as the cause of a [`RenderError`] so verifiers can tell it apart from a plain rendering failure.
+#[derive(Debug)]
+pub struct TemplateMismatch(pub String);
+
+impl TemplateMismatch {
+    pub fn find(err: &RenderError) -> Option<&Self> {
+        err.source().and_then(|e| e.downcast_ref::<Self>())
+    }
+}
+
+impl Display for TemplateMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for TemplateMismatch {}
+
+impl From<anyhow::Error> for TemplateMismatch {
+    fn from(e: anyhow::Error) -> Self {
+        Self(e.to_string())
+    }
+}
+
+impl From<TemplateMismatch> for RenderError {
+    fn from(e: TemplateMismatch) -> Self {
+        RenderError::from_error(&e.0.clone(), e)
+    }
+}
+
 #[cfg(test)]
 mod verifiable_test {
     use super::*;
@@ -0,0 +1,139 @@
+use std::{collections::HashMap, sync::{Arc, RwLock}};
+
+use serde::{Deserialize, Serialize};
+use wiremock::{Match, Request, Respond, ResponseTemplate};
+
+/// Makes a stub stateful. It only matches when its scenario is in the required state and moves
+/// the scenario to a new state once it has responded
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioStub {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scenario_name: Option<String>,
+    /// Defaults to [Scenarios::STARTED]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_scenario_state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_scenario_state: Option<String>,
+}
+
+impl ScenarioStub {
+    pub(crate) fn matcher(&self, scenarios: &Scenarios) -> Option<ScenarioStateMatcher> {
+        self.scenario_name.as_ref().map(|name| ScenarioStateMatcher {
+            scenarios: scenarios.clone(),
+            name: name.to_string(),
+            state: self.required_scenario_state.as_deref().unwrap_or(Scenarios::STARTED).to_string(),
+        })
+    }
+
+    pub(crate) fn transition(&self, scenarios: &Scenarios) -> Option<ScenarioTransition> {
+        self.scenario_name.as_ref()
+            .zip(self.new_scenario_state.as_ref())
+            .map(|(name, state)| ScenarioTransition {
+                scenarios: scenarios.clone(),
+                name: name.to_string(),
+                state: state.to_string(),
+            })
+    }
+}
+
+/// Current state of every scenario of a server. A scenario not transitioned yet is in
+/// [Scenarios::STARTED] state
+#[derive(Debug, Default, Clone)]
+pub struct Scenarios(Arc<RwLock<HashMap<String, String>>>);
+
+impl Scenarios {
+    pub const STARTED: &'static str = "Started";
+
+    pub fn state(&self, name: &str) -> String {
+        self.0.read().ok()
+            .and_then(|states| states.get(name).cloned())
+            .unwrap_or_else(|| Self::STARTED.to_string())
+    }
+
+    fn set(&self, name: &str, state: &str) {
+        if let Ok(mut states) = self.0.write() {
+            states.insert(name.to_string(), state.to_string());
+        }
+    }
+
+    /// Moves every scenario back to [Scenarios::STARTED]
+    pub fn reset(&self) {
+        if let Ok(mut states) = self.0.write() {
+            states.clear();
+        }
+    }
+}
+
+pub struct ScenarioStateMatcher {
+    scenarios: Scenarios,
+    name: String,
+    state: String,
+}
+
+impl Match for ScenarioStateMatcher {
+    fn matches(&self, _: &Request) -> bool {
+        self.scenarios.state(&self.name) == self.state
+    }
+}
+
+pub struct ScenarioTransition {
+    scenarios: Scenarios,
+    name: String,
+    state: String,
+}
+
+/// Applies a scenario transition, if any, after responding
+pub struct Transitioning<R: Respond> {
+    pub respond: R,
+    pub transition: Option<ScenarioTransition>,
+}
+
+impl<R: Respond> Respond for Transitioning<R> {
+    fn respond(&self, req: &Request) -> ResponseTemplate {
+        let resp = self.respond.respond(req);
+        if let Some(ScenarioTransition { scenarios, name, state }) = self.transition.as_ref() {
+            scenarios.set(name, state);
+        }
+        resp
+    }
+}
+
+#[cfg(test)]
+mod scenario_tests {
+    use super::*;
+
+    fn stub(required: Option<&str>, new: Option<&str>) -> ScenarioStub {
+        ScenarioStub {
+            scenario_name: Some(String::from("todo")),
+            required_scenario_state: required.map(str::to_string),
+            new_scenario_state: new.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn should_start_in_started_state() {
+        let scenarios = Scenarios::default();
+        assert_eq!(scenarios.state("todo"), Scenarios::STARTED);
+        assert!(stub(None, None).matcher(&scenarios).is_some());
+    }
+
+    #[test]
+    fn should_not_be_stateful_without_scenario_name() {
+        let scenarios = Scenarios::default();
+        let stub = ScenarioStub { new_scenario_state: Some(String::from("a")), ..Default::default() };
+        assert!(stub.matcher(&scenarios).is_none());
+        assert!(stub.transition(&scenarios).is_none());
+    }
+
+    #[test]
+    fn should_transition_then_reset() {
+        let scenarios = Scenarios::default();
+        let transition = stub(None, Some("done")).transition(&scenarios).unwrap();
+        scenarios.set(&transition.name, &transition.state);
+        assert_eq!(scenarios.state("todo"), "done");
+        assert_eq!(scenarios.state("other"), Scenarios::STARTED);
+        scenarios.reset();
+        assert_eq!(scenarios.state("todo"), Scenarios::STARTED);
+    }
+}
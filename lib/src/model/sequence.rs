@@ -0,0 +1,136 @@
+use std::sync::{Arc, atomic::{AtomicUsize, Ordering}, Mutex};
+
+use rand::{distributions::{Distribution, WeightedIndex}, Rng, thread_rng};
+use serde::{Deserialize, Serialize};
+use wiremock::{Request, Respond, ResponseTemplate};
+
+use super::response::template::StubTemplate;
+
+/// How a stub picks one of its `responses` at every call, instead of matching them conditionally
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum SequencePolicy {
+    /// Every response in order, then the last one forever e.g. a 500 then a 200 for retries
+    OnceEachThenLast,
+    /// Every response in order, over and over
+    Cycle,
+    Random,
+    /// Randomly, according to each response's `weight`
+    Weighted,
+}
+
+impl SequencePolicy {
+    /// Index of the response to serve
+    /// * `calls` - number of calls so far, incremented
+    /// * `weights` - of each response
+    fn pick(&self, calls: &AtomicUsize, weights: &[u32]) -> usize {
+        let len = weights.len();
+        match self {
+            Self::OnceEachThenLast => calls.fetch_add(1, Ordering::SeqCst).min(len - 1),
+            Self::Cycle => calls.fetch_add(1, Ordering::SeqCst) % len,
+            Self::Random => thread_rng().gen_range(0..len),
+            Self::Weighted => WeightedIndex::new(weights).map(|w| w.sample(&mut thread_rng())).unwrap_or_default(),
+        }
+    }
+}
+
+/// Calls made to every sequenced stub of a server
+#[derive(Debug, Default, Clone)]
+pub struct Sequences(Arc<Mutex<Vec<Arc<AtomicUsize>>>>);
+
+impl Sequences {
+    fn calls(&self) -> Arc<AtomicUsize> {
+        let calls = Arc::new(AtomicUsize::new(0));
+        if let Ok(mut all) = self.0.lock() {
+            all.push(calls.clone());
+        }
+        calls
+    }
+
+    /// Starts every sequence over
+    pub fn reset(&self) {
+        if let Ok(all) = self.0.lock() {
+            all.iter().for_each(|calls| calls.store(0, Ordering::SeqCst));
+        }
+    }
+}
+
+/// Responses of a stub served according to its [SequencePolicy]
+pub struct StubSequence {
+    policy: SequencePolicy,
+    responses: Vec<StubTemplate>,
+    weights: Vec<u32>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl StubSequence {
+    pub const DEFAULT_WEIGHT: u32 = 1;
+
+    /// * `responses` - along with their weight
+    pub fn new(policy: SequencePolicy, responses: Vec<(StubTemplate, u32)>, sequences: &Sequences) -> Self {
+        let (responses, weights) = responses.into_iter().unzip();
+        Self { policy, responses, weights, calls: sequences.calls() }
+    }
+}
+
+impl Respond for StubSequence {
+    fn respond(&self, req: &Request) -> ResponseTemplate {
+        self.responses[self.policy.pick(&self.calls, &self.weights)].respond(req)
+    }
+}
+
+#[cfg(test)]
+mod sequence_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn picks(policy: SequencePolicy, weights: &[u32], count: usize) -> Vec<usize> {
+        let calls = AtomicUsize::new(0);
+        (0..count).map(|_| policy.pick(&calls, weights)).collect()
+    }
+
+    #[test]
+    fn should_serve_once_each_then_last() {
+        assert_eq!(picks(SequencePolicy::OnceEachThenLast, &[1, 1, 1], 5), vec![0, 1, 2, 2, 2]);
+    }
+
+    #[test]
+    fn should_cycle() {
+        assert_eq!(picks(SequencePolicy::Cycle, &[1, 1], 5), vec![0, 1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn should_pick_randomly() {
+        let picks = picks(SequencePolicy::Random, &[1, 1, 1], 100);
+        assert!(picks.iter().all(|i| *i < 3));
+        assert!(picks.iter().any(|i| *i != picks[0]));
+    }
+
+    #[test]
+    fn should_pick_according_to_weights() {
+        assert!(picks(SequencePolicy::Weighted, &[0, 1, 0], 20).iter().all(|i| *i == 1));
+        let picks = picks(SequencePolicy::Weighted, &[1, 1], 100);
+        assert!(picks.contains(&0) && picks.contains(&1));
+    }
+
+    #[test]
+    fn should_reset_calls() {
+        let sequences = Sequences::default();
+        let (a, b) = (sequences.calls(), sequences.calls());
+        a.store(3, Ordering::SeqCst);
+        b.store(1, Ordering::SeqCst);
+        sequences.reset();
+        assert_eq!(a.load(Ordering::SeqCst), 0);
+        assert_eq!(b.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn should_deserialize_policies() {
+        let policy = |p: &str| serde_json::from_value::<SequencePolicy>(json!(p)).unwrap();
+        assert_eq!(policy("once-each-then-last"), SequencePolicy::OnceEachThenLast);
+        assert_eq!(policy("cycle"), SequencePolicy::Cycle);
+        assert_eq!(policy("random"), SequencePolicy::Random);
+        assert_eq!(policy("weighted"), SequencePolicy::Weighted);
+    }
+}
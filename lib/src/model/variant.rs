@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use wiremock::{Match, MockBuilder, Request};
+
+use super::{
+    request::RequestStub,
+    response::{ResponseStub, template::{data::{HandlebarsData, RequestData}, TemplateRenderer, Templates}},
+};
+
+/// One of the branches of a stub, listed in its `responses`. The first one whose request
+/// matchers and `when` expression both apply responds, otherwise the stub's `response` does.
+/// When the stub has a `sequence`, they are served in turn regardless of the request
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseVariantStub {
+    /// Matchers applied on top of the stub's own ones
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request: Option<RequestStub>,
+    /// Template rendered against the request e.g. `{{gt request.body.id 100}}`. Applies unless
+    /// it renders an empty string, `false`, `0` or `null`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+    /// Relative odds of being picked by a `weighted` sequence, defaults to 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u32>,
+    pub response: ResponseStub,
+}
+
+impl ResponseVariantStub {
+    /// Can be part of a sequence
+    pub fn is_unconditional(&self) -> bool {
+        self.request.is_none() && self.when.is_none()
+    }
+
+    /// Adds this variant's conditions to the matchers of its stub
+    pub(crate) fn register(&self, mut mock: MockBuilder, templates: &Templates) -> MockBuilder {
+        if let Some(request) = self.request.as_ref() {
+            mock = request.register(mock);
+        }
+        if let Some(when) = self.when.as_ref() {
+            let mut renderer = TemplateRenderer::new(templates.clone());
+            renderer.register(when);
+            mock = mock.and(WhenMatcher { when: when.to_string(), renderer });
+        }
+        mock
+    }
+}
+
+pub struct WhenMatcher {
+    when: String,
+    renderer: TemplateRenderer,
+}
+
+impl WhenMatcher {
+    const FALSY: [&'static str; 4] = ["", "false", "0", "null"];
+}
+
+impl Match for WhenMatcher {
+    fn matches(&self, req: &Request) -> bool {
+        let data = HandlebarsData {
+            request: &RequestData::from(req),
+            response: None,
+            stub_name: None,
+            is_verify: false,
+        };
+        self.renderer.try_render(&self.when, &data)
+            .map(|rendered| !Self::FALSY.contains(&rendered.trim()))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod variant_tests {
+    use std::str::FromStr;
+
+    use http_types::{Method, Url};
+    use serde_json::json;
+
+    use super::*;
+
+    fn matches(when: &str, url: &str, body: serde_json::Value) -> bool {
+        let mut renderer = TemplateRenderer::new(Templates::default());
+        renderer.register(when);
+        let matcher = WhenMatcher { when: when.to_string(), renderer };
+        let req = Request {
+            url: Url::from_str(url).unwrap(),
+            method: Method::Post,
+            headers: Default::default(),
+            body: serde_json::to_vec(&body).unwrap(),
+        };
+        matcher.matches(&req)
+    }
+
+    #[test]
+    fn should_match_when_truthy() {
+        assert!(matches("{{gt request.body.id 100}}", "http://localhost/", json!({"id": 101})));
+        assert!(matches("{{request.query.debug}}", "http://localhost/?debug=yes", json!({})));
+        assert!(matches("{{#if request.body.id}}yes{{/if}}", "http://localhost/", json!({"id": 1})));
+    }
+
+    #[test]
+    fn should_not_match_when_falsy() {
+        assert!(!matches("{{gt request.body.id 100}}", "http://localhost/", json!({"id": 99})));
+        assert!(!matches("{{request.query.debug}}", "http://localhost/", json!({})));
+        assert!(!matches("{{request.query.debug}}", "http://localhost/?debug=0", json!({})));
+        assert!(!matches("{{request.query.debug}}", "http://localhost/?debug=null", json!({})));
+    }
+
+    #[test]
+    fn should_not_match_when_invalid() {
+        assert!(!matches("{{#if}}", "http://localhost/", json!({})));
+    }
+
+    #[test]
+    fn should_deserialize() {
+        let variant: ResponseVariantStub = serde_json::from_value(json!({
+            "request": { "queryParameters": { "id": { "equalTo": "1" } } },
+            "when": "{{request.query.id}}",
+            "response": { "status": 404 }
+        })).unwrap();
+        assert!(variant.request.is_some());
+        assert_eq!(variant.when.as_deref(), Some("{{request.query.id}}"));
+        assert_eq!(variant.response.status(), 404);
+    }
+}
@@ -1,8 +1,11 @@
-use std::path::PathBuf;
+use std::{ops::RangeInclusive, path::PathBuf};
+
+use regex::Regex;
+#[cfg(feature = "record-standalone")]
 use reqwest::Client;
+use serde_json::Value;
 
-#[derive(Debug, Eq, PartialEq, Clone)]
-#[non_exhaustive]
+#[derive(Debug, Clone)]
 pub struct RecordConfig {
     /// Port number the recording proxy server is listening on.
     /// Defaults to a random one.
@@ -14,10 +17,57 @@ pub struct RecordConfig {
     pub except_request_headers: Option<Vec<&'static str>>,
     /// Do not record those response headers
     pub except_response_headers: Option<Vec<&'static str>>,
+    /// Json or text response bodies larger than this many bytes are written in a '__files'
+    /// directory next to the stub which references them with a 'bodyFileName' relative to it.
+    /// Replay them with that directory's parent as [crate::Config::root_dir].
+    /// Defaults to always inlining bodies.
+    pub body_file_threshold: Option<usize>,
+    /// Replaces uuids, numeric ids, timestamps and emails found in paths, queries and json bodies
+    /// by regex matchers in the request and by `any*` templates in the response so that the
+    /// recorded stub still matches once those values change.
+    /// Defaults to false
+    pub generalize: bool,
+    /// Only exchanges matching it get recorded.
+    /// Defaults to recording everything
+    pub include: Option<RecordFilter>,
+    /// Exchanges matching it are not recorded e.g. health checks.
+    /// Defaults to excluding nothing
+    pub exclude: Option<RecordFilter>,
+    /// Which matchers the recorded request gets for its url, headers and body.
+    /// Defaults to an exact path with a matcher per query parameter, every recorded header and
+    /// strict json equality
+    pub matchers: MatcherStrategy,
+    /// Masks secrets and personal data before the stub gets written
+    pub redact: Option<RedactConfig>,
+    /// How recorded stub files are named.
+    /// Defaults to [StubNaming::Hash]
+    pub naming: StubNaming,
+    /// What happens when the same request gets recorded again.
+    /// Defaults to [Dedupe::KeepLast]
+    pub dedupe: Dedupe,
+    /// When the same request gets a different response than the last time it was recorded, both
+    /// recordings are chained in a Wiremock scenario named after the stub so that replaying the
+    /// request returns the responses in the order they were recorded. Takes precedence over
+    /// [RecordConfig::dedupe].
+    /// Defaults to false
+    pub scenarios: bool,
+    /// Base url every request is forwarded to e.g. 'https://api.example.com', the recorder then
+    /// acting as a reverse proxy. Defaults to forwarding requests to the host they address.
+    #[cfg(feature = "record-standalone")]
+    pub target: Option<String>,
+    /// PEM encoded certificate authority issuing certificates for https hosts tunneled through
+    /// the recorder with 'CONNECT'. Requires [RecordConfig::ca_key].
+    /// Defaults to a new one generated when the recorder starts.
+    #[cfg(feature = "record-https")]
+    pub ca_cert: Option<PathBuf>,
+    /// PEM encoded private key of [RecordConfig::ca_cert]
+    #[cfg(feature = "record-https")]
+    pub ca_key: Option<PathBuf>,
     /// Custom client for proxy requests, this is useful in cases
     /// where you would want to disable or enable certain features in the deployed reverse
     /// proxy making the actual requests to the remote server.
-    pub client: Option<Client>
+    #[cfg(feature = "record-standalone")]
+    pub client: Option<Client>,
 }
 
 impl RecordConfig {
@@ -32,7 +82,171 @@ impl Default for RecordConfig {
             output: None,
             except_request_headers: Some(vec![Self::HOST_HEADER, Self::USER_AGENT_HEADER]),
             except_response_headers: None,
-            client: None
+            body_file_threshold: None,
+            generalize: false,
+            include: None,
+            exclude: None,
+            matchers: MatcherStrategy::default(),
+            redact: None,
+            naming: StubNaming::default(),
+            dedupe: Dedupe::default(),
+            scenarios: false,
+            #[cfg(feature = "record-standalone")]
+            target: None,
+            #[cfg(feature = "record-https")]
+            ca_cert: None,
+            #[cfg(feature = "record-https")]
+            ca_key: None,
+            #[cfg(feature = "record-standalone")]
+            client: None,
+        }
+    }
+}
+
+/// Recorded stub file names. Both are derived from the recorded request only, so recording the
+/// same request again always yields the same name
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum StubNaming {
+    /// '<path>-<hash>.json' with a hash of the request which does not change across Rust versions
+    #[default]
+    Hash,
+    /// '<method>-<path>-<n>.json' where 'n' numbers the distinct requests recorded on an endpoint
+    Sequence,
+}
+
+/// Policy for a request recorded more than once
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Dedupe {
+    /// The latest recording replaces the previous one
+    #[default]
+    KeepLast,
+    /// The first recording is kept, later ones are discarded
+    KeepFirst,
+    /// Every recording is kept, the ones after the first get suffixed with a sequence number.
+    /// They are chained in a scenario like with [RecordConfig::scenarios] so that each one gets
+    /// served in turn
+    KeepAll,
+}
+
+/// Criteria an exchange has to meet. It matches when it meets every criterion set, those left
+/// empty match any exchange
+#[derive(Debug, Clone, Default)]
+pub struct RecordFilter {
+    /// Request hosts, without port e.g. 'api.example.com'
+    pub hosts: Vec<String>,
+    /// Request methods, case insensitive e.g. 'GET'
+    pub methods: Vec<String>,
+    /// Regex the request path has to match e.g. `PathRegex::try_from("^/api/")?`
+    pub path: Option<PathRegex>,
+    /// Response status e.g. '200..=299'
+    pub status: Option<RangeInclusive<u16>>,
+    /// Response content types, parameters such as charset are ignored e.g. 'application/json'
+    pub content_types: Vec<String>,
+}
+
+/// Regex compiled once for all when the filter gets built
+#[derive(Debug, Clone)]
+pub struct PathRegex(pub(crate) Regex);
+
+impl TryFrom<&str> for PathRegex {
+    type Error = anyhow::Error;
+
+    fn try_from(regex: &str) -> anyhow::Result<Self> {
+        Regex::new(regex)
+            .map(Self)
+            .map_err(|e| anyhow::Error::msg(format!("Invalid recording filter path regex '{}': {}", regex, e)))
+    }
+}
+
+/// Matchers emitted for every recorded request
+#[derive(Debug, Clone, Default)]
+pub struct MatcherStrategy {
+    /// How the request url is matched.
+    /// Defaults to [UrlMatching::Path]
+    pub url: UrlMatching,
+    /// Only those request headers become 'equalTo' matchers e.g. 'content-type'. Headers in
+    /// [RecordConfig::except_request_headers] never do.
+    /// Defaults to every request header
+    pub headers: Option<Vec<String>>,
+    /// How a json request body is matched. Text and binary bodies are always matched by equality.
+    /// Defaults to strict [BodyMatching::EqualToJson]
+    pub body: BodyMatching,
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum UrlMatching {
+    /// 'urlPath' along with an 'equalTo' matcher per query parameter
+    #[default]
+    Path,
+    /// 'url' matching the path and the query, whatever the order of query parameters
+    Url,
+    /// 'urlPathPattern' with the path escaped, along with an 'equalTo' matcher per query parameter.
+    /// Meant to be hand edited afterwards
+    PathPattern,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BodyMatching {
+    /// 'equalToJson' on the whole body
+    EqualToJson {
+        ignore_extra_elements: bool,
+        ignore_array_order: bool,
+    },
+    /// A json path 'expression' with 'equalToJson' per leaf field of the body, so that extra
+    /// fields are ignored
+    JsonPath,
+}
+
+impl Default for BodyMatching {
+    fn default() -> Self {
+        Self::EqualToJson { ignore_extra_elements: false, ignore_array_order: false }
+    }
+}
+
+/// Redaction rules applied to recorded stubs.
+/// In the request, a masked header, query parameter or json field is only required to be present
+/// so that the stub still matches. In the response, its value is replaced by the mask.
+#[derive(Debug, Clone, Default)]
+pub struct RedactConfig {
+    /// Request and response headers whose value is masked e.g. 'authorization'
+    pub headers: Vec<&'static str>,
+    /// Query parameters whose value is masked e.g. 'api_key'
+    pub queries: Vec<&'static str>,
+    /// JsonPaths of request and response body fields to mask e.g. '$.password'
+    pub json_paths: JsonPaths,
+    /// Claims rewritten in every JWT found in headers or json bodies e.g. `("email", json!("john@doe.com"))`
+    pub jwt_claims: Vec<(&'static str, Value)>,
+    /// Replaces masked values in the response.
+    /// Defaults to '***'
+    pub mask: Option<String>,
+}
+
+impl RedactConfig {
+    const DEFAULT_MASK: &'static str = "***";
+
+    pub(crate) fn mask(&self) -> &str {
+        self.mask.as_deref().unwrap_or(Self::DEFAULT_MASK)
+    }
+}
+
+/// JsonPaths validated once for all e.g. `JsonPaths::try_from(vec!["$.password"])?`
+#[derive(Debug, Clone, Default)]
+pub struct JsonPaths(Vec<&'static str>);
+
+impl JsonPaths {
+    pub(crate) fn iter(&self) -> impl Iterator<Item=&'static str> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl TryFrom<Vec<&'static str>> for JsonPaths {
+    type Error = anyhow::Error;
+
+    fn try_from(paths: Vec<&'static str>) -> anyhow::Result<Self> {
+        for path in &paths {
+            jsonpath_lib::Compiled::compile(path)
+                .map_err(|e| anyhow::Error::msg(format!("Invalid json path '{}': {}", path, e)))?;
         }
+        Ok(Self(paths))
     }
 }
\ No newline at end of file
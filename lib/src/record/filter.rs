@@ -0,0 +1,138 @@
+use http_types::headers::CONTENT_TYPE;
+
+use super::{config::{RecordConfig, RecordFilter}, RecordedExchange};
+
+impl RecordConfig {
+    /// Whether an exchange passes both [RecordConfig::include] and [RecordConfig::exclude]
+    pub(crate) fn should_record(&self, ex: &RecordedExchange) -> bool {
+        let included = self.include.as_ref().map(|f| f.matches(ex)).unwrap_or(true);
+        let excluded = self.exclude.as_ref().map(|f| f.matches(ex)).unwrap_or_default();
+        included && !excluded
+    }
+}
+
+impl RecordFilter {
+    pub(crate) fn matches(&self, ex: &RecordedExchange) -> bool {
+        self.matches_host(ex) && self.matches_method(ex) && self.matches_path(ex)
+            && self.matches_status(ex) && self.matches_content_type(ex)
+    }
+
+    fn matches_host(&self, ex: &RecordedExchange) -> bool {
+        let host = ex.req().url().host_str().unwrap_or_default();
+        self.hosts.is_empty() || self.hosts.iter().any(|h| h.eq_ignore_ascii_case(host))
+    }
+
+    fn matches_method(&self, ex: &RecordedExchange) -> bool {
+        let method = ex.req().method().to_string();
+        self.methods.is_empty() || self.methods.iter().any(|m| m.eq_ignore_ascii_case(&method))
+    }
+
+    fn matches_path(&self, ex: &RecordedExchange) -> bool {
+        self.path.as_ref()
+            .map(|rgx| rgx.0.is_match(ex.req().url().path()))
+            .unwrap_or(true)
+    }
+
+    fn matches_status(&self, ex: &RecordedExchange) -> bool {
+        let status: u16 = ex.resp().status().into();
+        self.status.as_ref().map(|range| range.contains(&status)).unwrap_or(true)
+    }
+
+    fn matches_content_type(&self, ex: &RecordedExchange) -> bool {
+        let content_type = ex.resp().header(CONTENT_TYPE)
+            .and_then(|v| v.as_str().split(';').next().map(str::trim).map(str::to_string))
+            .unwrap_or_default();
+        self.content_types.is_empty() || self.content_types.iter().any(|c| c.eq_ignore_ascii_case(&content_type))
+    }
+}
+
+#[cfg(test)]
+mod filter_tests {
+    use http_types::{Method, Request, Response};
+
+    use super::*;
+    use super::super::{config::PathRegex, RecordedRequest, RecordedResponse};
+
+    fn path(regex: &str) -> Option<PathRegex> {
+        Some(PathRegex::try_from(regex).unwrap())
+    }
+
+    fn exchange(method: Method, url: &str, status: u16, content_type: Option<&str>) -> RecordedExchange {
+        let mut resp = Response::new(status);
+        if let Some(content_type) = content_type {
+            resp.insert_header(CONTENT_TYPE, content_type);
+        }
+        RecordedExchange(RecordedRequest(Request::new(method, url)), RecordedResponse(resp))
+    }
+
+    fn get(url: &str) -> RecordedExchange {
+        exchange(Method::Get, url, 200, None)
+    }
+
+    #[test]
+    fn empty_filter_should_match_anything() {
+        assert!(RecordFilter::default().matches(&get("http://localhost/a")));
+    }
+
+    #[test]
+    fn should_match_host_without_port() {
+        let filter = RecordFilter { hosts: vec![String::from("api.example.com")], ..Default::default() };
+        assert!(filter.matches(&get("http://api.example.com:8080/a")));
+        assert!(!filter.matches(&get("http://localhost/a")));
+    }
+
+    #[test]
+    fn should_match_method_case_insensitively() {
+        let filter = RecordFilter { methods: vec![String::from("post")], ..Default::default() };
+        assert!(filter.matches(&exchange(Method::Post, "http://localhost/a", 200, None)));
+        assert!(!filter.matches(&get("http://localhost/a")));
+    }
+
+    #[test]
+    fn should_match_path_regex() {
+        let filter = RecordFilter { path: path("^/api/"), ..Default::default() };
+        assert!(filter.matches(&get("http://localhost/api/users?page=1")));
+        assert!(!filter.matches(&get("http://localhost/health")));
+    }
+
+    #[test]
+    fn should_reject_invalid_path_regex() {
+        let err = PathRegex::try_from("^/api/(").unwrap_err();
+        assert!(err.to_string().starts_with("Invalid recording filter path regex '^/api/('"), "{}", err);
+    }
+
+    #[test]
+    fn should_match_status_range() {
+        let filter = RecordFilter { status: Some(200..=299), ..Default::default() };
+        assert!(filter.matches(&exchange(Method::Get, "http://localhost/a", 204, None)));
+        assert!(!filter.matches(&exchange(Method::Get, "http://localhost/a", 404, None)));
+    }
+
+    #[test]
+    fn should_match_content_type_without_parameters() {
+        let filter = RecordFilter { content_types: vec![String::from("application/json")], ..Default::default() };
+        assert!(filter.matches(&exchange(Method::Get, "http://localhost/a", 200, Some("application/json; charset=utf-8"))));
+        assert!(!filter.matches(&exchange(Method::Get, "http://localhost/a", 200, Some("text/plain"))));
+        assert!(!filter.matches(&get("http://localhost/a")));
+    }
+
+    #[test]
+    fn should_meet_every_criterion() {
+        let filter = RecordFilter { methods: vec![String::from("GET")], path: path("^/api/"), ..Default::default() };
+        assert!(filter.matches(&get("http://localhost/api/a")));
+        assert!(!filter.matches(&exchange(Method::Post, "http://localhost/api/a", 200, None)));
+    }
+
+    #[test]
+    fn should_record_included_but_not_excluded() {
+        let cfg = RecordConfig {
+            include: Some(RecordFilter { path: path("^/api/"), ..Default::default() }),
+            exclude: Some(RecordFilter { path: path("^/api/health"), ..Default::default() }),
+            ..Default::default()
+        };
+        assert!(cfg.should_record(&get("http://localhost/api/users")));
+        assert!(!cfg.should_record(&get("http://localhost/api/health")));
+        assert!(!cfg.should_record(&get("http://localhost/metrics")));
+        assert!(RecordConfig::default().should_record(&get("http://localhost/metrics")));
+    }
+}
@@ -0,0 +1,105 @@
+use std::{collections::BTreeMap, fs::{File, read_to_string}, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+/// Index of the stubs recorded in a directory, written alongside them.
+/// Being a dotfile, it is not mistaken for a stub when the directory gets served.
+#[derive(Serialize, Deserialize, Debug, Default, Eq, PartialEq)]
+pub struct Manifest {
+    /// Entries indexed by stub file name
+    pub stubs: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct ManifestEntry {
+    pub method: String,
+    pub url: String,
+    /// Stable hash of the recorded request
+    pub hash: String,
+    /// Stable hash of the recorded response
+    #[serde(default)]
+    pub response: String,
+    /// File name without occurrence suffix nor extension
+    pub name: String,
+    /// 1 for the first recording of this request then incremented when every recording is kept
+    pub occurrence: u32,
+}
+
+impl Manifest {
+    pub const FILE: &'static str = ".manifest.json";
+
+    /// An unreadable manifest is considered empty since it only serves naming purposes
+    pub fn read(dir: &Path) -> Self {
+        read_to_string(dir.join(Self::FILE)).ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn write(&self, dir: &Path) -> anyhow::Result<()> {
+        let file = File::create(dir.join(Self::FILE))?;
+        Ok(serde_json::to_writer_pretty(file, self)?)
+    }
+
+    /// Previous recordings of the request with this hash
+    pub fn recordings<'a>(&'a self, hash: &'a str) -> impl Iterator<Item=&'a ManifestEntry> + 'a {
+        self.stubs.values().filter(move |it| it.hash == hash)
+    }
+
+    /// Latest recording of the request with this hash
+    pub fn last_recording<'a>(&'a self, hash: &'a str) -> Option<&'a ManifestEntry> {
+        self.recordings(hash).max_by_key(|it| it.occurrence)
+    }
+
+    /// Number of distinct requests recorded on an endpoint
+    pub fn distinct_requests(&self, method: &str, url: &str) -> usize {
+        let mut hashes = self.stubs.values()
+            .filter(|it| it.method == method && it.url == url)
+            .map(|it| it.hash.as_str())
+            .collect::<Vec<_>>();
+        hashes.sort_unstable();
+        hashes.dedup();
+        hashes.len()
+    }
+}
+
+#[cfg(test)]
+mod manifest_tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn entry(url: &str, hash: &str, occurrence: u32) -> ManifestEntry {
+        let name = format!("get-{}", hash);
+        ManifestEntry { method: String::from("GET"), url: url.to_string(), hash: hash.to_string(), response: String::new(), name, occurrence }
+    }
+
+    #[test]
+    fn should_write_then_read() {
+        let dir = tempdir().unwrap();
+        let mut manifest = Manifest::default();
+        manifest.stubs.insert(String::from("get-a.json"), entry("/a", "a", 1));
+        manifest.write(dir.path()).unwrap();
+        assert_eq!(Manifest::read(dir.path()), manifest);
+    }
+
+    #[test]
+    fn should_be_empty_when_missing_or_invalid() {
+        let dir = tempdir().unwrap();
+        assert_eq!(Manifest::read(dir.path()), Manifest::default());
+        std::fs::write(dir.path().join(Manifest::FILE), "{not json").unwrap();
+        assert_eq!(Manifest::read(dir.path()), Manifest::default());
+    }
+
+    #[test]
+    fn should_count_distinct_requests_on_endpoint() {
+        let mut manifest = Manifest::default();
+        manifest.stubs.insert(String::from("1"), entry("/a", "x", 1));
+        manifest.stubs.insert(String::from("2"), entry("/a", "x", 2));
+        manifest.stubs.insert(String::from("3"), entry("/a", "y", 1));
+        manifest.stubs.insert(String::from("4"), entry("/b", "z", 1));
+        assert_eq!(manifest.distinct_requests("GET", "/a"), 2);
+        assert_eq!(manifest.distinct_requests("POST", "/a"), 0);
+        assert_eq!(manifest.recordings("x").count(), 2);
+        assert_eq!(manifest.last_recording("x").map(|it| it.occurrence), Some(2));
+    }
+}
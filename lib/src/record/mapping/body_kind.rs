@@ -0,0 +1,108 @@
+use std::str::from_utf8;
+
+use serde_json::Value;
+
+/// How a recorded body gets written in a stub, guessed from its `Content-Type`
+#[derive(Debug, PartialEq)]
+pub enum BodyKind {
+    Empty,
+    Json(Value),
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl BodyKind {
+    const TEXT_MIMES: [&'static str; 6] = ["xml", "x-www-form-urlencoded", "javascript", "yaml", "csv", "graphql"];
+    /// Also assigned to a recorded body sent without any content type
+    const OCTET_STREAM: &'static str = "application/octet-stream";
+
+    /// * `content_type` - mime essence e.g. `application/json`
+    pub fn guess(content_type: Option<&str>, body: Vec<u8>) -> Self {
+        if body.is_empty() {
+            return Self::Empty;
+        }
+        match content_type.map(str::to_lowercase) {
+            Some(ct) if ct.contains("json") => Self::json(body),
+            Some(ct) if ct.starts_with("text/") || Self::TEXT_MIMES.iter().any(|m| ct.contains(m)) => Self::structured_or_text(body),
+            Some(ct) if ct != Self::OCTET_STREAM => Self::Binary(body),
+            _ => Self::json(body),
+        }
+    }
+
+    fn json(body: Vec<u8>) -> Self {
+        serde_json::from_slice::<Value>(&body).map(Self::Json).unwrap_or_else(|_| Self::text(body))
+    }
+
+    /// Many clients (e.g. curl) default to a form or text content type even when posting json
+    fn structured_or_text(body: Vec<u8>) -> Self {
+        serde_json::from_slice::<Value>(&body).ok()
+            .filter(|it| it.is_object() || it.is_array())
+            .map(Self::Json)
+            .unwrap_or_else(|| Self::text(body))
+    }
+
+    fn text(body: Vec<u8>) -> Self {
+        from_utf8(&body).ok()
+            .filter(|t| !t.chars().any(|c| c.is_control() && !c.is_whitespace()))
+            .map(str::to_string)
+            .map(Self::Text)
+            .unwrap_or(Self::Binary(body))
+    }
+}
+
+#[cfg(test)]
+mod body_kind_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn should_be_empty() {
+        assert_eq!(BodyKind::guess(Some("application/json"), vec![]), BodyKind::Empty);
+        assert_eq!(BodyKind::guess(None, vec![]), BodyKind::Empty);
+    }
+
+    #[test]
+    fn should_be_json() {
+        let body = br#"{"a":1}"#.to_vec();
+        assert_eq!(BodyKind::guess(Some("application/json"), body.clone()), BodyKind::Json(json!({"a": 1})));
+        assert_eq!(BodyKind::guess(Some("application/problem+json"), body.clone()), BodyKind::Json(json!({"a": 1})));
+        assert_eq!(BodyKind::guess(Some("application/octet-stream"), body.clone()), BodyKind::Json(json!({"a": 1})));
+        assert_eq!(BodyKind::guess(None, body), BodyKind::Json(json!({"a": 1})));
+    }
+
+    #[test]
+    fn invalid_json_should_fallback_to_text() {
+        let body = b"{not json".to_vec();
+        assert_eq!(BodyKind::guess(Some("application/json"), body), BodyKind::Text(String::from("{not json")));
+    }
+
+    #[test]
+    fn should_be_text() {
+        let text = |ct: &str| BodyKind::guess(Some(ct), b"a=b".to_vec());
+        assert_eq!(text("text/plain"), BodyKind::Text(String::from("a=b")));
+        assert_eq!(text("TEXT/HTML"), BodyKind::Text(String::from("a=b")));
+        assert_eq!(text("application/xml"), BodyKind::Text(String::from("a=b")));
+        assert_eq!(text("application/soap+xml"), BodyKind::Text(String::from("a=b")));
+        assert_eq!(text("application/x-www-form-urlencoded"), BodyKind::Text(String::from("a=b")));
+        assert_eq!(BodyKind::guess(None, b"hello\nworld".to_vec()), BodyKind::Text(String::from("hello\nworld")));
+    }
+
+    #[test]
+    fn json_with_text_content_type_should_be_json() {
+        let body = br#"{"a":1}"#.to_vec();
+        assert_eq!(BodyKind::guess(Some("application/x-www-form-urlencoded"), body.clone()), BodyKind::Json(json!({"a": 1})));
+        assert_eq!(BodyKind::guess(Some("text/plain"), br#"[1]"#.to_vec()), BodyKind::Json(json!([1])));
+        assert_eq!(BodyKind::guess(Some("text/plain"), b"42".to_vec()), BodyKind::Text(String::from("42")));
+    }
+
+    #[test]
+    fn should_be_binary() {
+        assert_eq!(BodyKind::guess(Some("application/octet-stream"), vec![1, 2, 3]), BodyKind::Binary(vec![1, 2, 3]));
+        assert_eq!(BodyKind::guess(Some("application/pdf"), b"abc".to_vec()), BodyKind::Binary(b"abc".to_vec()));
+        assert_eq!(BodyKind::guess(Some("image/png"), vec![1, 2, 3]), BodyKind::Binary(vec![1, 2, 3]));
+        assert_eq!(BodyKind::guess(Some("text/plain"), vec![0xff, 0xfe]), BodyKind::Binary(vec![0xff, 0xfe]));
+        assert_eq!(BodyKind::guess(None, vec![0xff, 0xfe]), BodyKind::Binary(vec![0xff, 0xfe]));
+        assert_eq!(BodyKind::guess(None, vec![1, 2, 3]), BodyKind::Binary(vec![1, 2, 3]));
+    }
+}
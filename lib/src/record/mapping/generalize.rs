@@ -0,0 +1,329 @@
+use regex::Regex;
+use serde_json::{Map, Value};
+
+use crate::model::{
+    JsonStub,
+    request::{body::BodyPatternStub, matcher::MatcherValueStub, query::HttpQueryParamsStub, url::HttpUrlStub},
+    response::{
+        ResponseStub,
+        template::helpers::any::{
+            email::{AnyEmail, EMAIL_REGEX},
+            iso_8601_datetime::{AnyIso8601Datetime, ISO_8601_DATETIME_REGEX},
+            uuid::{AnyUuid, UUID_REGEX},
+        },
+    },
+};
+
+/// A recorded value which is likely to change from one exchange to another
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Volatile {
+    Uuid,
+    Datetime,
+    Email,
+    Id,
+}
+
+lazy_static! {
+    static ref ID_REGEX: Regex = Regex::new(&format!("^{}$", Volatile::ID_RGX)).unwrap();
+    static ref JSON_KEY_REGEX: Regex = Regex::new("^[A-Za-z_][A-Za-z0-9_]*$").unwrap();
+}
+
+impl Volatile {
+    const ID_RGX: &'static str = "[0-9]+";
+
+    fn detect(value: &str) -> Option<Self> {
+        if UUID_REGEX.is_match(value) {
+            Some(Self::Uuid)
+        } else if ISO_8601_DATETIME_REGEX.is_match(value) {
+            Some(Self::Datetime)
+        } else if EMAIL_REGEX.is_match(value) {
+            Some(Self::Email)
+        } else if ID_REGEX.is_match(value) {
+            Some(Self::Id)
+        } else {
+            None
+        }
+    }
+
+    /// Non-negative integers are numeric ids as well
+    fn detect_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::String(s) => Self::detect(s),
+            Value::Number(n) if n.is_u64() => Some(Self::Id),
+            _ => None,
+        }
+    }
+
+    /// Matches any value of this kind
+    fn regex(&self) -> &'static str {
+        match self {
+            Self::Uuid => AnyUuid::UUID_RGX,
+            Self::Datetime => AnyIso8601Datetime::ISO_8601_DATETIME_RGX,
+            Self::Email => AnyEmail::EMAIL_RGX_VERIFY,
+            Self::Id => Self::ID_RGX,
+        }
+    }
+
+    /// Generates a value of this kind in a response. Numeric ids are left untouched since a
+    /// template always renders a string
+    fn template(&self) -> Option<String> {
+        match self {
+            Self::Uuid => Some(AnyUuid::NAME),
+            Self::Datetime => Some(AnyIso8601Datetime::NAME),
+            Self::Email => Some(AnyEmail::NAME),
+            Self::Id => None,
+        }.map(|name| format!("{{{{{}}}}}", name))
+    }
+}
+
+/// Replaces recorded values likely to change (uuids, numeric ids, timestamps, emails) by matchers
+/// in the request and by templates in the response
+pub trait Generalize {
+    fn generalize(&mut self);
+}
+
+impl Generalize for JsonStub {
+    fn generalize(&mut self) {
+        self.request.url.generalize();
+        self.request.queries.generalize();
+        self.request.body_patterns.generalize();
+        if let Some(response) = self.response.as_mut() {
+            response.generalize();
+        }
+    }
+}
+
+impl Generalize for HttpUrlStub {
+    fn generalize(&mut self) {
+        if let Some(path) = self.url_path.as_deref() {
+            let segments = path.split('/').map(|s| (s, Volatile::detect(s))).collect::<Vec<_>>();
+            if segments.iter().any(|(_, volatile)| volatile.is_some()) {
+                let pattern = segments.into_iter()
+                    .map(|(s, volatile)| volatile.map(|v| v.regex().to_string()).unwrap_or_else(|| regex::escape(s)))
+                    .collect::<Vec<_>>()
+                    .join("/");
+                self.url_path = None;
+                self.url_path_pattern = Some(pattern);
+            }
+        }
+    }
+}
+
+impl Generalize for HttpQueryParamsStub {
+    fn generalize(&mut self) {
+        for matcher in self.query_parameters.iter_mut().flat_map(Map::values_mut) {
+            let volatile = matcher.get("equalTo").and_then(Value::as_str).and_then(Volatile::detect);
+            if let Some(volatile) = volatile {
+                let regex = MatcherValueStub { matches: Some(volatile.regex().into()), ..Default::default() };
+                *matcher = serde_json::to_value(regex).unwrap();
+            }
+        }
+    }
+}
+
+impl Generalize for Vec<BodyPatternStub> {
+    fn generalize(&mut self) {
+        let mut matchers = vec![];
+        for pattern in self.iter_mut().filter(|it| it.expression.is_none()) {
+            if let Some(json) = pattern.equal_to_json.as_mut() {
+                let before = matchers.len();
+                walk(json, "$", &mut |path, volatile, value| {
+                    let matcher = if value.is_number() {
+                        // a regex only matches strings, hence numeric ids are filtered on
+                        let (parent, key) = path.rsplit_once('.').unwrap_or(("$", &path));
+                        BodyPatternStub { matches_json_path: Some(format!("{}[?(@.{} >= 0)]", parent, key)), ..Default::default() }
+                    } else {
+                        BodyPatternStub { expression: Some(path), matches: Some(volatile.regex().to_string()), ..Default::default() }
+                    };
+                    matchers.push(matcher);
+                    false
+                });
+                if matchers.len() > before {
+                    pattern.ignore_extra_elements = Some(true);
+                }
+            }
+        }
+        self.extend(matchers);
+    }
+}
+
+impl Generalize for ResponseStub {
+    fn generalize(&mut self) {
+        let mut templated = false;
+        if let Some(json) = self.body.json_body.as_mut() {
+            walk(json, "$", &mut |_, volatile, value| {
+                if let Some(template) = volatile.template() {
+                    *value = Value::String(template);
+                    templated = true;
+                }
+                true
+            });
+        }
+        if templated && !self.requires_response_templating() {
+            self.transformers.push(ResponseStub::RESPONSE_TEMPLATE.to_string());
+        }
+    }
+}
+
+/// Visits every volatile string or numeric id of a json object (arrays excluded) along with its
+/// json path. The field is removed when `f` returns false
+fn walk(json: &mut Value, path: &str, f: &mut impl FnMut(String, Volatile, &mut Value) -> bool) {
+    if let Some(obj) = json.as_object_mut() {
+        let keys = obj.keys().filter(|k| JSON_KEY_REGEX.is_match(k)).cloned().collect::<Vec<_>>();
+        for key in keys {
+            let field_path = format!("{}.{}", path, key);
+            let value = obj.get_mut(&key).unwrap();
+            if value.is_object() {
+                walk(value, &field_path, f);
+            } else if let Some(volatile) = Volatile::detect_value(value) {
+                if !f(field_path, volatile, value) {
+                    obj.remove(&key);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod generalize_tests {
+    use serde_json::json;
+
+    use crate::model::response::body::BodyStub;
+
+    use super::*;
+
+    const UUID: &str = "8c1f1e4a-0c6f-4a8e-9a39-6d1b3c2f7d10";
+
+    mod detect {
+        use super::*;
+
+        #[test]
+        fn should_detect_uuid() {
+            assert_eq!(Volatile::detect(UUID), Some(Volatile::Uuid));
+        }
+
+        #[test]
+        fn should_detect_datetime() {
+            assert_eq!(Volatile::detect("2022-03-04T10:11:12Z"), Some(Volatile::Datetime));
+            assert_eq!(Volatile::detect("2022-03-04T10:11:12.123+02:00"), Some(Volatile::Datetime));
+        }
+
+        #[test]
+        fn should_detect_email() {
+            assert_eq!(Volatile::detect("john.doe@example.com"), Some(Volatile::Email));
+        }
+
+        #[test]
+        fn should_detect_id() {
+            assert_eq!(Volatile::detect("42"), Some(Volatile::Id));
+        }
+
+        #[test]
+        fn should_not_detect_anything_else() {
+            assert!(Volatile::detect("users").is_none());
+            assert!(Volatile::detect("v2").is_none());
+            assert!(Volatile::detect("").is_none());
+        }
+    }
+
+    mod url {
+        use super::*;
+
+        #[test]
+        fn should_generalize_path_segments() {
+            let mut url = HttpUrlStub { url_path: Some(format!("/users/42/orders/{}", UUID)), ..Default::default() };
+            url.generalize();
+            let expected = format!("/users/[0-9]+/orders/{}", AnyUuid::UUID_RGX);
+            assert_eq!(url, HttpUrlStub { url_path_pattern: Some(expected), ..Default::default() });
+        }
+
+        #[test]
+        fn should_escape_literal_segments() {
+            let mut url = HttpUrlStub { url_path: Some(String::from("/api.v1/users/42")), ..Default::default() };
+            url.generalize();
+            assert_eq!(url.url_path_pattern.as_deref(), Some(r"/api\.v1/users/[0-9]+"));
+        }
+
+        #[test]
+        fn should_keep_path_without_volatile_segment() {
+            let mut url = HttpUrlStub { url_path: Some(String::from("/api/users")), ..Default::default() };
+            url.generalize();
+            assert_eq!(url, HttpUrlStub { url_path: Some(String::from("/api/users")), ..Default::default() });
+        }
+    }
+
+    mod queries {
+        use super::*;
+
+        #[test]
+        fn should_generalize_volatile_query() {
+            let queries = json!({"id": {"equalTo": "42"}, "sort": {"equalTo": "asc"}});
+            let mut queries = HttpQueryParamsStub { query_parameters: queries.as_object().cloned() };
+            queries.generalize();
+            let expected = json!({"id": {"matches": "[0-9]+"}, "sort": {"equalTo": "asc"}});
+            assert_eq!(queries.query_parameters, expected.as_object().cloned());
+        }
+    }
+
+    mod req_body {
+        use super::*;
+
+        #[test]
+        fn should_replace_volatile_fields_by_json_path_matchers() {
+            let json = json!({"name": "john", "owner": {"id": UUID}});
+            let mut patterns = vec![BodyPatternStub { equal_to_json: Some(json), ..Default::default() }];
+            patterns.generalize();
+            assert_eq!(patterns, vec![
+                BodyPatternStub {
+                    equal_to_json: Some(json!({"name": "john", "owner": {}})),
+                    ignore_extra_elements: Some(true),
+                    ..Default::default()
+                },
+                BodyPatternStub {
+                    expression: Some(String::from("$.owner.id")),
+                    matches: Some(AnyUuid::UUID_RGX.to_string()),
+                    ..Default::default()
+                },
+            ]);
+        }
+
+        #[test]
+        fn should_generalize_numeric_ids() {
+            let json = json!({"id": 42, "owner": {"id": 7, "score": 1.5}, "ids": [UUID]});
+            let mut patterns = vec![BodyPatternStub { equal_to_json: Some(json), ..Default::default() }];
+            patterns.generalize();
+            assert_eq!(patterns, vec![
+                BodyPatternStub {
+                    equal_to_json: Some(json!({"owner": {"score": 1.5}, "ids": [UUID]})),
+                    ignore_extra_elements: Some(true),
+                    ..Default::default()
+                },
+                BodyPatternStub { matches_json_path: Some(String::from("$[?(@.id >= 0)]")), ..Default::default() },
+                BodyPatternStub { matches_json_path: Some(String::from("$.owner[?(@.id >= 0)]")), ..Default::default() },
+            ]);
+        }
+    }
+
+    mod resp_body {
+        use super::*;
+
+        #[test]
+        fn should_template_volatile_fields() {
+            let json = json!({"id": UUID, "at": "2022-03-04T10:11:12Z", "email": "john@doe.com", "count": "42"});
+            let mut resp = ResponseStub { body: BodyStub { json_body: Some(json), ..Default::default() }, ..Default::default() };
+            resp.generalize();
+            let expected = json!({"id": "{{anyUuid}}", "at": "{{anyIso8601}}", "email": "{{anyEmail}}", "count": "42"});
+            assert_eq!(resp.body.json_body, Some(expected));
+            assert_eq!(resp.transformers, vec![ResponseStub::RESPONSE_TEMPLATE.to_string()]);
+        }
+
+        #[test]
+        fn should_not_require_templating_without_volatile_field() {
+            let json = json!({"name": "john"});
+            let mut resp = ResponseStub { body: BodyStub { json_body: Some(json.clone()), ..Default::default() }, ..Default::default() };
+            resp.generalize();
+            assert_eq!(resp.body.json_body, Some(json));
+            assert!(resp.transformers.is_empty());
+        }
+    }
+}
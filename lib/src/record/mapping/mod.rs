@@ -1,18 +1,35 @@
 use crate::record::RecordInput;
 
-use super::super::model::{JsonStub, request::RequestStub, response::ResponseStub};
+use generalize::Generalize;
+use redact::Redact;
+
+use super::super::model::{JsonStub, request::RequestStub, response::ResponseStub, scenario::ScenarioStub};
 
 pub mod req;
 pub mod resp;
+mod body_kind;
+mod generalize;
+mod redact;
 
 impl From<RecordInput<'_>> for JsonStub {
     fn from((ex, cfg): RecordInput) -> Self {
-        Self {
+        let mut stub = Self {
             id: None,
             uuid: None,
             priority: None,
             request: RequestStub::from((&mut *ex, cfg)),
-            response: ResponseStub::from((&mut *ex, cfg)),
+            response: Some(ResponseStub::from((&mut *ex, cfg))),
+            responses: vec![],
+            sequence: None,
+            scenario: ScenarioStub::default(),
+            metadata: None,
+        };
+        if let Some(redact) = cfg.redact.as_ref() {
+            stub.redact(redact);
+        }
+        if cfg.generalize {
+            stub.generalize();
         }
+        stub
     }
 }
@@ -0,0 +1,373 @@
+use http_types::Url;
+use regex::{Captures, Regex};
+use serde_json::{Map, Value};
+
+use crate::{
+    model::{
+        JsonStub,
+        request::{body::BodyPatternStub, headers::HttpReqHeadersStub, matcher::MatcherValueStub, query::HttpQueryParamsStub, RequestStub},
+        response::{body::BodyStub, headers::HttpRespHeadersStub},
+    },
+    record::config::RedactConfig,
+};
+
+lazy_static! {
+    static ref JWT_REGEX: Regex = Regex::new(r"([A-Za-z0-9_-]+)\.([A-Za-z0-9_-]+)\.([A-Za-z0-9_-]*)").unwrap();
+}
+
+/// Masks secrets and personal data according to [RedactConfig]
+pub trait Redact {
+    fn redact(&mut self, cfg: &RedactConfig);
+}
+
+impl Redact for JsonStub {
+    fn redact(&mut self, cfg: &RedactConfig) {
+        self.request.headers.redact(cfg);
+        split_redacted_url(&mut self.request, cfg);
+        self.request.queries.redact(cfg);
+        self.request.body_patterns.redact(cfg);
+        if let Some(response) = self.response.as_mut() {
+            response.headers.redact(cfg);
+            response.body.redact(cfg);
+        }
+    }
+}
+
+impl Redact for HttpReqHeadersStub {
+    fn redact(&mut self, cfg: &RedactConfig) {
+        for (k, v) in self.headers.iter_mut().flat_map(Map::iter_mut) {
+            if cfg.headers.iter().any(|h| h.eq_ignore_ascii_case(k)) {
+                *v = present();
+            } else if let Some(Value::String(value)) = v.get_mut("equalTo") {
+                *value = rewrite_jwts(value, cfg);
+            }
+        }
+    }
+}
+
+impl Redact for HttpQueryParamsStub {
+    fn redact(&mut self, cfg: &RedactConfig) {
+        for (k, v) in self.query_parameters.iter_mut().flat_map(Map::iter_mut) {
+            if cfg.queries.contains(&k.as_str()) {
+                *v = present();
+            }
+        }
+    }
+}
+
+impl Redact for Vec<BodyPatternStub> {
+    fn redact(&mut self, cfg: &RedactConfig) {
+        let mut presences = vec![];
+        for pattern in self.iter_mut().filter(|it| it.expression.is_none()) {
+            if let Some(json) = pattern.equal_to_json.take() {
+                let mut json = rewrite_json_jwts(json, cfg);
+                for path in cfg.json_paths.iter() {
+                    if select_any(&json, path) {
+                        json = replace(json, path, &mut |_| None);
+                        pattern.ignore_extra_elements = Some(true);
+                        presences.push(BodyPatternStub { matches_json_path: Some(path.to_string()), ..Default::default() });
+                    }
+                }
+                pattern.equal_to_json = Some(json);
+            }
+            if let Some(text) = pattern.equal_to.as_mut() {
+                *text = rewrite_jwts(text, cfg);
+            }
+        }
+        // recorded with json path matchers, one per field
+        for pattern in self.iter_mut().filter(|it| it.expression.is_some() && it.equal_to_json.is_some()) {
+            let expression = pattern.expression.clone().unwrap_or_default();
+            if cfg.json_paths.iter().any(|path| is_within(&expression, path)) {
+                *pattern = BodyPatternStub { matches_json_path: Some(expression), ..Default::default() };
+            } else {
+                pattern.equal_to_json = pattern.equal_to_json.take().map(|json| rewrite_json_jwts(json, cfg));
+            }
+        }
+        self.extend(presences);
+    }
+}
+
+impl Redact for HttpRespHeadersStub {
+    fn redact(&mut self, cfg: &RedactConfig) {
+        for (k, v) in self.headers.iter_mut().flat_map(Map::iter_mut) {
+            if cfg.headers.iter().any(|h| h.eq_ignore_ascii_case(k)) {
+                *v = Value::String(cfg.mask().to_string());
+            } else if let Value::String(value) = v {
+                *value = rewrite_jwts(value, cfg);
+            }
+        }
+    }
+}
+
+impl Redact for BodyStub {
+    fn redact(&mut self, cfg: &RedactConfig) {
+        if let Some(json) = self.json_body.take() {
+            let mut json = rewrite_json_jwts(json, cfg);
+            for path in cfg.json_paths.iter() {
+                json = replace(json, path, &mut |_| Some(Value::String(cfg.mask().to_string())));
+            }
+            self.json_body = Some(json);
+        }
+        if let Some(body) = self.body.as_mut() {
+            *body = rewrite_jwts(body, cfg);
+        }
+    }
+}
+
+/// A query inlined in 'url' cannot be partially masked, so when it holds a masked parameter it
+/// falls back to 'urlPath' along with a matcher per query parameter
+fn split_redacted_url(req: &mut RequestStub, cfg: &RedactConfig) {
+    let split = req.url.url.as_deref()
+        .and_then(|url| Url::parse(&format!("http://localhost{}", url)).ok())
+        .map(|url| (url.path().to_string(), url.query_pairs().into_owned().collect::<Vec<_>>()))
+        .filter(|(_, queries)| queries.iter().any(|(k, _)| cfg.queries.contains(&k.as_str())));
+    if let Some((path, queries)) = split {
+        req.url.url = None;
+        req.url.url_path = Some(path).filter(|p| !p.is_empty() && p != "/");
+        let queries = queries.into_iter()
+            .map(|(k, v)| (k, serde_json::to_value(MatcherValueStub { equal_to: Some(v.into()), ..Default::default() }).unwrap()))
+            .collect::<Map<String, Value>>();
+        req.queries = HttpQueryParamsStub { query_parameters: Some(queries) };
+    }
+}
+
+/// Request matcher only requiring a header or query parameter to be present
+fn present() -> Value {
+    let any = MatcherValueStub { matches: Some(".*".into()), ..Default::default() };
+    serde_json::to_value(any).unwrap()
+}
+
+/// Whether a field's json path is the masked one or one of its children
+fn is_within(expression: &str, path: &str) -> bool {
+    expression.strip_prefix(path)
+        .map(|rest| rest.is_empty() || rest.starts_with('.') || rest.starts_with('['))
+        .unwrap_or_default()
+}
+
+fn select_any(json: &Value, path: &str) -> bool {
+    jsonpath_lib::select(json, path)
+        .map(|selected| selected.iter().any(|it| !it.is_null()))
+        .unwrap_or_default()
+}
+
+/// Paths are validated in [crate::JsonPaths] hence the json is only kept as is as a precaution
+fn replace(json: Value, path: &str, f: &mut impl FnMut(Value) -> Option<Value>) -> Value {
+    jsonpath_lib::replace_with(json.clone(), path, f).unwrap_or(json)
+}
+
+fn rewrite_json_jwts(json: Value, cfg: &RedactConfig) -> Value {
+    if cfg.jwt_claims.is_empty() {
+        return json;
+    }
+    match json {
+        Value::String(s) => Value::String(rewrite_jwts(&s, cfg)),
+        Value::Array(a) => Value::Array(a.into_iter().map(|it| rewrite_json_jwts(it, cfg)).collect()),
+        Value::Object(o) => Value::Object(o.into_iter().map(|(k, v)| (k, rewrite_json_jwts(v, cfg))).collect()),
+        other => other,
+    }
+}
+
+/// Rewrites the claims of every JWT found in a value. Only the payload gets replaced so the
+/// signature is no longer valid
+fn rewrite_jwts(value: &str, cfg: &RedactConfig) -> String {
+    if cfg.jwt_claims.is_empty() {
+        return value.to_string();
+    }
+    JWT_REGEX.replace_all(value, |c: &Captures| {
+        rewrite_payload(&c[2], cfg)
+            .map(|payload| format!("{}.{}.{}", &c[1], payload, &c[3]))
+            .unwrap_or_else(|| c[0].to_string())
+    }).to_string()
+}
+
+fn rewrite_payload(payload: &str, cfg: &RedactConfig) -> Option<String> {
+    let mut claims = base64::decode_config(payload, base64::URL_SAFE_NO_PAD).ok()
+        .and_then(|it| serde_json::from_slice::<Map<String, Value>>(&it).ok())?;
+    let mut rewritten = false;
+    for (claim, value) in &cfg.jwt_claims {
+        if let Some(current) = claims.get_mut(*claim) {
+            *current = value.clone();
+            rewritten = true;
+        }
+    }
+    rewritten
+        .then(|| serde_json::to_vec(&claims).ok())
+        .flatten()
+        .map(|it| base64::encode_config(it, base64::URL_SAFE_NO_PAD))
+}
+
+#[cfg(test)]
+mod redact_tests {
+    use serde_json::json;
+
+    use crate::record::config::JsonPaths;
+
+    use super::*;
+
+    fn jwt(claims: Value) -> String {
+        let header = base64::encode_config(json!({"alg": "HS256", "typ": "JWT"}).to_string(), base64::URL_SAFE_NO_PAD);
+        let payload = base64::encode_config(claims.to_string(), base64::URL_SAFE_NO_PAD);
+        format!("{}.{}.c2lnbmF0dXJl", header, payload)
+    }
+
+    fn claims(token: &str) -> Value {
+        let payload = token.split('.').nth(1).unwrap();
+        serde_json::from_slice(&base64::decode_config(payload, base64::URL_SAFE_NO_PAD).unwrap()).unwrap()
+    }
+
+    mod headers {
+        use super::*;
+
+        #[test]
+        fn should_only_require_masked_request_header() {
+            let cfg = RedactConfig { headers: vec!["Authorization"], ..Default::default() };
+            let headers = json!({"authorization": {"equalTo": "Bearer secret"}, "x-a": {"equalTo": "a"}});
+            let mut headers = HttpReqHeadersStub { headers: headers.as_object().cloned() };
+            headers.redact(&cfg);
+            let expected = json!({"authorization": {"matches": ".*"}, "x-a": {"equalTo": "a"}});
+            assert_eq!(headers.headers, expected.as_object().cloned());
+        }
+
+        #[test]
+        fn should_mask_response_header() {
+            let cfg = RedactConfig { headers: vec!["set-cookie"], mask: Some(String::from("<redacted>")), ..Default::default() };
+            let headers = json!({"set-cookie": "session=abc", "x-a": "a"});
+            let mut headers = HttpRespHeadersStub { headers: headers.as_object().cloned() };
+            headers.redact(&cfg);
+            let expected = json!({"set-cookie": "<redacted>", "x-a": "a"});
+            assert_eq!(headers.headers, expected.as_object().cloned());
+        }
+    }
+
+    mod queries {
+        use super::*;
+
+        #[test]
+        fn should_only_require_masked_query() {
+            let cfg = RedactConfig { queries: vec!["api_key"], ..Default::default() };
+            let queries = json!({"api_key": {"equalTo": "secret"}, "page": {"equalTo": "1"}});
+            let mut queries = HttpQueryParamsStub { query_parameters: queries.as_object().cloned() };
+            queries.redact(&cfg);
+            let expected = json!({"api_key": {"matches": ".*"}, "page": {"equalTo": "1"}});
+            assert_eq!(queries.query_parameters, expected.as_object().cloned());
+        }
+    }
+
+    mod url {
+        use crate::model::request::url::HttpUrlStub;
+
+        use super::*;
+
+        fn request(url: &str) -> RequestStub {
+            RequestStub { url: HttpUrlStub { url: Some(url.to_string()), ..Default::default() }, ..Default::default() }
+        }
+
+        #[test]
+        fn should_split_url_with_masked_query() {
+            let cfg = RedactConfig { queries: vec!["api_key"], ..Default::default() };
+            let mut stub = JsonStub { request: request("/users?api_key=secret&page=1"), ..Default::default() };
+            stub.redact(&cfg);
+            assert_eq!(stub.request.url, HttpUrlStub { url_path: Some(String::from("/users")), ..Default::default() });
+            let expected = json!({"api_key": {"matches": ".*"}, "page": {"equalTo": "1"}});
+            assert_eq!(stub.request.queries.query_parameters, expected.as_object().cloned());
+            assert!(!serde_json::to_string(&stub).unwrap().contains("secret"));
+        }
+
+        #[test]
+        fn should_keep_url_without_masked_query() {
+            let cfg = RedactConfig { queries: vec!["api_key"], ..Default::default() };
+            let mut stub = JsonStub { request: request("/users?page=1"), ..Default::default() };
+            stub.redact(&cfg);
+            assert_eq!(stub.request.url.url.as_deref(), Some("/users?page=1"));
+            assert!(stub.request.queries.query_parameters.is_none());
+        }
+    }
+
+    mod json_paths {
+        use super::*;
+
+        #[test]
+        fn should_only_require_masked_request_field() {
+            let cfg = RedactConfig { json_paths: JsonPaths::try_from(vec!["$.password", "$.missing"]).unwrap(), ..Default::default() };
+            let json = json!({"user": "john", "password": "secret"});
+            let mut patterns = vec![BodyPatternStub { equal_to_json: Some(json), ..Default::default() }];
+            patterns.redact(&cfg);
+            assert_eq!(patterns, vec![
+                BodyPatternStub { equal_to_json: Some(json!({"user": "john"})), ignore_extra_elements: Some(true), ..Default::default() },
+                BodyPatternStub { matches_json_path: Some(String::from("$.password")), ..Default::default() },
+            ]);
+        }
+
+        #[test]
+        fn should_only_require_masked_json_path_field() {
+            let cfg = RedactConfig { json_paths: JsonPaths::try_from(vec!["$.credentials"]).unwrap(), ..Default::default() };
+            let path_eq = |path: &str, value: Value| BodyPatternStub { expression: Some(path.to_string()), equal_to_json: Some(value), ..Default::default() };
+            let mut patterns = vec![path_eq("$.user", json!("john")), path_eq("$.credentials.password", json!("secret"))];
+            patterns.redact(&cfg);
+            assert_eq!(patterns, vec![
+                path_eq("$.user", json!("john")),
+                BodyPatternStub { matches_json_path: Some(String::from("$.credentials.password")), ..Default::default() },
+            ]);
+        }
+
+        #[test]
+        fn should_reject_invalid_json_path() {
+            let err = JsonPaths::try_from(vec!["$.password", "$[?("]).unwrap_err();
+            assert!(err.to_string().starts_with("Invalid json path '$[?('"), "{}", err);
+        }
+
+        #[test]
+        fn should_mask_response_field() {
+            let cfg = RedactConfig { json_paths: JsonPaths::try_from(vec!["$.user.password", "$.missing"]).unwrap(), ..Default::default() };
+            let mut body = BodyStub { json_body: Some(json!({"user": {"name": "john", "password": "secret"}})), ..Default::default() };
+            body.redact(&cfg);
+            assert_eq!(body.json_body, Some(json!({"user": {"name": "john", "password": "***"}})));
+        }
+    }
+
+    mod jwt_claims {
+        use super::*;
+
+        #[test]
+        fn should_rewrite_claims_in_request_header() {
+            let cfg = RedactConfig { jwt_claims: vec![("email", json!("jdoe@example.com"))], ..Default::default() };
+            let token = jwt(json!({"sub": "1", "email": "john@doe.com"}));
+            let headers = json!({"authorization": {"equalTo": format!("Bearer {}", token)}});
+            let mut headers = HttpReqHeadersStub { headers: headers.as_object().cloned() };
+            headers.redact(&cfg);
+            let value = headers.headers.unwrap()["authorization"]["equalTo"].as_str().unwrap().to_string();
+            let rewritten = value.strip_prefix("Bearer ").unwrap();
+            assert_eq!(claims(rewritten), json!({"sub": "1", "email": "jdoe@example.com"}));
+            assert_eq!(rewritten.split('.').next(), token.split('.').next());
+        }
+
+        #[test]
+        fn should_rewrite_claims_in_text_request_body() {
+            let cfg = RedactConfig { jwt_claims: vec![("email", json!("jdoe@example.com"))], ..Default::default() };
+            let token = jwt(json!({"email": "john@doe.com"}));
+            let mut patterns = vec![BodyPatternStub { equal_to: Some(format!("token={}", token)), ..Default::default() }];
+            patterns.redact(&cfg);
+            let rewritten = patterns[0].equal_to.as_deref().unwrap().strip_prefix("token=").unwrap().to_string();
+            assert_eq!(claims(&rewritten), json!({"email": "jdoe@example.com"}));
+        }
+
+        #[test]
+        fn should_rewrite_claims_in_response_body() {
+            let cfg = RedactConfig { jwt_claims: vec![("name", json!("anonymous"))], ..Default::default() };
+            let token = jwt(json!({"name": "john"}));
+            let mut body = BodyStub { json_body: Some(json!({"tokens": [{"access_token": token}]})), ..Default::default() };
+            body.redact(&cfg);
+            let rewritten = body.json_body.unwrap()["tokens"][0]["access_token"].as_str().unwrap().to_string();
+            assert_eq!(claims(&rewritten), json!({"name": "anonymous"}));
+        }
+
+        #[test]
+        fn should_not_touch_token_without_claim() {
+            let cfg = RedactConfig { jwt_claims: vec![("email", json!("jdoe@example.com"))], ..Default::default() };
+            let token = jwt(json!({"sub": "1"}));
+            assert_eq!(rewrite_jwts(&token, &cfg), token);
+            assert_eq!(rewrite_jwts("not.a-jwt", &cfg), "not.a-jwt");
+            assert_eq!(rewrite_jwts("plain text", &cfg), "plain text");
+        }
+    }
+}
@@ -1,25 +1,87 @@
 use async_std::task::block_on;
+use regex::Regex;
 use serde_json::Value;
 
-use crate::model::request::body::BodyPatternStub;
+use crate::{model::request::body::BodyPatternStub, record::{config::BodyMatching, RecordInput}};
 
-use super::super::super::RecordedExchange;
+use super::super::{body_kind::BodyKind, super::RecordedExchange};
+
+lazy_static! {
+    static ref JSON_PATH_KEY_REGEX: Regex = Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$").unwrap();
+}
+
+/// Body patterns following [crate::MatcherStrategy::body]
+pub fn body_patterns((ex, cfg): RecordInput) -> Vec<BodyPatternStub> {
+    Vec::<BodyPatternStub>::from(ex).into_iter()
+        .flat_map(|it| match it.equal_to_json {
+            Some(json) => json_patterns(json, cfg.matchers.body),
+            None => vec![it],
+        })
+        .collect()
+}
 
 impl From<&mut RecordedExchange> for Vec<BodyPatternStub> {
     fn from(ex: &mut RecordedExchange) -> Self {
-        block_on(async move { ex.0.0.body_json::<Value>().await }).ok()
-            .map(|json_body| BodyPatternStub { equal_to_json: Some(json_body), ..Default::default() })
+        let content_type = ex.req().content_type().map(|m| m.essence().to_string());
+        let body = block_on(async move {
+            let body = ex.0.0.take_body().into_bytes().await.unwrap_or_default();
+            ex.0.0.set_body(body.clone());
+            body
+        });
+        match BodyKind::guess(content_type.as_deref(), body) {
+            BodyKind::Empty => None,
+            BodyKind::Json(json) => Some(BodyPatternStub { equal_to_json: Some(json), ..Default::default() }),
+            BodyKind::Text(text) => Some(BodyPatternStub { equal_to: Some(text), ..Default::default() }),
+            BodyKind::Binary(bytes) => Some(BodyPatternStub { binary_equal_to: Some(base64::encode(bytes)), ..Default::default() }),
+        }
             .map(|it| vec![it])
             .unwrap_or_default()
     }
 }
 
+fn json_patterns(json: Value, matching: BodyMatching) -> Vec<BodyPatternStub> {
+    match matching {
+        BodyMatching::JsonPath if json.as_object().map(|o| !o.is_empty()).unwrap_or_default() => {
+            let mut leaves = vec![];
+            json_path_leaves(String::from("$"), json, &mut leaves);
+            leaves.into_iter()
+                .map(|(path, value)| BodyPatternStub { expression: Some(path), equal_to_json: Some(value), ..Default::default() })
+                .collect()
+        }
+        BodyMatching::EqualToJson { ignore_extra_elements, ignore_array_order } => vec![BodyPatternStub {
+            equal_to_json: Some(json),
+            ignore_extra_elements: Some(true).filter(|_| ignore_extra_elements),
+            ignore_array_order: Some(true).filter(|_| ignore_array_order),
+            ..Default::default()
+        }],
+        // nothing to select in an empty object or a scalar/array body
+        BodyMatching::JsonPath => vec![BodyPatternStub { equal_to_json: Some(json), ..Default::default() }],
+    }
+}
+
+/// Json path of every field which is not a non-empty object along with its value
+fn json_path_leaves(path: String, json: Value, leaves: &mut Vec<(String, Value)>) {
+    match json {
+        Value::Object(fields) if !fields.is_empty() => {
+            for (key, value) in fields {
+                let path = if JSON_PATH_KEY_REGEX.is_match(&key) {
+                    format!("{}.{}", path, key)
+                } else {
+                    format!("{}['{}']", path, key)
+                };
+                json_path_leaves(path, value, leaves);
+            }
+        }
+        leaf => leaves.push((path, leaf)),
+    }
+}
+
 #[cfg(test)]
 mod req_body_mapping_tests {
     use http_types::Request;
     use serde_json::json;
 
-    use crate::record::RecordedRequest;
+    use crate::{MatcherStrategy, record::{RecordConfig, RecordedRequest}};
 
     use super::*;
 
@@ -49,4 +111,76 @@ mod req_body_mapping_tests {
         let mut exchange = RecordedExchange { 0: RecordedRequest(req), ..Default::default() };
         assert!(Vec::<BodyPatternStub>::from(&mut exchange).is_empty())
     }
+
+    #[test]
+    fn should_map_text_body() {
+        let mut req = Request::post("http://localhost");
+        req.set_body("<name>beltram</name>");
+        req.set_content_type(http_types::Mime::from("application/xml"));
+        let mut exchange = RecordedExchange { 0: RecordedRequest(req), ..Default::default() };
+        let expected = BodyPatternStub { equal_to: Some(String::from("<name>beltram</name>")), ..Default::default() };
+        assert_eq!(Vec::<BodyPatternStub>::from(&mut exchange), vec![expected])
+    }
+
+    #[test]
+    fn should_map_binary_body() {
+        let mut req = Request::post("http://localhost");
+        req.set_body(vec![1, 2, 3]);
+        let mut exchange = RecordedExchange { 0: RecordedRequest(req), ..Default::default() };
+        let expected = BodyPatternStub { binary_equal_to: Some(String::from("AQID")), ..Default::default() };
+        assert_eq!(Vec::<BodyPatternStub>::from(&mut exchange), vec![expected])
+    }
+
+    #[test]
+    fn should_keep_body_in_exchange() {
+        let mut req = Request::post("http://localhost");
+        req.set_body("hello");
+        let mut exchange = RecordedExchange { 0: RecordedRequest(req), ..Default::default() };
+        Vec::<BodyPatternStub>::from(&mut exchange);
+        assert_eq!(block_on(exchange.0.0.body_string()).unwrap(), "hello")
+    }
+
+    fn with_body(body: BodyMatching) -> RecordConfig {
+        RecordConfig { matchers: MatcherStrategy { body, ..Default::default() }, ..Default::default() }
+    }
+
+    #[test]
+    fn should_map_relaxed_json_body() {
+        let body = json!({"names": ["a", "b"]});
+        let mut req = Request::post("http://localhost");
+        req.set_body(body.clone());
+        let mut exchange = RecordedExchange { 0: RecordedRequest(req), ..Default::default() };
+        let cfg = with_body(BodyMatching::EqualToJson { ignore_extra_elements: true, ignore_array_order: true });
+        let expected = BodyPatternStub {
+            equal_to_json: Some(body),
+            ignore_extra_elements: Some(true),
+            ignore_array_order: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(body_patterns((&mut exchange, &cfg)), vec![expected])
+    }
+
+    #[test]
+    fn should_map_json_body_leaves_to_json_paths() {
+        let mut req = Request::post("http://localhost");
+        req.set_body(json!({"name": "beltram", "address": {"city": "Paris"}, "tags": ["a"], "x-id": 1}));
+        let mut exchange = RecordedExchange { 0: RecordedRequest(req), ..Default::default() };
+        let patterns = body_patterns((&mut exchange, &with_body(BodyMatching::JsonPath)));
+        let path_eq = |path: &str, value: Value| BodyPatternStub { expression: Some(path.to_string()), equal_to_json: Some(value), ..Default::default() };
+        assert_eq!(patterns.len(), 4);
+        assert!(patterns.contains(&path_eq("$.name", json!("beltram"))));
+        assert!(patterns.contains(&path_eq("$.address.city", json!("Paris"))));
+        assert!(patterns.contains(&path_eq("$.tags", json!(["a"]))));
+        assert!(patterns.contains(&path_eq("$['x-id']", json!(1))));
+    }
+
+    #[test]
+    fn should_map_json_array_body_by_equality_when_json_paths() {
+        let body = json!([1, 2]);
+        let mut req = Request::post("http://localhost");
+        req.set_body(body.clone());
+        let mut exchange = RecordedExchange { 0: RecordedRequest(req), ..Default::default() };
+        let expected = BodyPatternStub { equal_to_json: Some(body), ..Default::default() };
+        assert_eq!(body_patterns((&mut exchange, &with_body(BodyMatching::JsonPath))), vec![expected])
+    }
 }
@@ -16,6 +16,11 @@ impl From<RecordInput<'_>> for HttpReqHeadersStub {
                     .map(|it| it.contains(&k.as_str()))
                     .unwrap_or_default()
             })
+            .filter(|k| {
+                cfg.matchers.headers.as_ref()
+                    .map(|it| it.iter().any(|h| h.eq_ignore_ascii_case(k.as_str())))
+                    .unwrap_or(true)
+            })
             .filter_map(|k| req.header(k).map(|v| (k, v)))
             .map(|(k, v)| {
                 let v: Value = v.iter().map(|it| it.as_str()).join(", ").into();
@@ -37,7 +42,7 @@ mod req_url_mapping_tests {
     use http_types::Request;
     use serde_json::{json, Map};
 
-    use crate::{record::{RecordedExchange, RecordedRequest}, RecordConfig};
+    use crate::{MatcherStrategy, record::{RecordedExchange, RecordedRequest}, RecordConfig};
 
     use super::*;
 
@@ -69,6 +74,20 @@ mod req_url_mapping_tests {
         )
     }
 
+    #[test]
+    fn should_only_map_chosen_headers() {
+        let mut req = Request::get("http://localhost");
+        req.append_header("x-a", "value-a");
+        req.append_header("x-b", "value-b");
+        let mut exchange = RecordedExchange { 0: RecordedRequest(req), ..Default::default() };
+        let cfg = RecordConfig { matchers: MatcherStrategy { headers: Some(vec![String::from("X-B")]), ..Default::default() }, ..Default::default() };
+        let expected = Map::from_iter(vec![("x-b".to_string(), json!({"equalTo": "value-b"}))]);
+        assert_eq!(
+            HttpReqHeadersStub::from((&mut exchange, &cfg)),
+            HttpReqHeadersStub { headers: Some(expected) }
+        )
+    }
+
     #[test]
     fn should_not_fail_when_no_header() {
         let req = Request::get("http://localhost");
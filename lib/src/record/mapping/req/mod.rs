@@ -1,5 +1,4 @@
 use crate::model::request::{
-    body::BodyPatternStub,
     headers::HttpReqHeadersStub,
     method::HttpMethodStub,
     query::HttpQueryParamsStub,
@@ -18,10 +17,10 @@ impl From<RecordInput<'_>> for RequestStub {
     fn from((ex, cfg): RecordInput) -> Self {
         Self {
             method: HttpMethodStub::from(&mut *ex),
-            url: HttpUrlStub::from(&mut *ex),
+            url: HttpUrlStub::from((&mut *ex, cfg)),
             headers: HttpReqHeadersStub::from((&mut *ex, cfg)),
-            queries: HttpQueryParamsStub::from(&mut *ex),
-            body_patterns: Vec::<BodyPatternStub>::from(&mut *ex),
+            queries: HttpQueryParamsStub::from((&mut *ex, cfg)),
+            body_patterns: body::body_patterns((&mut *ex, cfg)),
             ..Default::default()
         }
     }
@@ -1,11 +1,16 @@
 use serde_json::{Map, Value};
 
-use crate::model::request::{matcher::MatcherValueStub, query::HttpQueryParamsStub};
+use crate::{
+    model::request::{matcher::MatcherValueStub, query::HttpQueryParamsStub},
+    record::{config::UrlMatching, RecordInput},
+};
 
-use super::super::super::RecordedExchange;
-
-impl From<&mut RecordedExchange> for HttpQueryParamsStub {
-    fn from(ex: &mut RecordedExchange) -> Self {
+impl From<RecordInput<'_>> for HttpQueryParamsStub {
+    fn from((ex, cfg): RecordInput) -> Self {
+        // already matched by 'url'
+        if cfg.matchers.url == UrlMatching::Url {
+            return Self { query_parameters: None };
+        }
         let req = ex.req();
         let queries = req.url().query_pairs().into_iter()
             .map(|(k, v)| {
@@ -27,7 +32,7 @@ mod req_queries_mapping_tests {
     use http_types::Request;
     use serde_json::json;
 
-    use crate::record::RecordedRequest;
+    use crate::{MatcherStrategy, record::{RecordConfig, RecordedExchange, RecordedRequest}};
 
     use super::*;
 
@@ -37,7 +42,7 @@ mod req_queries_mapping_tests {
         let mut exchange = RecordedExchange { 0: RecordedRequest(req), ..Default::default() };
         let expected = Map::from_iter(vec![("a".to_string(), json!({"equalTo": "1"}))]);
         assert_eq!(
-            HttpQueryParamsStub::from(&mut exchange),
+            HttpQueryParamsStub::from((&mut exchange, &RecordConfig::default())),
             HttpQueryParamsStub { query_parameters: Some(expected) }
         )
     }
@@ -51,7 +56,7 @@ mod req_queries_mapping_tests {
             ("b".to_string(), json!({"equalTo": "2"})),
         ]);
         assert_eq!(
-            HttpQueryParamsStub::from(&mut exchange),
+            HttpQueryParamsStub::from((&mut exchange, &RecordConfig::default())),
             HttpQueryParamsStub { query_parameters: Some(expected) }
         )
     }
@@ -61,7 +66,7 @@ mod req_queries_mapping_tests {
         let req = Request::get("http://localhost");
         let mut exchange = RecordedExchange { 0: RecordedRequest(req), ..Default::default() };
         assert_eq!(
-            HttpQueryParamsStub::from(&mut exchange),
+            HttpQueryParamsStub::from((&mut exchange, &RecordConfig::default())),
             HttpQueryParamsStub { query_parameters: None }
         )
     }
@@ -75,8 +80,19 @@ mod req_queries_mapping_tests {
             ("a".to_string(), json!({"equalTo": "2"})),
         ]);
         assert_eq!(
-            HttpQueryParamsStub::from(&mut exchange),
+            HttpQueryParamsStub::from((&mut exchange, &RecordConfig::default())),
             HttpQueryParamsStub { query_parameters: Some(expected) }
         )
     }
+
+    #[test]
+    fn should_not_map_queries_already_in_url() {
+        let req = Request::get("http://localhost?a=1");
+        let mut exchange = RecordedExchange { 0: RecordedRequest(req), ..Default::default() };
+        let cfg = RecordConfig { matchers: MatcherStrategy { url: UrlMatching::Url, ..Default::default() }, ..Default::default() };
+        assert_eq!(
+            HttpQueryParamsStub::from((&mut exchange, &cfg)),
+            HttpQueryParamsStub { query_parameters: None }
+        )
+    }
 }
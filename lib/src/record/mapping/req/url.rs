@@ -1,12 +1,21 @@
-use crate::model::request::url::HttpUrlStub;
+use crate::{model::request::url::HttpUrlStub, record::{config::UrlMatching, RecordInput}};
 
-use super::super::super::RecordedExchange;
-
-impl From<&mut RecordedExchange> for HttpUrlStub {
-    fn from(ex: &mut RecordedExchange) -> Self {
-        let path = ex.req().url().path().trim().to_string();
-        let url = if !path.is_empty() && path != "/" { Some(path) } else { None };
-        Self { url_path: url, ..Default::default() }
+impl From<RecordInput<'_>> for HttpUrlStub {
+    fn from((ex, cfg): RecordInput) -> Self {
+        let url = ex.req().url();
+        let path = url.path().trim().to_string();
+        let path = if !path.is_empty() && path != "/" { Some(path) } else { None };
+        match cfg.matchers.url {
+            UrlMatching::Path => Self { url_path: path, ..Default::default() },
+            UrlMatching::PathPattern => Self { url_path_pattern: path.as_deref().map(regex::escape), ..Default::default() },
+            UrlMatching::Url => {
+                let url = match url.query().filter(|q| !q.is_empty()) {
+                    Some(query) => Some(format!("{}?{}", url.path(), query)),
+                    None => path,
+                };
+                Self { url, ..Default::default() }
+            }
+        }
     }
 }
 
@@ -14,7 +23,7 @@ impl From<&mut RecordedExchange> for HttpUrlStub {
 mod req_url_mapping_tests {
     use http_types::Request;
 
-    use crate::record::RecordedRequest;
+    use crate::{MatcherStrategy, record::{RecordConfig, RecordedExchange, RecordedRequest}};
 
     use super::*;
 
@@ -23,27 +32,55 @@ mod req_url_mapping_tests {
         let path = "http://localhost/api/pets";
         let req = RecordedRequest(Request::get(path));
         let mut exchange = RecordedExchange { 0: req, ..Default::default() };
-        assert_eq!(HttpUrlStub::from(&mut exchange), HttpUrlStub { url_path: Some("/api/pets".to_string()), ..Default::default() })
+        assert_eq!(HttpUrlStub::from((&mut exchange, &RecordConfig::default())), HttpUrlStub { url_path: Some("/api/pets".to_string()), ..Default::default() })
     }
 
     #[test]
     fn should_not_map_empty_path() {
         let req = RecordedRequest(Request::get("http://localhost"));
         let mut exchange = RecordedExchange { 0: req, ..Default::default() };
-        assert_eq!(HttpUrlStub::from(&mut exchange), HttpUrlStub { url_path: None, ..Default::default() })
+        assert_eq!(HttpUrlStub::from((&mut exchange, &RecordConfig::default())), HttpUrlStub { url_path: None, ..Default::default() })
     }
 
     #[test]
     fn should_not_map_blank_path() {
         let req = RecordedRequest(Request::get("http://localhost  "));
         let mut exchange = RecordedExchange { 0: req, ..Default::default() };
-        assert_eq!(HttpUrlStub::from(&mut exchange), HttpUrlStub { url_path: None, ..Default::default() })
+        assert_eq!(HttpUrlStub::from((&mut exchange, &RecordConfig::default())), HttpUrlStub { url_path: None, ..Default::default() })
     }
 
     #[test]
     fn should_not_map_single_slash_path() {
         let req = RecordedRequest(Request::get("http://localhost/"));
         let mut exchange = RecordedExchange { 0: req, ..Default::default() };
-        assert_eq!(HttpUrlStub::from(&mut exchange), HttpUrlStub { url_path: None, ..Default::default() })
+        assert_eq!(HttpUrlStub::from((&mut exchange, &RecordConfig::default())), HttpUrlStub { url_path: None, ..Default::default() })
+    }
+
+    fn with_url(url: UrlMatching) -> RecordConfig {
+        RecordConfig { matchers: MatcherStrategy { url, ..Default::default() }, ..Default::default() }
+    }
+
+    #[test]
+    fn should_map_path_and_query_to_url() {
+        let req = RecordedRequest(Request::get("http://localhost/api/pets?a=1&b=2"));
+        let mut exchange = RecordedExchange { 0: req, ..Default::default() };
+        let expected = HttpUrlStub { url: Some("/api/pets?a=1&b=2".to_string()), ..Default::default() };
+        assert_eq!(HttpUrlStub::from((&mut exchange, &with_url(UrlMatching::Url))), expected)
+    }
+
+    #[test]
+    fn should_map_path_without_query_to_url() {
+        let req = RecordedRequest(Request::get("http://localhost/api/pets"));
+        let mut exchange = RecordedExchange { 0: req, ..Default::default() };
+        let expected = HttpUrlStub { url: Some("/api/pets".to_string()), ..Default::default() };
+        assert_eq!(HttpUrlStub::from((&mut exchange, &with_url(UrlMatching::Url))), expected)
+    }
+
+    #[test]
+    fn should_map_escaped_path_to_url_path_pattern() {
+        let req = RecordedRequest(Request::get("http://localhost/api/pets.json"));
+        let mut exchange = RecordedExchange { 0: req, ..Default::default() };
+        let expected = HttpUrlStub { url_path_pattern: Some("/api/pets\\.json".to_string()), ..Default::default() };
+        assert_eq!(HttpUrlStub::from((&mut exchange, &with_url(UrlMatching::PathPattern))), expected)
     }
 }
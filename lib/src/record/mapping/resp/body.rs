@@ -1,21 +1,24 @@
 use async_std::task::block_on;
-use serde_json::Value;
 
 use crate::model::response::body::BodyStub;
 
-use super::super::super::RecordedExchange;
+use super::super::{body_kind::BodyKind, super::RecordedExchange};
 
 impl From<&mut RecordedExchange> for BodyStub {
     fn from(ex: &mut RecordedExchange) -> Self {
-        let json_body = block_on(async move {
+        let content_type = ex.resp().content_type().map(|m| m.essence().to_string());
+        let body = block_on(async move {
             ex.1.0.take_body()
                 .into_bytes().await.ok()
-                .and_then(|b| {
-                    ex.1.0.set_body(b.clone());
-                    serde_json::from_slice::<Value>(b.as_slice()).ok()
-                })
+                .inspect(|b| ex.1.0.set_body(b.clone()))
+                .unwrap_or_default()
         });
-        Self { json_body, ..Default::default() }
+        match BodyKind::guess(content_type.as_deref(), body) {
+            BodyKind::Empty => Self::default(),
+            BodyKind::Json(json) => Self { json_body: Some(json), ..Default::default() },
+            BodyKind::Text(text) => Self { body: Some(text), ..Default::default() },
+            BodyKind::Binary(bytes) => Self { base_64_body: Some(base64::encode(bytes)), ..Default::default() },
+        }
     }
 }
 
@@ -55,4 +58,32 @@ mod resp_body_mapping_tests {
         let expected = BodyStub { json_body: None, ..Default::default() };
         assert_eq!(BodyStub::from(&mut exchange), expected)
     }
+
+    #[test]
+    fn should_map_text_body() {
+        let mut resp = Response::new(200);
+        resp.set_body("hello");
+        let mut exchange = RecordedExchange { 1: RecordedResponse(resp), ..Default::default() };
+        let expected = BodyStub { body: Some(String::from("hello")), ..Default::default() };
+        assert_eq!(BodyStub::from(&mut exchange), expected)
+    }
+
+    #[test]
+    fn should_map_xml_body() {
+        let mut resp = Response::new(200);
+        resp.set_body("<a>b</a>");
+        resp.set_content_type(http_types::Mime::from("application/xml"));
+        let mut exchange = RecordedExchange { 1: RecordedResponse(resp), ..Default::default() };
+        let expected = BodyStub { body: Some(String::from("<a>b</a>")), ..Default::default() };
+        assert_eq!(BodyStub::from(&mut exchange), expected)
+    }
+
+    #[test]
+    fn should_map_binary_body() {
+        let mut resp = Response::new(200);
+        resp.set_body(vec![1, 2, 3]);
+        let mut exchange = RecordedExchange { 1: RecordedResponse(resp), ..Default::default() };
+        let expected = BodyStub { base_64_body: Some(String::from("AQID")), ..Default::default() };
+        assert_eq!(BodyStub::from(&mut exchange), expected)
+    }
 }
\ No newline at end of file
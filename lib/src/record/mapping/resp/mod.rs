@@ -6,7 +6,7 @@ pub mod headers;
 impl From<RecordInput<'_>> for ResponseStub {
     fn from((ex, cfg): RecordInput) -> Self {
         Self {
-            status: Some(ex.resp().status().into()),
+            status: Some(u16::from(ex.resp().status()).into()),
             fixed_delay_milliseconds: None,
             body: BodyStub::from(&mut *ex),
             headers: HttpRespHeadersStub::from((&mut *ex, cfg)),
@@ -27,20 +27,20 @@ mod status_mapping_tests {
     fn should_map_200_status() {
         let resp = Response::new(200);
         let mut exchange = RecordedExchange { 1: RecordedResponse(resp), ..Default::default() };
-        assert_eq!(ResponseStub::from((&mut exchange, &RecordConfig::default())).status, Some(200))
+        assert_eq!(ResponseStub::from((&mut exchange, &RecordConfig::default())).status, Some(200.into()))
     }
 
     #[test]
     fn should_map_400_status() {
         let resp = Response::new(400);
         let mut exchange = RecordedExchange { 1: RecordedResponse(resp), ..Default::default() };
-        assert_eq!(ResponseStub::from((&mut exchange, &RecordConfig::default())).status, Some(400))
+        assert_eq!(ResponseStub::from((&mut exchange, &RecordConfig::default())).status, Some(400.into()))
     }
 
     #[test]
     fn should_map_500_status() {
         let resp = Response::new(500);
         let mut exchange = RecordedExchange { 1: RecordedResponse(resp), ..Default::default() };
-        assert_eq!(ResponseStub::from((&mut exchange, &RecordConfig::default())).status, Some(500))
+        assert_eq!(ResponseStub::from((&mut exchange, &RecordConfig::default())).status, Some(500.into()))
     }
 }
\ No newline at end of file
@@ -9,6 +9,9 @@ mod mapping;
 pub mod config;
 pub mod client;
 mod writer;
+mod manifest;
+mod filter;
+#[cfg(feature = "record-standalone")]
 pub mod core;
 pub mod record_client;
 
@@ -28,6 +31,60 @@ impl RecordedExchange {
     pub fn req(&self) -> &HttpRequest { &self.0.0 }
     pub fn resp(&self) -> &HttpResponse { &self.1.0 }
     pub fn host(&self) -> &str { self.req().host().unwrap_or(Self::DEFAULT_HOST) }
+
+    /// Writes it as a stub unless filtered out. Failures are logged rather than failing the
+    /// recorded call
+    #[cfg(any(feature = "record-reqwest", feature = "record-actix", feature = "record-tower", feature = "record-reqwest-middleware", feature = "record-surf", feature = "record-hyper"))]
+    pub(crate) fn write(mut self, cfg: &RecordConfig) {
+        if cfg.should_record(&self) {
+            let host = self.host().to_string();
+            let (method, url) = (self.req().method(), self.req().url().clone());
+            let stub = crate::model::JsonStub::from((&mut self, cfg));
+            if let Err(e) = (writer::StubWriter { stub }).write(&host, cfg) {
+                log::error!("failed recording {} {} because {:?}", method, url, e);
+            }
+        }
+    }
+}
+
+/// Headers whose single value may contain a ',' e.g. a date or a cookie
+#[cfg(any(feature = "record-standalone", feature = "record-actix", feature = "record-tower", feature = "record-reqwest-middleware"))]
+const UNSPLITTABLE_HEADERS: [&str; 15] = [
+    "date", "expires", "last-modified", "if-modified-since", "if-unmodified-since", "if-range", "retry-after",
+    "set-cookie", "cookie", "user-agent", "server", "authorization", "proxy-authorization", "www-authenticate",
+    "proxy-authenticate",
+];
+
+/// Values of a recorded header. Comma separated lists are split, except for headers whose own
+/// values may contain a ',' which are kept as they are
+#[cfg(any(feature = "record-standalone", feature = "record-actix", feature = "record-tower", feature = "record-reqwest-middleware"))]
+pub(crate) fn header_values(key: &str, value: &str) -> http_types::headers::HeaderValues {
+    use http_types::headers::HeaderValue;
+    if UNSPLITTABLE_HEADERS.contains(&key.to_lowercase().as_str()) {
+        value.parse::<HeaderValue>().into_iter().collect()
+    } else {
+        value.split(',').map(str::trim).filter_map(|v| v.parse::<HeaderValue>().ok()).collect()
+    }
 }
 
-type RecordInput<'a> = (&'a mut RecordedExchange, &'a RecordConfig);
\ No newline at end of file
+type RecordInput<'a> = (&'a mut RecordedExchange, &'a RecordConfig);
+#[cfg(test)]
+mod record_tests {
+    use super::*;
+
+    fn values(key: &str, value: &str) -> Vec<String> {
+        header_values(key, value).iter().map(|v| v.as_str().to_string()).collect()
+    }
+
+    #[test]
+    fn should_split_list_headers() {
+        assert_eq!(values("x-m", "a, b"), vec!["a", "b"]);
+        assert_eq!(values("cache-control", "no-cache,no-store"), vec!["no-cache", "no-store"]);
+    }
+
+    #[test]
+    fn should_keep_values_containing_a_comma() {
+        assert_eq!(values("Date", "Tue, 15 Nov 1994 08:12:31 GMT"), vec!["Tue, 15 Nov 1994 08:12:31 GMT"]);
+        assert_eq!(values("set-cookie", "a=1; Expires=Wed, 21 Oct 2015 07:28:00 GMT"), vec!["a=1; Expires=Wed, 21 Oct 2015 07:28:00 GMT"]);
+    }
+}
\ No newline at end of file
@@ -22,16 +22,14 @@ use futures_util::{
 };
 use http::uri::Scheme;
 use http_types::{
-    headers::{HeaderName as HttpHeaderName, HeaderValue as HttpHeaderValue, HeaderValues as HttpHeaderValues},
+    headers::HeaderName as HttpHeaderName,
     Method as HttpMethod,
     Request as HttpRequest,
     Response as HttpResponse,
     Url,
 };
 
-use crate::model::JsonStub;
-
-use super::super::{config::RecordConfig, RecordedExchange, RecordedRequest, RecordedResponse, writer::StubWriter};
+use super::super::{config::RecordConfig, header_values, RecordedExchange, RecordedRequest, RecordedResponse};
 
 #[derive(Default)]
 pub struct ActixRecord(pub RecordConfig);
@@ -73,12 +71,8 @@ impl<S> Service<ActixRequest> for ActixRecordMiddleware<S>
         let fut = self.service.call(ServiceRequest::from_parts(http_req, payload));
         Box::pin(async move {
             let resp: ActixServiceResponse = fut.await?;
-            let host = rec_req.0.url().host_str().unwrap().to_string();
             let RecordedResponsePair(resp, rec_resp) = RecordedResponsePair::from(resp);
-            let mut exchange = RecordedExchange(rec_req, rec_resp);
-            let stub = JsonStub::from((&mut exchange, &cfg));
-            let writer = StubWriter { stub };
-            writer.write(&host, cfg.output.as_ref()).unwrap();
+            RecordedExchange(rec_req, rec_resp).write(&cfg);
             Ok(resp)
         })
     }
@@ -98,10 +92,8 @@ impl From<(&actix_web::HttpRequest, Payload)> for RecordedRequestPair {
         let mut http_req = HttpRequest::new(method, url.as_str());
         req.headers().iter()
             .filter_map(|(k, v)| {
+                let v = v.to_str().ok().map(|v| header_values(k.as_str(), v));
                 let k = HttpHeaderName::from_str(k.as_str()).ok();
-                let v = v.to_str().ok()
-                    .map(|it| it.split(',').map(|s| s.trim()).filter_map(|i| HttpHeaderValue::from_str(i).ok()))
-                    .map(HttpHeaderValues::from_iter);
                 k.zip(v)
             })
             .for_each(|(k, v)| http_req.append_header(k, &v));
@@ -130,10 +122,8 @@ impl From<ActixServiceResponse> for RecordedResponsePair {
         let mut http_resp = HttpResponse::new(status);
         resp.headers().iter()
             .filter_map(|(k, v)| {
+                let v = v.to_str().ok().map(|v| header_values(k.as_str(), v));
                 let k = HttpHeaderName::from_str(k.as_str()).ok();
-                let v = v.to_str().ok()
-                    .map(|it| it.split(',').map(|s| s.trim()).filter_map(|i| HttpHeaderValue::from_str(i).ok()))
-                    .map(HttpHeaderValues::from_iter);
                 k.zip(v)
             })
             .for_each(|(k, v)| http_resp.append_header(k, &v));
@@ -0,0 +1,58 @@
+use std::task::{Context, Poll};
+
+use hyper::{Body, Client, client::{connect::Connect, HttpConnector}, Request, Response, Uri};
+use tower::{BoxError, Layer, Service, ServiceExt};
+
+use super::{super::config::RecordConfig, tower::{TowerRecord, TowerRecordService}};
+
+/// Wraps a hyper [`Client`] so that every exchange it goes through gets recorded as a stub,
+/// whatever its connector.
+/// Recording happens at the client level since a connector only sees raw (and maybe encrypted)
+/// bytes. So it is not a connector itself and cannot be handed to [`hyper::client::Builder::build`]
+pub trait HyperRecord<C> {
+    fn record(self) -> HyperRecordClient<C>;
+
+    fn record_with(self, cfg: RecordConfig) -> HyperRecordClient<C>;
+}
+
+impl<C> HyperRecord<C> for Client<C> {
+    fn record(self) -> HyperRecordClient<C> {
+        self.record_with(RecordConfig::default())
+    }
+
+    fn record_with(self, cfg: RecordConfig) -> HyperRecordClient<C> {
+        HyperRecordClient(TowerRecord(cfg).layer(self))
+    }
+}
+
+/// A hyper [`Client`] recording its exchanges. Requests have to be absolute e.g. 'http://localhost:8080/api'.
+/// Besides [`HyperRecordClient::get`] and [`HyperRecordClient::request`], it is a tower [`Service`]
+/// like the [`Client`] it wraps. It fails with the [`hyper::Error`] of the client, or of a body which could not be read
+#[derive(Clone)]
+pub struct HyperRecordClient<C = HttpConnector>(TowerRecordService<Client<C>>);
+
+impl<C> HyperRecordClient<C> where C: Connect + Clone + Send + Sync + 'static {
+    pub async fn get(&self, uri: Uri) -> Result<Response<Body>, BoxError> {
+        let mut req = Request::new(Body::empty());
+        *req.uri_mut() = uri;
+        self.request(req).await
+    }
+
+    pub async fn request(&self, req: Request<Body>) -> Result<Response<Body>, BoxError> {
+        self.0.clone().oneshot(req).await
+    }
+}
+
+impl<C> Service<Request<Body>> for HyperRecordClient<C> where C: Connect + Clone + Send + Sync + 'static {
+    type Response = Response<Body>;
+    type Error = BoxError;
+    type Future = <TowerRecordService<Client<C>> as Service<Request<Body>>>::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        self.0.call(req)
+    }
+}
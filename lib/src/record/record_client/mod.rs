@@ -1,4 +1,12 @@
 #[cfg(feature = "record-reqwest")]
 pub mod reqwest;
 #[cfg(feature = "record-actix")]
-pub mod actix;
\ No newline at end of file
+pub mod actix;
+#[cfg(feature = "record-tower")]
+pub mod tower;
+#[cfg(feature = "record-reqwest-middleware")]
+pub mod reqwest_middleware;
+#[cfg(feature = "record-surf")]
+pub mod surf;
+#[cfg(feature = "record-hyper")]
+pub mod hyper;
\ No newline at end of file
@@ -3,8 +3,6 @@ use std::str::FromStr;
 use http_types::{
     Body as HttpBody,
     headers::HeaderName as HttpHeaderName,
-    headers::HeaderValue as HttpHeaderValue,
-    headers::HeaderValues as HttpHeaderValues,
     Method as HttpMethod,
     Request as HttpRequest,
     Response as HttpResponse,
@@ -12,25 +10,18 @@ use http_types::{
 };
 use reqwest::blocking::{Request, RequestBuilder as ReqwestRequestBuilder, Response};
 
-use crate::model::JsonStub;
-
 use super::super::{
     core::Record,
+    header_values,
     RecordConfig,
     RecordedExchange, RecordedRequest, RecordedResponse,
-    writer::StubWriter,
 };
 
 impl Record for ReqwestRequestBuilder {
     fn record_with(self, cfg: RecordConfig) -> Self {
         let req = RecordedRequest::from(self.try_clone().and_then(|it| it.build().ok()).unwrap());
         let resp = RecordedResponse::from(self.try_clone().and_then(|it| it.send().ok()).unwrap());
-        let host = req.0.url().host_str().unwrap().to_string();
-        let mut exchange = RecordedExchange(req, resp);
-
-        let stub = JsonStub::from((&mut exchange, &cfg));
-        let writer = StubWriter { stub };
-        writer.write(&host, cfg.output.as_ref()).unwrap();
+        RecordedExchange(req, resp).write(&cfg);
         self
     }
 }
@@ -47,10 +38,8 @@ impl From<Request> for RecordedRequest {
         let mut http_req = HttpRequest::new(method, url.as_str());
         req.headers().iter()
             .filter_map(|(k, v)| {
+                let v = v.to_str().ok().map(|v| header_values(k.as_str(), v));
                 let k = HttpHeaderName::from_str(k.as_str()).ok();
-                let v = v.to_str().ok()
-                    .map(|it| it.split(',').map(|s| s.trim()).filter_map(|i| HttpHeaderValue::from_str(i).ok()))
-                    .map(HttpHeaderValues::from_iter);
                 k.zip(v)
             })
             .for_each(|(k, v)| http_req.append_header(k, &v));
@@ -68,10 +57,8 @@ impl From<Response> for RecordedResponse {
         let mut http_resp = HttpResponse::new(status);
         resp.headers().iter()
             .filter_map(|(k, v)| {
+                let v = v.to_str().ok().map(|v| header_values(k.as_str(), v));
                 let k = HttpHeaderName::from_str(k.as_str()).ok();
-                let v = v.to_str().ok()
-                    .map(|it| it.split(',').map(|s| s.trim()).filter_map(|i| HttpHeaderValue::from_str(i).ok()))
-                    .map(HttpHeaderValues::from_iter);
                 k.zip(v)
             })
             .for_each(|(k, v)| http_resp.append_header(k, &v));
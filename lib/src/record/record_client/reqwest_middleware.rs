@@ -0,0 +1,121 @@
+use std::str::FromStr;
+
+use http_types::{
+    headers::HeaderName as HttpHeaderName,
+    Method as HttpMethod,
+    Request as HttpRequest,
+    Response as HttpResponse,
+};
+use reqwest::{Request, Response, ResponseBuilderExt};
+use reqwest_middleware::{Error, Middleware, Next, Result};
+use task_local_extensions::Extensions;
+
+use super::super::{config::RecordConfig, header_values, RecordedExchange, RecordedRequest, RecordedResponse};
+
+/// [`Middleware`] recording every exchange of a `reqwest_middleware::ClientWithMiddleware` as a stub
+#[derive(Default, Clone)]
+pub struct ReqwestRecordMiddleware(pub RecordConfig);
+
+#[async_trait::async_trait]
+impl Middleware for ReqwestRecordMiddleware {
+    async fn handle(&self, req: Request, extensions: &mut Extensions, next: Next<'_>) -> Result<Response> {
+        let rec_req = RecordedRequest::from(&req);
+        let resp = next.run(req, extensions).await?;
+        let status = resp.status();
+        let version = resp.version();
+        let url = resp.url().clone();
+        let headers = resp.headers().clone();
+        let body = resp.bytes().await?;
+        let rec_resp = RecordedResponse::from((status.as_u16(), &headers, body.as_ref()));
+        RecordedExchange(rec_req, rec_resp).write(&self.0);
+        let mut resp = http::Response::builder().status(status).version(version).url(url);
+        if let Some(h) = resp.headers_mut() {
+            *h = headers;
+        }
+        resp.body(body)
+            .map(Response::from)
+            .map_err(|e| Error::Middleware(e.into()))
+    }
+}
+
+impl From<&Request> for RecordedRequest {
+    fn from(req: &Request) -> Self {
+        let method = HttpMethod::from_str(req.method().as_str()).unwrap_or(HttpMethod::Get);
+        let mut url = req.url().clone();
+        url.set_fragment(None);
+        let mut http_req = HttpRequest::new(method, url.as_str());
+        req.headers().iter()
+            .filter_map(|(k, v)| {
+                let v = v.to_str().ok().map(|v| header_values(k.as_str(), v));
+                let k = HttpHeaderName::from_str(k.as_str()).ok();
+                k.zip(v)
+            })
+            .for_each(|(k, v)| http_req.append_header(k, &v));
+        // a streamed body cannot be read without consuming it
+        if let Some(body) = req.body().and_then(|it| it.as_bytes()) {
+            http_req.set_body(body)
+        }
+        Self(http_req)
+    }
+}
+
+impl From<(u16, &reqwest::header::HeaderMap, &[u8])> for RecordedResponse {
+    fn from((status, headers, body): (u16, &reqwest::header::HeaderMap, &[u8])) -> Self {
+        let mut http_resp = HttpResponse::new(status);
+        headers.iter()
+            .filter_map(|(k, v)| {
+                let v = v.to_str().ok().map(|v| header_values(k.as_str(), v));
+                let k = HttpHeaderName::from_str(k.as_str()).ok();
+                k.zip(v)
+            })
+            .for_each(|(k, v)| http_resp.append_header(k, &v));
+        http_resp.set_body(body);
+        Self(http_resp)
+    }
+}
+
+#[cfg(test)]
+mod reqwest_middleware_tests {
+    use async_std::task::block_on;
+    use reqwest::{Client, header::HeaderMap};
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    #[test]
+    fn should_map_request() {
+        let req = Client::new().post("http://localhost:8080/api?a=1")
+            .header("x-a", "a, b")
+            .json(&json!({"a": "b"}))
+            .build().unwrap();
+        let mut output = RecordedRequest::from(&req).0;
+        assert_eq!(output.method(), HttpMethod::Post);
+        assert_eq!(output.url().as_str(), "http://localhost:8080/api?a=1");
+        let multi = output.header("x-a").unwrap();
+        assert_eq!(multi.get(0).unwrap().as_str(), "a");
+        assert_eq!(multi.get(1).unwrap().as_str(), "b");
+        let body = block_on(async move { output.body_json::<Value>().await.unwrap() });
+        assert_eq!(body, json!({"a": "b"}));
+    }
+
+    #[test]
+    fn should_not_fail_when_no_request_body() {
+        let req = Client::new().get("http://localhost").build().unwrap();
+        let mut output = RecordedRequest::from(&req).0;
+        let body = block_on(async move { output.body_bytes().await.unwrap() });
+        assert!(body.is_empty());
+    }
+
+    #[test]
+    fn should_map_response() {
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", "application/xml".parse().unwrap());
+        headers.insert("date", "Tue, 15 Nov 1994 08:12:31 GMT".parse().unwrap());
+        let mut output = RecordedResponse::from((201, &headers, b"<a/>".as_ref())).0;
+        assert_eq!(output.header("date").unwrap().as_str(), "Tue, 15 Nov 1994 08:12:31 GMT");
+        assert_eq!(u16::from(output.status()), 201);
+        assert_eq!(output.header("content-type").unwrap().get(0).unwrap().as_str(), "application/xml");
+        let body = block_on(async move { output.body_string().await.unwrap() });
+        assert_eq!(body, "<a/>");
+    }
+}
@@ -0,0 +1,24 @@
+use surf::{Client, middleware::{Middleware, Next}, Request, Response, Result};
+
+use super::super::{config::RecordConfig, RecordedExchange, RecordedRequest, RecordedResponse};
+
+/// [`Middleware`] recording every exchange of a surf [`Client`] as a stub
+#[derive(Default, Clone)]
+pub struct SurfRecord(pub RecordConfig);
+
+#[async_trait::async_trait]
+impl Middleware for SurfRecord {
+    async fn handle(&self, mut req: Request, client: Client, next: Next<'_>) -> Result<Response> {
+        let body = req.take_body().into_bytes().await?;
+        let mut rec_req = AsRef::<http_types::Request>::as_ref(&req).clone();
+        rec_req.set_body(body.as_slice());
+        req.set_body(body);
+        let mut resp = next.run(req, client).await?;
+        let body = resp.take_body().into_bytes().await?;
+        let mut rec_resp = AsRef::<http_types::Response>::as_ref(&resp).clone();
+        rec_resp.set_body(body.as_slice());
+        resp.set_body(body);
+        RecordedExchange(RecordedRequest(rec_req), RecordedResponse(rec_resp)).write(&self.0);
+        Ok(resp)
+    }
+}
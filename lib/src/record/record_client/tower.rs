@@ -0,0 +1,222 @@
+use std::{future::Future, pin::Pin, str::FromStr, task::{Context, Poll}};
+
+use http::{request::Parts as ReqParts, response::Parts as RespParts, uri::Scheme};
+use http_types::{
+    headers::HeaderName as HttpHeaderName,
+    Method as HttpMethod,
+    Request as HttpRequest,
+    Response as HttpResponse,
+    Url,
+};
+use hyper::{Body, body::{Bytes, HttpBody, to_bytes}, Request, Response};
+use tower::{BoxError, Layer, Service};
+
+use super::super::{config::RecordConfig, header_values, RecordedExchange, RecordedRequest, RecordedResponse};
+
+/// [`Layer`] recording every exchange of the wrapped service (e.g. an axum `Router`) as a stub.
+/// When reading a request or response body fails, the exchange fails with this error and is not recorded
+#[derive(Default, Clone)]
+pub struct TowerRecord(pub RecordConfig);
+
+impl<S> Layer<S> for TowerRecord {
+    type Service = TowerRecordService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TowerRecordService { inner, cfg: self.0.to_owned() }
+    }
+}
+
+#[derive(Clone)]
+pub struct TowerRecordService<S> {
+    inner: S,
+    cfg: RecordConfig,
+}
+
+impl<S, B> Service<Request<Body>> for TowerRecordService<S>
+    where S: Service<Request<Body>, Response=Response<B>> + Clone + Send + 'static,
+          S::Error: Into<BoxError>,
+          S::Future: Send,
+          B: HttpBody + Send + 'static,
+          B::Data: Send,
+          B::Error: Into<BoxError>,
+{
+    type Response = Response<Body>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output=Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let cfg = self.cfg.clone();
+        // the clone might not be ready, so keep the one we polled
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+            let body = to_bytes(body).await?;
+            let rec_req = RecordedRequest::from((&parts, &body));
+            let resp = inner.call(Request::from_parts(parts, Body::from(body))).await.map_err(Into::into)?;
+            let (parts, body) = resp.into_parts();
+            let body = to_bytes(body).await.map_err(Into::into)?;
+            let rec_resp = RecordedResponse::from((&parts, &body));
+            RecordedExchange(rec_req, rec_resp).write(&cfg);
+            Ok(Response::from_parts(parts, Body::from(body)))
+        })
+    }
+}
+
+impl From<(&ReqParts, &Bytes)> for RecordedRequest {
+    fn from((req, body): (&ReqParts, &Bytes)) -> Self {
+        let method = HttpMethod::from_str(req.method.as_str()).unwrap_or(HttpMethod::Get);
+        let path = req.uri.path();
+        let scheme = req.uri.scheme().unwrap_or(&Scheme::HTTP);
+        let host = req.uri.host().unwrap_or("localhost");
+        let queries = req.uri.query().unwrap_or_default();
+        let mut url = Url::from_str(&format!("{}://{}{}?{}", scheme, host, path, queries)).unwrap();
+        url.set_port(req.uri.port_u16()).unwrap();
+        let mut http_req = HttpRequest::new(method, url.as_str());
+        req.headers.iter()
+            .filter_map(|(k, v)| {
+                let v = v.to_str().ok().map(|v| header_values(k.as_str(), v));
+                let k = HttpHeaderName::from_str(k.as_str()).ok();
+                k.zip(v)
+            })
+            .for_each(|(k, v)| http_req.append_header(k, &v));
+        http_req.set_body(body.as_ref());
+        Self(http_req)
+    }
+}
+
+impl From<(&RespParts, &Bytes)> for RecordedResponse {
+    fn from((resp, body): (&RespParts, &Bytes)) -> Self {
+        let mut http_resp = HttpResponse::new(resp.status.as_u16());
+        resp.headers.iter()
+            .filter_map(|(k, v)| {
+                let v = v.to_str().ok().map(|v| header_values(k.as_str(), v));
+                let k = HttpHeaderName::from_str(k.as_str()).ok();
+                k.zip(v)
+            })
+            .for_each(|(k, v)| http_resp.append_header(k, &v));
+        http_resp.set_body(body.as_ref());
+        Self(http_resp)
+    }
+}
+
+#[cfg(test)]
+mod tower_record_tests {
+    use async_std::task::block_on;
+
+    use super::*;
+
+    fn req(req: http::request::Builder, body: &'static str) -> HttpRequest {
+        let (parts, _) = req.body(()).unwrap().into_parts();
+        RecordedRequest::from((&parts, &Bytes::from_static(body.as_bytes()))).0
+    }
+
+    fn resp(resp: http::response::Builder, body: &'static str) -> HttpResponse {
+        let (parts, _) = resp.body(()).unwrap().into_parts();
+        RecordedResponse::from((&parts, &Bytes::from_static(body.as_bytes()))).0
+    }
+
+    mod method {
+        use http_types::Method;
+
+        use super::*;
+
+        #[test]
+        fn should_map_method_get() {
+            assert_eq!(req(Request::get("/"), "").method(), Method::Get)
+        }
+
+        #[test]
+        fn should_map_method_post() {
+            assert_eq!(req(Request::post("/"), "").method(), Method::Post)
+        }
+
+        #[test]
+        fn should_map_method_delete() {
+            assert_eq!(req(Request::delete("/"), "").method(), Method::Delete)
+        }
+    }
+
+    mod uri {
+        use super::*;
+
+        #[test]
+        fn should_map_scheme_host_and_port() {
+            let url = req(Request::get("https://github.com:8080/api/colors?a=1"), "").url().clone();
+            assert_eq!(url.scheme(), "https");
+            assert_eq!(url.host_str(), Some("github.com"));
+            assert_eq!(url.port(), Some(8080));
+            assert_eq!(url.path(), "/api/colors");
+            assert_eq!(url.query(), Some("a=1"));
+        }
+
+        #[test]
+        fn should_default_to_localhost_when_relative() {
+            let url = req(Request::get("/api/colors"), "").url().clone();
+            assert_eq!(url.host_str(), Some("localhost"));
+            assert_eq!(url.path(), "/api/colors");
+        }
+    }
+
+    mod headers {
+        use super::*;
+
+        #[test]
+        fn should_map_multi_req_header() {
+            let output = req(Request::get("/").header("x-m", "a, b"), "");
+            let multi = output.header("x-m").unwrap();
+            assert_eq!(multi.get(0).unwrap().as_str(), "a");
+            assert_eq!(multi.get(1).unwrap().as_str(), "b");
+        }
+
+        #[test]
+        fn should_map_many_resp_header() {
+            let output = resp(Response::builder().header("x-a", "a").header("x-b", "b"), "");
+            assert_eq!(output.header("x-a").unwrap().get(0).unwrap().as_str(), "a");
+            assert_eq!(output.header("x-b").unwrap().get(0).unwrap().as_str(), "b");
+        }
+    }
+
+    mod body {
+        use serde_json::{json, Value};
+
+        use super::*;
+
+        #[test]
+        fn should_map_json_req_body() {
+            let mut output = req(Request::post("/"), r#"{"a":"b"}"#);
+            let body = block_on(async move { output.body_json::<Value>().await.unwrap() });
+            assert_eq!(body, json!({"a": "b"}));
+        }
+
+        #[test]
+        fn should_map_text_resp_body() {
+            let mut output = resp(Response::builder(), "Hello World!");
+            let body = block_on(async move { output.body_bytes().await.unwrap() });
+            assert_eq!(&body, b"Hello World!");
+        }
+
+        #[test]
+        fn should_not_fail_when_resp_body_empty() {
+            let mut output = resp(Response::builder(), "");
+            let body = block_on(async move { output.body_bytes().await.unwrap() });
+            assert!(body.is_empty());
+        }
+    }
+
+    mod status {
+        use http_types::StatusCode;
+
+        use super::*;
+
+        #[test]
+        fn should_map_status() {
+            assert_eq!(resp(Response::builder().status(200), "").status(), StatusCode::Ok);
+            assert_eq!(resp(Response::builder().status(500), "").status(), StatusCode::InternalServerError);
+        }
+    }
+}
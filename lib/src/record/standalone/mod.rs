@@ -1,10 +1,14 @@
 use std::net::SocketAddr;
+#[cfg(feature = "record-https")]
+use std::sync::Arc;
 
 use log::{error, info};
 use tokio::sync::mpsc::Sender;
 
 use logger::RecordLogger;
 use proxy::Proxy;
+#[cfg(feature = "record-https")]
+use tls::RecordCa;
 
 use super::config::RecordConfig;
 use super::super::model::JsonStub;
@@ -15,11 +19,15 @@ pub mod proxy;
 pub mod logger;
 pub mod warp_exchange;
 pub mod port;
+#[cfg(feature = "record-https")]
+pub mod tls;
 
 #[cfg(feature = "record-standalone")]
 pub struct StubrRecord {
     addr: SocketAddr,
     tx: Sender<String>,
+    #[cfg(feature = "record-https")]
+    ca: Arc<RecordCa>,
 }
 
 #[cfg(feature = "record-standalone")]
@@ -30,19 +38,25 @@ impl StubrRecord {
         format!("http://{}", self.addr)
     }
 
-    pub(crate) fn record(config: RecordConfig) -> Self {
-        let (addr, tx) = Proxy::run(config, |(ex, cfg)| {
+    pub(crate) fn record(config: RecordConfig) -> anyhow::Result<Self> {
+        Proxy::run(config, |(ex, cfg)| {
             let host = format!("http://{}", ex.host());
             let method = ex.req().method().to_string();
             let url = ex.req().url().clone();
             let status: u16 = ex.resp().status().into();
             let stub = JsonStub::from((ex, cfg));
             let writer = StubWriter { stub };
-            writer.write(&host, cfg.output.as_ref())
+            writer.write(&host, cfg)
                 .map(|f| RecordLogger::success(f, status, &method, &url))
                 .unwrap_or_else(|e| RecordLogger::error(e, status, &method, &url));
-        });
-        Self { addr, tx }
+        })
+    }
+
+    /// PEM encoded certificate authority issuing certificates for https hosts tunneled through
+    /// the recorder. Clients have to trust it for their https traffic to be recorded.
+    #[cfg(feature = "record-https")]
+    pub fn ca_cert(&self) -> Vec<u8> {
+        self.ca.cert_pem()
     }
 
     #[cfg(feature = "record-isahc")]
@@ -50,10 +64,21 @@ impl StubrRecord {
         crate::isahc_client(self.uri())
     }
 
-    #[cfg(feature = "record-reqwest")]
+    #[cfg(all(feature = "record-reqwest", not(feature = "record-https")))]
     pub fn reqwest_client(&self) -> reqwest::Client {
         crate::reqwest_client(self.uri())
     }
+
+    /// Also records https requests since it trusts [StubrRecord::ca_cert]
+    #[cfg(all(feature = "record-reqwest", feature = "record-https"))]
+    pub fn reqwest_client(&self) -> reqwest::Client {
+        let ca = reqwest::Certificate::from_pem(&self.ca_cert()).expect("Invalid recorder CA");
+        reqwest::ClientBuilder::new()
+            .proxy(reqwest::Proxy::all(self.uri()).expect("Failed building reqwest proxy"))
+            .add_root_certificate(ca)
+            .build()
+            .expect("Failed building reqwest recording client")
+    }
 }
 
 #[cfg(feature = "record-standalone")]
@@ -1,42 +1,110 @@
-use std::net::SocketAddr;
+use std::{convert::Infallible, future::Future, pin::Pin};
+#[cfg(feature = "record-https")]
+use std::sync::Arc;
 
-use log::info;
-use tokio::sync::mpsc::{channel, Sender};
-use warp::{Filter, filters::{host::Authority, path::FullPath}, http::HeaderMap, hyper::body::Bytes, Rejection, Reply};
+#[cfg(feature = "record-https")]
+use anyhow::Context;
+
+use http_types::Url;
+#[cfg(feature = "record-https")]
+use log::error;
+use log::{info, warn};
+use tokio::sync::mpsc::channel;
+use warp::{
+    Filter,
+    filters::{BoxedFilter, host::Authority, path::FullPath},
+    http::{header::HOST, HeaderMap, HeaderValue},
+    hyper::{Body, body::Bytes, Request, Response, Server, service::{make_service_fn, Service, service_fn}},
+    Rejection,
+    Reply,
+};
+#[cfg(feature = "record-https")]
+use warp::hyper::{Method as HyperMethod, server::conn::Http, StatusCode, upgrade};
 use warp_reverse_proxy::{CLIENT, extract_request_data_filter, Method, proxy_to_and_forward_response, QueryParameters};
 
+#[cfg(feature = "record-https")]
+use super::tls::RecordCa;
 use super::{
     port::PortAllocator,
+    StubrRecord,
     super::{config::RecordConfig, RecordedExchange, RecordInput},
     warp_exchange::{WarpExchange, WarpRequest, WarpResponse},
 };
 
+type Replied = Pin<Box<dyn Future<Output=Result<Response<Body>, Infallible>> + Send>>;
+
+/// Where a request gets forwarded to
+#[derive(Debug, Clone)]
+enum Upstream {
+    /// The host the client addressed, the recorder being a forward proxy
+    Host,
+    /// A fixed base url: the configured target or a host tunneled with 'CONNECT'
+    Fixed(String),
+}
+
 pub struct Proxy;
 
 impl Proxy {
-    pub(crate) fn run(mut cfg: RecordConfig, then: fn(RecordInput)) -> (SocketAddr, Sender<String>) {
+    pub(crate) fn run(mut cfg: RecordConfig, then: fn(RecordInput)) -> anyhow::Result<StubrRecord> {
         if let Some(client) = cfg.client.take() {
-            CLIENT.set(client).unwrap();
+            if CLIENT.set(client).is_err() {
+                warn!("Proxy client already initialized by a previous recorder, ignoring the configured one");
+            }
         }
+        #[cfg(feature = "record-https")]
+        let ca = Arc::new(Self::ca(&cfg)?);
 
         let (tx, mut rx) = channel::<String>(1);
         let addr = PortAllocator::new_binding(cfg.port);
-        let server = warp::serve(warp::any().and(Self::forward_and_record(cfg, then).boxed()));
-        let (addr, server) = server.bind_with_graceful_shutdown(addr, async move { rx.recv().await; });
-        tokio::spawn(async move { server.await; });
+        let upstream = cfg.target.clone().map(Upstream::Fixed).unwrap_or(Upstream::Host);
+        let service = warp::service(Self::forward_and_record(cfg.clone(), then, upstream));
+        #[cfg(feature = "record-https")]
+        let tunnel_ca = ca.clone();
+        let make_service = make_service_fn(move |_| {
+            let service = service.clone();
+            #[cfg(feature = "record-https")]
+            let (ca, cfg) = (tunnel_ca.clone(), cfg.clone());
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| -> Replied {
+                    #[cfg(feature = "record-https")]
+                    if req.method() == HyperMethod::CONNECT {
+                        let resp = Self::tunnel(req, ca.clone(), cfg.clone(), then);
+                        return Box::pin(async move { Ok(resp) });
+                    }
+                    Box::pin(service.clone().call(req))
+                }))
+            }
+        });
+        let server = Server::bind(&addr).serve(make_service);
+        let addr = server.local_addr();
+        let server = server.with_graceful_shutdown(async move { rx.recv().await; });
+        tokio::spawn(async move { server.await.ok(); });
         info!("Started stubr recorder on {}", addr);
-        (addr, tx)
+        Ok(StubrRecord {
+            addr,
+            tx,
+            #[cfg(feature = "record-https")]
+            ca,
+        })
     }
 
-    fn forward_and_record(cfg: RecordConfig, then: fn(RecordInput)) -> impl Filter<Extract=(impl Reply, ), Error=Rejection> {
-        Self::host()
+    fn forward_and_record(cfg: RecordConfig, then: fn(RecordInput), upstream: Upstream) -> BoxedFilter<(impl Reply, )> {
+        Self::upstream(upstream)
             .and(warp::any().map(String::new))
             .and(extract_request_data_filter())
             .and_then(Self::proxy)
             .and_then(move |exchange| Self::reply(exchange, cfg.clone(), then))
+            .boxed()
+    }
+
+    fn upstream(upstream: Upstream) -> BoxedFilter<(String, )> {
+        match upstream {
+            Upstream::Host => Self::host().boxed(),
+            Upstream::Fixed(base) => warp::any().map(move || base.clone()).boxed(),
+        }
     }
 
-    fn host() -> impl Filter<Extract=(String, ), Error=Rejection> {
+    fn host() -> impl Filter<Extract=(String, ), Error=Rejection> + Clone {
         warp::filters::host::optional()
             .map(|authority: Option<Authority>| authority.map(Self::base_uri).unwrap_or_default())
     }
@@ -45,6 +113,45 @@ impl Proxy {
         format!("http://{}:{}/", a.host(), a.port_u16().unwrap_or(80))
     }
 
+    /// Answers a 'CONNECT' then terminates TLS with a certificate issued for the tunneled host
+    /// so that the requests it carries can be recorded like plain http ones
+    #[cfg(feature = "record-https")]
+    fn tunnel(req: Request<Body>, ca: Arc<RecordCa>, cfg: RecordConfig, then: fn(RecordInput)) -> Response<Body> {
+        let authority = match req.uri().authority() {
+            Some(authority) => authority.clone(),
+            None => {
+                let mut resp = Response::new(Body::empty());
+                *resp.status_mut() = StatusCode::BAD_REQUEST;
+                return resp;
+            }
+        };
+        tokio::spawn(async move {
+            if let Err(e) = Self::intercept(req, &authority, ca, cfg, then).await {
+                error!("Failed recording tunnel to {} because {:?}", authority, e);
+            }
+        });
+        Response::new(Body::empty())
+    }
+
+    #[cfg(feature = "record-https")]
+    async fn intercept(req: Request<Body>, authority: &Authority, ca: Arc<RecordCa>, cfg: RecordConfig, then: fn(RecordInput)) -> anyhow::Result<()> {
+        let acceptor = ca.acceptor(authority.host())?;
+        let tls = acceptor.accept(upgrade::on(req).await?).await?;
+        let base = format!("https://{}:{}/", authority.host(), authority.port_u16().unwrap_or(443));
+        let service = warp::service(Self::forward_and_record(cfg, then, Upstream::Fixed(base)));
+        Ok(Http::new().serve_connection(tls, service).await?)
+    }
+
+    #[cfg(feature = "record-https")]
+    fn ca(cfg: &RecordConfig) -> anyhow::Result<RecordCa> {
+        match (cfg.ca_cert.as_ref(), cfg.ca_key.as_ref()) {
+            (Some(cert), Some(key)) => RecordCa::load(cert, key)
+                .with_context(|| format!("Failed loading recorder CA from {:?}", cert)),
+            (None, None) => RecordCa::generate().context("Failed generating recorder CA"),
+            _ => Err(anyhow::Error::msg("Both 'ca_cert' and 'ca_key' are required to use a custom recorder CA")),
+        }
+    }
+
     async fn proxy(
         addr: String,
         base_path: String,
@@ -55,16 +162,71 @@ impl Proxy {
         body: Bytes,
     ) -> Result<RecordedExchange, Rejection> {
         let path = uri.as_str().to_string();
-        proxy_to_and_forward_response(addr.clone(), base_path, uri, queries.clone(), method.clone(), headers.clone(), body.clone()).await
+        // the client derives it from the proxy url, the upstream one is set from the forwarded url
+        let mut forwarded_headers = headers.clone();
+        forwarded_headers.remove(HOST);
+        let (origin, headers) = Self::recorded_origin(&addr, headers);
+        proxy_to_and_forward_response(addr, base_path, uri, queries.clone(), method.clone(), forwarded_headers, body.clone()).await
             .map(move |resp| {
-                let req = WarpRequest { method, addr, path, queries, headers, body };
+                let req = WarpRequest { method, addr: origin, path, queries, headers, body };
                 let resp = WarpResponse(resp);
                 WarpExchange(req, resp).into()
             })
     }
 
+    /// Requests are recorded as the client sent them but addressed to the upstream host. So a
+    /// path prefix in the target is left out and stubs are written in the upstream host's directory
+    fn recorded_origin(addr: &str, mut headers: HeaderMap) -> (String, HeaderMap) {
+        let origin = Url::parse(addr).ok()
+            .and_then(|url| url.host_str().map(|host| (url.scheme().to_string(), host.to_string(), url.port())));
+        if let Some((scheme, host, port)) = origin {
+            let authority = port.map(|p| format!("{}:{}", host, p)).unwrap_or(host);
+            if let Ok(value) = HeaderValue::from_str(&authority) {
+                headers.insert(HOST, value);
+            }
+            (format!("{}://{}/", scheme, authority), headers)
+        } else {
+            (addr.to_string(), headers)
+        }
+    }
+
     async fn reply(mut exchange: RecordedExchange, cfg: RecordConfig, then: fn(RecordInput)) -> Result<impl Reply, Rejection> {
-        then((&mut exchange, &cfg));
+        if cfg.should_record(&exchange) {
+            then((&mut exchange, &cfg));
+        }
         Ok(exchange.1)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod proxy_tests {
+    use super::*;
+
+    #[test]
+    fn base_uri_should_default_to_http_port() {
+        assert_eq!(Proxy::base_uri(Authority::from_static("example.com")), "http://example.com:80/");
+        assert_eq!(Proxy::base_uri(Authority::from_static("localhost:8080")), "http://localhost:8080/");
+    }
+
+    #[tokio::test]
+    async fn fixed_upstream_should_ignore_host() {
+        let upstream = Proxy::upstream(Upstream::Fixed(String::from("https://example.com/")));
+        let base = warp::test::request().header("host", "localhost:1234").filter(&upstream).await.unwrap();
+        assert_eq!(base, "https://example.com/");
+        let upstream = Proxy::upstream(Upstream::Host);
+        let base = warp::test::request().header("host", "localhost:1234").filter(&upstream).await.unwrap();
+        assert_eq!(base, "http://localhost:1234/");
+    }
+
+    #[test]
+    fn should_record_target_origin_and_host() {
+        let mut headers = HeaderMap::new();
+        headers.insert(HOST, HeaderValue::from_static("127.0.0.1:3030"));
+        let (origin, headers) = Proxy::recorded_origin("https://api.example.com/v1/", headers);
+        assert_eq!(origin, "https://api.example.com/");
+        assert_eq!(headers.get(HOST).unwrap(), "api.example.com");
+        let (origin, headers) = Proxy::recorded_origin("http://localhost:8080/", HeaderMap::new());
+        assert_eq!(origin, "http://localhost:8080/");
+        assert_eq!(headers.get(HOST).unwrap(), "localhost:8080");
+    }
+}
@@ -0,0 +1,173 @@
+use std::{collections::HashMap, fs::read, net::IpAddr, path::Path, sync::Mutex};
+
+use anyhow::anyhow;
+use native_tls::Identity;
+use openssl::{
+    asn1::Asn1Time,
+    bn::{BigNum, MsbOption},
+    ec::{EcGroup, EcKey},
+    hash::MessageDigest,
+    nid::Nid,
+    pkcs12::Pkcs12,
+    pkey::{PKey, Private},
+    stack::Stack,
+    x509::{
+        extension::{BasicConstraints, ExtendedKeyUsage, KeyUsage, SubjectAlternativeName},
+        X509, X509Name, X509NameBuilder,
+    },
+};
+use tokio_native_tls::TlsAcceptor;
+
+/// Certificate authority issuing on the fly a certificate for every host a client tunnels to
+/// through the recorder. Clients have to trust it in order to be recorded.
+pub struct RecordCa {
+    cert: X509,
+    key: PKey<Private>,
+    acceptors: Mutex<HashMap<String, TlsAcceptor>>,
+}
+
+impl RecordCa {
+    const NAME: &'static str = "stubr recorder CA";
+    const VALIDITY_DAYS: u32 = 365;
+
+    /// Loads a PEM encoded certificate authority
+    pub fn load(cert: &Path, key: &Path) -> anyhow::Result<Self> {
+        let cert = X509::from_pem(&read(cert)?)?;
+        let key = PKey::private_key_from_pem(&read(key)?)?;
+        Ok(Self::new(cert, key))
+    }
+
+    /// Generates a self-signed certificate authority living as long as the recorder
+    pub fn generate() -> anyhow::Result<Self> {
+        let key = Self::new_key()?;
+        let name = Self::name(Self::NAME)?;
+        let mut builder = X509::builder()?;
+        builder.set_version(2)?;
+        builder.set_serial_number(&*Self::serial()?)?;
+        builder.set_subject_name(&name)?;
+        builder.set_issuer_name(&name)?;
+        builder.set_pubkey(&key)?;
+        builder.set_not_before(&*Asn1Time::days_from_now(0)?)?;
+        builder.set_not_after(&*Asn1Time::days_from_now(Self::VALIDITY_DAYS)?)?;
+        builder.append_extension(BasicConstraints::new().critical().ca().build()?)?;
+        builder.append_extension(KeyUsage::new().critical().key_cert_sign().crl_sign().build()?)?;
+        builder.sign(&key, MessageDigest::sha256())?;
+        Ok(Self::new(builder.build(), key))
+    }
+
+    fn new(cert: X509, key: PKey<Private>) -> Self {
+        Self { cert, key, acceptors: Mutex::new(HashMap::new()) }
+    }
+
+    /// PEM encoded certificate to add to the client's trusted roots
+    pub fn cert_pem(&self) -> Vec<u8> {
+        self.cert.to_pem().unwrap_or_default()
+    }
+
+    /// Terminates TLS for a tunneled host. Acceptors are cached since issuing a certificate
+    /// is expensive
+    pub fn acceptor(&self, host: &str) -> anyhow::Result<TlsAcceptor> {
+        let mut acceptors = self.acceptors.lock().map_err(|_| anyhow!("Poisoned TLS acceptors"))?;
+        if let Some(acceptor) = acceptors.get(host) {
+            return Ok(acceptor.clone());
+        }
+        let (cert, key) = self.issue(host)?;
+        let mut chain = Stack::new()?;
+        chain.push(self.cert.clone())?;
+        let mut pkcs12 = Pkcs12::builder();
+        pkcs12.ca(chain);
+        let identity = Identity::from_pkcs12(&pkcs12.build("", host, &key, &cert)?.to_der()?, "")?;
+        let acceptor = TlsAcceptor::from(native_tls::TlsAcceptor::new(identity)?);
+        acceptors.insert(host.to_string(), acceptor.clone());
+        Ok(acceptor)
+    }
+
+    fn issue(&self, host: &str) -> anyhow::Result<(X509, PKey<Private>)> {
+        let key = Self::new_key()?;
+        let mut builder = X509::builder()?;
+        builder.set_version(2)?;
+        builder.set_serial_number(&*Self::serial()?)?;
+        builder.set_subject_name(&*Self::name(host)?)?;
+        builder.set_issuer_name(self.cert.subject_name())?;
+        builder.set_pubkey(&key)?;
+        builder.set_not_before(&*Asn1Time::days_from_now(0)?)?;
+        builder.set_not_after(&*Asn1Time::days_from_now(Self::VALIDITY_DAYS)?)?;
+        let mut san = SubjectAlternativeName::new();
+        if host.parse::<IpAddr>().is_ok() {
+            san.ip(host);
+        } else {
+            san.dns(host);
+        }
+        let san = san.build(&builder.x509v3_context(Some(&self.cert), None))?;
+        builder.append_extension(san)?;
+        builder.append_extension(BasicConstraints::new().build()?)?;
+        builder.append_extension(KeyUsage::new().critical().digital_signature().key_encipherment().build()?)?;
+        builder.append_extension(ExtendedKeyUsage::new().server_auth().build()?)?;
+        builder.sign(&self.key, MessageDigest::sha256())?;
+        Ok((builder.build(), key))
+    }
+
+    fn new_key() -> anyhow::Result<PKey<Private>> {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?;
+        Ok(PKey::from_ec_key(EcKey::generate(&group)?)?)
+    }
+
+    fn name(common_name: &str) -> anyhow::Result<X509Name> {
+        let mut name = X509NameBuilder::new()?;
+        name.append_entry_by_nid(Nid::COMMONNAME, common_name)?;
+        Ok(name.build())
+    }
+
+    fn serial() -> anyhow::Result<openssl::asn1::Asn1Integer> {
+        let mut serial = BigNum::new()?;
+        serial.rand(128, MsbOption::MAYBE_ZERO, false)?;
+        Ok(serial.to_asn1_integer()?)
+    }
+}
+
+#[cfg(test)]
+mod tls_tests {
+    use openssl::{stack::Stack, x509::{store::X509StoreBuilder, X509StoreContext}};
+
+    use super::*;
+
+    fn verify(ca: &RecordCa, cert: &X509) -> bool {
+        let mut store = X509StoreBuilder::new().unwrap();
+        store.add_cert(ca.cert.clone()).unwrap();
+        let store = store.build();
+        let mut ctx = X509StoreContext::new().unwrap();
+        ctx.init(&store, cert, &Stack::new().unwrap(), |c| c.verify_cert()).unwrap()
+    }
+
+    #[test]
+    fn should_issue_certificate_signed_by_ca() {
+        let ca = RecordCa::generate().unwrap();
+        let (cert, _) = ca.issue("example.com").unwrap();
+        assert!(verify(&ca, &cert));
+        let other = RecordCa::generate().unwrap();
+        assert!(!verify(&other, &cert));
+    }
+
+    #[test]
+    fn should_issue_certificate_for_host() {
+        let ca = RecordCa::generate().unwrap();
+        let (cert, _) = ca.issue("example.com").unwrap();
+        let san = cert.subject_alt_names().unwrap();
+        assert_eq!(san.iter().filter_map(|n| n.dnsname()).collect::<Vec<_>>(), vec!["example.com"]);
+        let (cert, _) = ca.issue("127.0.0.1").unwrap();
+        let san = cert.subject_alt_names().unwrap();
+        assert_eq!(san.iter().filter_map(|n| n.ipaddress()).collect::<Vec<_>>(), vec![&[127, 0, 0, 1][..]]);
+    }
+
+    #[test]
+    fn should_load_pem_ca() {
+        let ca = RecordCa::generate().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let (cert, key) = (dir.path().join("ca.pem"), dir.path().join("ca.key"));
+        std::fs::write(&cert, ca.cert_pem()).unwrap();
+        std::fs::write(&key, ca.key.private_key_to_pem_pkcs8().unwrap()).unwrap();
+        let loaded = RecordCa::load(&cert, &key).unwrap();
+        assert_eq!(loaded.cert_pem(), ca.cert_pem());
+        assert!(loaded.acceptor("localhost").is_ok());
+    }
+}
@@ -3,8 +3,6 @@ use std::str::FromStr;
 use http_types::{
     Body as HttpBody,
     headers::HeaderName as HttpHeaderName,
-    headers::HeaderValue as HttpHeaderValue,
-    headers::HeaderValues as HttpHeaderValues,
     Method as HttpMethod,
     Request as HttpRequest,
     Response as HttpResponse,
@@ -15,7 +13,7 @@ use warp::{
     hyper::body::Bytes,
 };
 
-use super::super::{RecordedExchange, RecordedRequest, RecordedResponse};
+use super::super::{header_values, RecordedExchange, RecordedRequest, RecordedResponse};
 
 pub struct WarpRequest {
     pub method: WarpMethod,
@@ -48,10 +46,8 @@ impl From<WarpRequest> for RecordedRequest {
         let mut http_req = HttpRequest::new(method, url.as_str());
         req.headers.iter()
             .filter_map(|(k, v)| {
+                let v = v.to_str().ok().map(|v| header_values(k.as_str(), v));
                 let k = HttpHeaderName::from_str(k.as_str()).ok();
-                let v = v.to_str().ok()
-                    .map(|it| it.split(',').map(|s| s.trim()).filter_map(|i| HttpHeaderValue::from_str(i).ok()))
-                    .map(HttpHeaderValues::from_iter);
                 k.zip(v)
             })
             .for_each(|(k, v)| http_req.append_header(k, &v));
@@ -69,10 +65,8 @@ impl From<WarpResponse> for RecordedResponse {
         http_resp.set_body(resp.0.body().as_ref());
         resp.0.headers().iter()
             .filter_map(|(k, v)| {
+                let v = v.to_str().ok().map(|v| header_values(k.as_str(), v));
                 let k = HttpHeaderName::from_str(k.as_str()).ok();
-                let v = v.to_str().ok()
-                    .map(|it| it.split(',').map(|s| s.trim()).filter_map(|i| HttpHeaderValue::from_str(i).ok()))
-                    .map(HttpHeaderValues::from_iter);
                 k.zip(v)
             })
             .for_each(|(k, v)| { http_resp.insert_header(k, &v); });
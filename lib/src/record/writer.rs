@@ -1,15 +1,22 @@
 use std::{
-    collections::hash_map::DefaultHasher,
-    fs::{create_dir_all, File},
-    hash::{Hash, Hasher},
-    path::PathBuf,
+    fs::{create_dir_all, File, write},
+    path::{Path, PathBuf},
     str::FromStr,
+    sync::{Mutex, PoisonError},
 };
 
 use http_types::Url;
 use log::info;
+use serde_json::{Map, Value};
 
-use crate::{model::JsonStub, server::stub_finder::StubFinder};
+use crate::{gen::hash::FnvHasher, model::{JsonStub, response::body_file::BodyFile, scenario::{Scenarios, ScenarioStub}}, server::stub_finder::StubFinder};
+
+use super::{config::{Dedupe, RecordConfig, StubNaming}, manifest::{Manifest, ManifestEntry}};
+
+lazy_static! {
+    /// Recordings happen concurrently e.g. in tests while the manifest is shared by a directory
+    static ref MANIFEST_LOCK: Mutex<()> = Mutex::new(());
+}
 
 pub(crate) struct StubWriter {
     pub(crate) stub: JsonStub,
@@ -18,32 +25,152 @@ pub(crate) struct StubWriter {
 impl StubWriter {
     const RECORDED_TEST_DIR: &'static str = "stubs";
 
-    pub(crate) fn write(&self, host: &str, output: Option<&PathBuf>) -> anyhow::Result<PathBuf> {
-        let output = self.output_and_create(host, output);
-        let file = output.join(self.stub_name());
-        File::create(&file)
-            .map_err(anyhow::Error::msg)
-            .and_then(|f| serde_json::to_writer_pretty(&f, &self.stub).map_err(anyhow::Error::msg).map(|_| file))
-            .map(|p| {
-                info!("Recorded stub in {:?}", p);
-                p
-            })
-            .map_err(anyhow::Error::msg)
+    pub(crate) fn write(mut self, host: &str, cfg: &RecordConfig) -> anyhow::Result<PathBuf> {
+        let output = self.output_and_create(host, cfg.output.as_ref());
+        let _lock = MANIFEST_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut manifest = Manifest::read(&output);
+        let entry = self.manifest_entry(&manifest, cfg.naming);
+        let previous = manifest.last_recording(&entry.hash).cloned();
+        let entry = match (previous, cfg.dedupe) {
+            (Some(previous), dedupe) if cfg.scenarios || dedupe == Dedupe::KeepAll => {
+                if previous.response == entry.response && dedupe != Dedupe::KeepAll {
+                    info!("Same response already recorded in '{}'", Self::file_name(&previous));
+                    return Ok(output.join(Self::file_name(&previous)));
+                }
+                let entry = ManifestEntry { occurrence: previous.occurrence + 1, ..entry };
+                Self::chain(&output.join(Self::file_name(&previous)), &entry)?;
+                self.stub.scenario = ScenarioStub {
+                    scenario_name: Some(entry.name.to_string()),
+                    required_scenario_state: Some(Self::scenario_state(entry.occurrence)),
+                    new_scenario_state: None,
+                };
+                entry
+            }
+            (Some(_), Dedupe::KeepFirst) if output.join(format!("{}.json", entry.name)).exists() => {
+                info!("Request already recorded in '{}.json'", entry.name);
+                return Ok(output.join(format!("{}.json", entry.name)));
+            }
+            _ => entry,
+        };
+        let name = Self::file_name(&entry);
+        if let Some(threshold) = cfg.body_file_threshold {
+            self.extract_body_file(&output, &name, threshold)?;
+        }
+        let file = output.join(&name);
+        serde_json::to_writer_pretty(&File::create(&file)?, &self.stub)?;
+        manifest.stubs.insert(name, entry);
+        manifest.write(&output)?;
+        info!("Recorded stub in {:?}", file);
+        Ok(file)
+    }
+
+    fn manifest_entry(&self, manifest: &Manifest, naming: StubNaming) -> ManifestEntry {
+        let request = &self.stub.request;
+        let method = format!("{:?}", request.method.0).to_uppercase();
+        let url = self.path()
+            .or(request.url.url_path_pattern.as_deref())
+            .map(str::to_string)
+            .unwrap_or_else(|| String::from("/"));
+        let hash = self.request_hash();
+        let name = match naming {
+            StubNaming::Hash => format!("{}{}", self.base_path().unwrap_or_default(), hash),
+            StubNaming::Sequence => manifest.recordings(&hash).next()
+                .map(|it| it.name.to_string())
+                .unwrap_or_else(|| {
+                    let n = manifest.distinct_requests(&method, &url) + 1;
+                    format!("{}-{}{}", method.to_lowercase(), self.base_path().unwrap_or_default(), n)
+                }),
+        };
+        let response = FnvHasher::hash(&serde_json::to_vec(&self.stub.response).unwrap_or_default());
+        ManifestEntry { method, url, hash, response, name, occurrence: 1 }
+    }
+
+    fn file_name(entry: &ManifestEntry) -> String {
+        if entry.occurrence > 1 {
+            format!("{}-{}.json", entry.name, entry.occurrence)
+        } else {
+            format!("{}.json", entry.name)
+        }
+    }
+
+    /// Scenario state in which the n-th recording of a request gets replayed
+    fn scenario_state(occurrence: u32) -> String {
+        if occurrence > 1 {
+            occurrence.to_string()
+        } else {
+            Scenarios::STARTED.to_string()
+        }
+    }
+
+    /// Makes the previous recording of a request move its scenario to the state of the next one.
+    /// The file is edited as raw json so that it is rewritten as is
+    fn chain(previous: &Path, next: &ManifestEntry) -> anyhow::Result<()> {
+        let mut stub: Map<String, Value> = serde_json::from_reader(File::open(previous)?)?;
+        stub.insert(String::from("scenarioName"), Value::String(next.name.to_string()));
+        stub.insert(String::from("requiredScenarioState"), Value::String(Self::scenario_state(next.occurrence - 1)));
+        stub.insert(String::from("newScenarioState"), Value::String(Self::scenario_state(next.occurrence)));
+        serde_json::to_writer_pretty(&File::create(previous)?, &stub)?;
+        Ok(())
     }
 
-    pub(crate) fn stub_name(&self) -> String {
-        let mut hasher = DefaultHasher::new();
-        self.stub.hash(&mut hasher);
-        format!("{}{}.json", self.base_path().unwrap_or_default(), hasher.finish())
+    fn request_hash(&self) -> String {
+        FnvHasher::hash(&serde_json::to_vec(&self.stub.request).unwrap_or_default())
+    }
+
+    /// Moves a large response body in a file of the '__files' directory, referenced by 'bodyFileName'
+    /// relative to it like Wiremock does
+    fn extract_body_file(&mut self, output: &Path, name: &str, threshold: usize) -> anyhow::Result<()> {
+        let body = match self.stub.response.as_mut() {
+            Some(response) => &mut response.body,
+            None => return Ok(()),
+        };
+        let (content, extension) = if let Some(json) = body.json_body.as_ref() {
+            (serde_json::to_string_pretty(json)?, "json")
+        } else if let Some(text) = body.body.as_ref() {
+            (text.to_string(), "txt")
+        } else {
+            return Ok(());
+        };
+        if content.len() > threshold {
+            let dir = output.join(StubFinder::BODY_FILES_DIR);
+            create_dir_all(&dir)?;
+            let file_name = format!("{}.{}", name.trim_end_matches(".json"), extension);
+            write(dir.join(&file_name), &content)?;
+            body.body_file_name = Some(BodyFile::locate(&file_name, Some(&dir)));
+            body.json_body = None;
+            body.body = None;
+        }
+        Ok(())
+    }
+
+    /// Exact path, with 'url' its query is left out
+    fn path(&self) -> Option<&str> {
+        let url = &self.stub.request.url;
+        url.url_path.as_deref()
+            .or_else(|| url.url.as_deref().and_then(|it| it.split('?').next()))
+            .filter(|it| !it.is_empty() && *it != "/")
     }
 
     fn base_path(&self) -> Option<String> {
-        self.stub.request.url.url_path.as_ref()
+        self.path()
             .map(|it| it.strip_prefix('/').unwrap_or(it))
             .map(|it| it.replace('/', "-"))
+            .or_else(|| self.literal_path_pattern())
             .map(|it| format!("{}-", it))
     }
 
+    /// Keeps only plain segments of a generalized path e.g. '/users/[0-9]+/orders' becomes 'users-orders'
+    fn literal_path_pattern(&self) -> Option<String> {
+        self.stub.request.url.url_path_pattern.as_deref()
+            .map(|it| {
+                it.split('/')
+                    .filter(|s| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_'))
+                    .collect::<Vec<_>>()
+                    .join("-")
+            })
+            .filter(|it| !it.is_empty())
+    }
+
     fn output_and_create(&self, host: &str, output: Option<&PathBuf>) -> PathBuf {
         let output = output.map(|it| it.to_path_buf()).unwrap_or_else(Self::default_output).join(self.dir_name(host));
         if !output.exists() {
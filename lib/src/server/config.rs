@@ -1,5 +1,10 @@
-/// Stubr server configuration.
-#[derive(Default, Copy, Clone)]
+use std::path::PathBuf;
+
+use crate::model::response::template::helpers::{custom::{Helpers, StubrHelper}, jwt::JwtKey};
+
+/// Stubr server configuration: port, delays, response template helpers and where body files
+/// are looked up
+#[derive(Default, Clone)]
 pub struct Config {
     /// HTTP port the mock server will be listening on
     pub port: Option<u16>,
@@ -12,4 +17,20 @@ pub struct Config {
     /// Contrary to [global_delay], this one is added to any locally defined delay.
     /// Use it to simulate network delays.
     pub latency: Option<u64>,
+    /// User-defined handlebars helpers available in response templates
+    pub helpers: Helpers,
+    /// Signs tokens of the `jwt` response template helper.
+    /// When none, every server generates its own ES256 key
+    pub jwt_key: Option<JwtKey>,
+    /// Wiremock's root directory. A 'bodyFileName' is also looked up in its '__files' folder.
+    /// When none, the parent of the 'mappings' folder holding a stub, if any
+    pub root_dir: Option<PathBuf>,
+}
+
+impl Config {
+    /// Registers a handlebars helper templates can call with `name`
+    pub fn helper(mut self, name: &str, helper: impl StubrHelper + 'static) -> Self {
+        self.helpers.register(name, helper);
+        self
+    }
 }
\ No newline at end of file
@@ -9,7 +9,9 @@ use wiremock::MockServer;
 use any_stub::AnyStubs;
 use stub_finder::StubFinder;
 
-use crate::{cloud::probe::HttpProbe, Config, model::JsonStub};
+use crate::{cloud::probe::HttpProbe, Config, model::{JsonStub, response::template::Templates, scenario::Scenarios, sequence::Sequences}};
+#[cfg(feature = "broker")]
+use crate::broker::{AnyBroker, Broker, Selector};
 #[cfg(feature = "record-standalone")]
 use crate::record::{config::RecordConfig, standalone::StubrRecord};
 
@@ -21,6 +23,10 @@ pub mod config;
 /// Delegates runtime to wiremock-rs.
 pub struct Stubr {
     instance: MockServer,
+    scenarios: Scenarios,
+    sequences: Sequences,
+    /// Not shared with other servers
+    templates: Templates,
 }
 
 impl Stubr {
@@ -52,13 +58,16 @@ impl Stubr {
     /// * `stubs` - folder or file containing the stubs
     /// * `config` - global server configuration
     pub async fn start_with<T>(stubs: T, config: Config) -> Self where T: Into<AnyStubs> {
+        let jwt_key = config.jwt_key.clone().unwrap_or_default();
+        let templates = Templates::with_jwt_key(&config.helpers, jwt_key);
         let server = if let Some(p) = config.port {
-            Self::start_on(p).await
+            Self::start_on(p, templates).await
         } else {
-            Self::start_on_random_port().await
+            Self::start_on_random_port(templates).await
         };
         server.register_stubs(stubs.into(), config);
         server.register_cloud_features().await;
+        server.register_jwks().await;
         server
     }
 
@@ -74,12 +83,20 @@ impl Stubr {
     /// Proxies requests and converts them into stubs
     #[cfg(feature = "record-standalone")]
     pub fn record() -> StubrRecord {
-        StubrRecord::record(RecordConfig::default())
+        Self::record_with(RecordConfig::default())
     }
 
-    /// Proxies requests and converts them into stubs
+    /// Proxies requests and converts them into stubs.
+    /// Use this in a test context, it panics when the recorder cannot start
     #[cfg(feature = "record-standalone")]
     pub fn record_with(config: RecordConfig) -> StubrRecord {
+        Self::try_record_with(config).unwrap_or_else(|e| panic!("Could not start recorder because {:#}", e))
+    }
+
+    /// Proxies requests and converts them into stubs.
+    /// Fails when the recorder cannot start e.g. with an invalid custom certificate authority
+    #[cfg(feature = "record-standalone")]
+    pub fn try_record_with(config: RecordConfig) -> anyhow::Result<StubrRecord> {
         StubrRecord::record(config)
     }
 
@@ -90,11 +107,40 @@ impl Stubr {
     }
 
     /// Runs stubs of a remote producer app.
+    /// With the `broker` feature, they are fetched from `STUBR_BROKER` when set,
+    /// picking the latest version tagged `STUBR_BROKER_TAG` if any.
     /// * `name` - producer name
     pub async fn app_with(name: &str, config: Config) -> Self {
+        #[cfg(feature = "broker")]
+        if let Some(broker) = AnyBroker::from_env() {
+            return Self::app_from_with(&broker, name, &Selector::from_env(), config).await;
+        }
         Self::start_with(StubFinder::find_app(name), config).await
     }
 
+    /// Runs stubs of a producer app published in a broker.
+    /// * `broker` - where stubs were published
+    /// * `name` - producer name
+    /// * `selector` - version to fetch
+    #[cfg(feature = "broker")]
+    pub async fn app_from(broker: &impl Broker, name: &str, selector: &Selector) -> Self {
+        Self::app_from_with(broker, name, selector, Config::default()).await
+    }
+
+    /// Runs stubs of a producer app published in a broker.
+    /// * `broker` - where stubs were published
+    /// * `name` - producer name
+    /// * `selector` - version to fetch
+    /// * `config` - its `root_dir` is replaced by the directory where the stubs are written
+    #[cfg(feature = "broker")]
+    pub async fn app_from_with(broker: &impl Broker, name: &str, selector: &Selector, config: Config) -> Self {
+        let root = broker.fetch(name, selector).await
+            .and_then(|contract| contract.materialize())
+            .unwrap_or_else(|e| panic!("Could not fetch app '{}' from broker because {:#}", name, e));
+        let config = Config { root_dir: Some(root.clone()), ..config };
+        Self::start_with(root, config).await
+    }
+
     /// Runs stubs of a remote producer app.
     /// * `name` - producer name
     pub fn app_blocking(name: &str) -> Self {
@@ -112,7 +158,7 @@ impl Stubr {
     }
 
     pub async fn apps_with(names: &[&str], config: Config) -> Vec<Self> {
-        join_all(names.iter().map(|n| async move { Self::app_with(n, config).await })).await
+        join_all(names.iter().map(|n| Self::app_with(n, config.clone()))).await
     }
 
     pub fn apps_blocking(names: &[&str]) -> Vec<Self> {
@@ -133,27 +179,50 @@ impl Stubr {
         format!("{}{}", self.uri(), path)
     }
 
-    async fn start_on(port: u16) -> Self {
+    /// Moves every stateful stub's scenario back to its initial state
+    pub fn reset_scenarios(&self) {
+        self.scenarios.reset()
+    }
+
+    /// Starts every stub's response sequence over
+    pub fn reset_sequences(&self) {
+        self.sequences.reset()
+    }
+
+    async fn start_on(port: u16, templates: Templates) -> Self {
         if let Ok(listener) = TcpListener::bind(format!("{}:{}", Self::HOST, port)) {
             Self {
-                instance: MockServer::builder().disable_request_recording().listener(listener).start().await
+                instance: MockServer::builder().disable_request_recording().listener(listener).start().await,
+                scenarios: Scenarios::default(),
+                sequences: Sequences::default(),
+                templates,
             }
         } else {
-            Self::start_on_random_port().await
+            Self::start_on_random_port(templates).await
         }
     }
 
-    async fn start_on_random_port() -> Self {
-        Self { instance: MockServer::builder().disable_request_recording().start().await }
+    async fn start_on_random_port(templates: Templates) -> Self {
+        Self {
+            instance: MockServer::builder().disable_request_recording().start().await,
+            scenarios: Scenarios::default(),
+            sequences: Sequences::default(),
+            templates,
+        }
     }
 
     fn register_stubs(&self, stub_folder: AnyStubs, config: Config) {
         stub_folder.0.iter()
             .flat_map(|folder| self.find_all_mocks(folder).map(move |(s, p)| (s, p, folder)))
             .sorted_by(|(a, _, _), (b, _, _)| a.priority.cmp(&b.priority))
-            .filter_map(|(stub, path, folder)| stub.try_creating_from(&config).ok().map(|mock| (mock, path, folder)))
-            .for_each(|(mock, file, folder)| {
-                block_on(async move { self.instance.register(mock).await; });
+            .map(|(stub, path, folder)| (stub.with_files_dir(StubFinder::body_files_dir(&path, config.root_dir.as_deref()).as_deref()), path, folder))
+            .filter_map(|(stub, path, folder)| stub.try_creating_from(&config, &self.scenarios, &self.sequences, &self.templates).ok().map(|mocks| (mocks, path, folder)))
+            .for_each(|(mocks, file, folder)| {
+                block_on(async move {
+                    for mock in mocks {
+                        self.instance.register(mock).await;
+                    }
+                });
                 if config.verbose.unwrap_or_default() {
                     let maybe_file_name = file.strip_prefix(&folder).ok().and_then(|file| file.to_str());
                     if let Some(file_name) = maybe_file_name {
@@ -172,6 +241,10 @@ impl Stubr {
     async fn register_cloud_features(&self) {
         self.instance.register(HttpProbe::health_probe()).await;
     }
+
+    async fn register_jwks(&self) {
+        self.instance.register(self.templates.jwt_key().jwks_mock()).await;
+    }
 }
 
 #[cfg(test)]
@@ -181,26 +254,26 @@ mod server_test {
     #[async_std::test]
     async fn should_find_all_mocks_from_dir() {
         let from = PathBuf::from("tests/stubs/server");
-        assert!(Stubr::start_on_random_port().await.find_all_mocks(&from).count().gt(&2));
+        assert!(Stubr::start_on_random_port(Templates::default()).await.find_all_mocks(&from).count().gt(&2));
     }
 
     #[async_std::test]
     async fn should_find_all_mocks_from_single_file() {
         let from = PathBuf::from("tests/stubs/server/valid.json");
-        assert_eq!(Stubr::start_on_random_port().await.find_all_mocks(&from).count(), 1);
+        assert_eq!(Stubr::start_on_random_port(Templates::default()).await.find_all_mocks(&from).count(), 1);
     }
 
     #[async_std::test]
     async fn should_not_find_any_mock_when_none_valid() {
         let from = PathBuf::from("tests/stubs/server/invalid");
-        assert_eq!(Stubr::start_on_random_port().await.find_all_mocks(&from).count(), 0);
+        assert_eq!(Stubr::start_on_random_port(Templates::default()).await.find_all_mocks(&from).count(), 0);
     }
 
     #[async_std::test]
     async fn should_not_find_any_mock_when_path_does_not_exist() {
         let from = PathBuf::from("tests/stubs/server/unknown");
-        assert_eq!(Stubr::start_on_random_port().await.find_all_mocks(&from).count(), 0);
+        assert_eq!(Stubr::start_on_random_port(Templates::default()).await.find_all_mocks(&from).count(), 0);
         let from = PathBuf::from("tests/stubs/server/unknown.json");
-        assert_eq!(Stubr::start_on_random_port().await.find_all_mocks(&from).count(), 0);
+        assert_eq!(Stubr::start_on_random_port(Templates::default()).await.find_all_mocks(&from).count(), 0);
     }
 }
\ No newline at end of file
@@ -8,6 +8,9 @@ pub struct StubFinder;
 impl StubFinder {
     const LOCAL_DIR: &'static str = "stubr";
     const JSON_EXTENSION: &'static str = "json";
+    /// Holds response bodies referenced by 'bodyFileName', not stubs
+    pub(crate) const BODY_FILES_DIR: &'static str = "__files";
+    pub(crate) const MAPPINGS_DIR: &'static str = "mappings";
 
     #[cfg(target_os = "macos")]
     const LIB_PATH_ENV_VAR: &'static str = "DYLD_FALLBACK_LIBRARY_PATH";
@@ -29,6 +32,10 @@ impl StubFinder {
         if let Ok(mut from) = read_dir(from) {
             while let Some(Ok(entry)) = from.next() {
                 let path = entry.path();
+                let is_hidden = entry.file_name().to_str().map(|n| n.starts_with('.')).unwrap_or_default();
+                if is_hidden || entry.file_name() == Self::BODY_FILES_DIR {
+                    continue;
+                }
                 if path.is_file() && path.extension().and_then(OsStr::to_str) == Some(Self::JSON_EXTENSION) {
                     stubs.push(path)
                 } else if path.is_dir() {
@@ -40,6 +47,13 @@ impl StubFinder {
         stubs
     }
 
+    /// Wiremock's '__files' folder, either under `root_dir` or next to the 'mappings' folder holding `stub`
+    pub fn body_files_dir(stub: &Path, root_dir: Option<&Path>) -> Option<PathBuf> {
+        root_dir
+            .or_else(|| stub.ancestors().find(|p| p.file_name() == Some(OsStr::new(Self::MAPPINGS_DIR))).and_then(Path::parent))
+            .map(|root| root.join(Self::BODY_FILES_DIR))
+    }
+
     pub fn find_app(name: &str) -> PathBuf {
         env::var("CARGO_PKG_NAME").ok()
             .map(|pkg| Self::output_dir().join(Self::LOCAL_DIR).join(pkg).join(name))
@@ -124,4 +138,23 @@ mod stub_finder_test {
         assert!(file_names.contains(&"post.json"));
         assert!(file_names.contains(&"delete.json"));
     }
+
+    #[test]
+    fn should_skip_body_files_and_hidden_files() {
+        let from = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(from.path().join("__files")).unwrap();
+        std::fs::write(from.path().join("__files").join("body.json"), "{}").unwrap();
+        std::fs::write(from.path().join(".manifest.json"), "{}").unwrap();
+        std::fs::write(from.path().join("stub.json"), "{}").unwrap();
+        let files = StubFinder::find_all_stubs(from.path()).collect::<Vec<PathBuf>>();
+        assert_eq!(files, vec![from.path().join("stub.json")]);
+    }
+
+    #[test]
+    fn should_locate_body_files_dir() {
+        let stub = PathBuf::from("root/mappings/pets/get.json");
+        assert_eq!(StubFinder::body_files_dir(&stub, None), Some(PathBuf::from("root/__files")));
+        assert_eq!(StubFinder::body_files_dir(&stub, Some(&PathBuf::from("other"))), Some(PathBuf::from("other/__files")));
+        assert_eq!(StubFinder::body_files_dir(&PathBuf::from("stubs/get.json"), None), None);
+    }
 }
\ No newline at end of file
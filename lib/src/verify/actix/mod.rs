@@ -3,13 +3,18 @@ use std::fmt::Debug;
 use actix_http::Request as ActixRequest;
 use actix_service::{IntoServiceFactory, Service as ActixService, ServiceFactory};
 use actix_web::{dev::{AppConfig, ServiceResponse}, test::TestRequest};
+use anyhow::{anyhow, Context};
 use async_trait::async_trait;
 
+use crate::model::response::template::Templates;
+
 use super::{
     mapping::{req::StdRequest, resp::{RequestAndStub, StdResponse}},
+    report::VerifyReport,
     stub_finder::ProducerStubFinder,
     StubrVerify,
     VerifyExcept,
+    VerifyOptions,
 };
 
 mod req;
@@ -22,17 +27,36 @@ impl<A, T> StubrVerify<T> for A where
     T: ServiceFactory<ActixRequest, Config=AppConfig, Response=ServiceResponse>,
     <T as ServiceFactory<ActixRequest>>::InitError: Debug,
 {
-    async fn verify_except<N>(self, except: impl VerifyExcept<N> + 'async_trait) {
+    async fn verify_report_except_with<N>(self, except: impl VerifyExcept<N> + 'async_trait, options: VerifyOptions) -> VerifyReport {
+        let mut report = VerifyReport::default();
+        let templates = Templates::new(&options.helpers);
         let srv = self.into_factory();
         if let Ok(app) = srv.new_service(AppConfig::default()).await {
             for (stub, name) in ProducerStubFinder::find_stubs(except) {
-                let req = StdRequest::try_from(&stub).unwrap_or_else(|_| panic!("Could not verify '{:?}'. Invalid json stub.", name));
-                let test_req = TestRequest::from(&req).set_payload(Vec::<u8>::from(&stub.request)).to_request();
-                let resp: StdResponse = app.call(test_req).await
-                    .unwrap_or_else(|_| panic!("Failed verifying stub {:?}", name))
-                    .into();
-                RequestAndStub { req, stub: stub.response, name }.verify(resp);
+                let state = stub.state().map(str::to_string);
+                let mut samples = vec![];
+                for sample in options.generator.samples() {
+                    let result = async {
+                        options.states.setup(state.as_deref()).await?;
+                        let replayed = async {
+                            let response = stub.verified_response().cloned().context("stub has no response to verify")?;
+                            let (req, body) = sample.scope(|| {
+                                StdRequest::try_from(&stub).map(|req| (req, Vec::<u8>::from(&stub.request)))
+                            }).context("invalid json stub")?;
+                            let test_req = TestRequest::from(&req).set_payload(body).to_request();
+                            let resp: StdResponse = app.call(test_req).await
+                                .map_err(|_| anyhow!("application failed handling the request"))?
+                                .into();
+                            Ok(RequestAndStub { req, stub: response, name: name.clone() }.collect(resp, &templates))
+                        }.await;
+                        options.states.teardown(state.as_deref()).await;
+                        replayed
+                    }.await;
+                    samples.push((sample.seed, result));
+                }
+                report.add_samples(&name, samples);
             };
         }
+        report
     }
-}
\ No newline at end of file
+}
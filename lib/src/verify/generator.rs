@@ -0,0 +1,70 @@
+use crate::gen::regex::RegexGenConfig;
+
+/// Drives how requests are generated from stubs with `matches`, `urlPathPattern`,
+/// `matchesJsonPath`... during verification.
+/// Each stub is replayed once per sample, sample `i` being generated with `seed + i`.
+///
+/// ```
+/// # use stubr::GeneratorOptions;
+/// // replays each stub 20 times with a random seed, printed alongside mismatches
+/// let generator = GeneratorOptions::property(20);
+/// // reproduces a failure
+/// let generator = GeneratorOptions { seed: 1234, ..Default::default() };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GeneratorOptions {
+    /// Seed of the first sample
+    pub seed: u64,
+    /// Number of times each stub gets replayed with different generated values
+    pub samples: u32,
+    /// Upper bound for unbounded repetitions in regexes e.g. `*` or `+`
+    pub max_repeat: u32,
+}
+
+impl GeneratorOptions {
+    /// Property-based mode: replays each stub `samples` times from a random seed
+    pub fn property(samples: u32) -> Self {
+        Self { seed: rand::random(), samples, ..Default::default() }
+    }
+
+    /// Configuration of every sample to replay
+    pub(crate) fn samples(&self) -> impl Iterator<Item=RegexGenConfig> + '_ {
+        (0..self.samples.max(1) as u64)
+            .map(move |i| RegexGenConfig { seed: self.seed.wrapping_add(i), max_repeat: self.max_repeat })
+    }
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        Self { seed: RegexGenConfig::DEFAULT_SEED, samples: 1, max_repeat: RegexGenConfig::DEFAULT_MAX_REPEAT }
+    }
+}
+
+#[cfg(test)]
+mod generator_options_tests {
+    use super::*;
+
+    #[test]
+    fn default_should_have_a_single_sample() {
+        let samples = GeneratorOptions::default().samples().collect::<Vec<_>>();
+        assert_eq!(samples, vec![RegexGenConfig::default()]);
+    }
+
+    #[test]
+    fn should_increment_seed_for_each_sample() {
+        let options = GeneratorOptions { seed: 10, samples: 3, max_repeat: 2 };
+        let seeds = options.samples().map(|s| (s.seed, s.max_repeat)).collect::<Vec<_>>();
+        assert_eq!(seeds, vec![(10, 2), (11, 2), (12, 2)]);
+    }
+
+    #[test]
+    fn should_replay_at_least_once() {
+        assert_eq!(GeneratorOptions { samples: 0, ..Default::default() }.samples().count(), 1);
+    }
+
+    #[test]
+    fn should_wrap_seed() {
+        let options = GeneratorOptions { seed: u64::MAX, samples: 2, ..Default::default() };
+        assert_eq!(options.samples().map(|s| s.seed).collect::<Vec<_>>(), vec![u64::MAX, 0]);
+    }
+}
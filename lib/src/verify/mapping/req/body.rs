@@ -5,7 +5,7 @@ use json_value_merge::Merge;
 use serde_json::{json, Value};
 
 use crate::{
-    gen::string::StringRndGenerator,
+    gen::{regex::RegexRndGenerator, string::StringRndGenerator},
     model::request::{body::BodyPatternStub, RequestStub},
     verify::mapping::jsonpath::JsonGeneratorIterator,
 };
@@ -14,22 +14,26 @@ use super::super::jsonpath::JsonPathGenerator;
 
 impl From<&RequestStub> for Vec<u8> {
     fn from(stub: &RequestStub) -> Self {
-        stub.body_patterns.iter()
+        let full = stub.body_patterns.iter()
             .map(PartialBody::from)
-            .find(|it| !it.is_partial())
-            .and_then(PartialBody::to_bytes)
-            .unwrap_or_else(|| {
-                let merged = stub.body_patterns.iter()
-                    .map(PartialBody::from)
-                    .unique()
-                    .fold(Value::default(), |mut acc, it| {
-                        if let Some(value) = it.to_partial_value() {
-                            acc.merge(value);
-                        }
-                        acc
-                    });
-                serde_json::to_vec::<Value>(&merged).unwrap()
-            })
+            .find(|it| !it.is_partial());
+        // a json body ignoring extra elements can be completed by json path matchers
+        let (base, full) = match full {
+            Some(it) if it.extensible => (it.to_value(), None),
+            it => (None, it.and_then(PartialBody::to_bytes)),
+        };
+        full.unwrap_or_else(|| {
+            let merged = stub.body_patterns.iter()
+                .map(PartialBody::from)
+                .unique()
+                .fold(base.unwrap_or_default(), |mut acc, it| {
+                    if let Some(value) = it.to_partial_value() {
+                        acc.merge(value);
+                    }
+                    acc
+                });
+            serde_json::to_vec::<Value>(&merged).unwrap()
+        })
     }
 }
 
@@ -38,6 +42,7 @@ struct PartialBody {
     path: Option<String>,
     bytes: Option<Vec<u8>>,
     value: Option<Value>,
+    extensible: bool,
 }
 
 lazy_static! {
@@ -74,15 +79,21 @@ impl From<&BodyPatternStub> for PartialBody {
             base64::decode(binary_equal_to)
                 .unwrap_or_else(|_| panic!("'{}' must be Base64 encoded", binary_equal_to))
                 .into()
+        } else if let Some(equal_to) = stub.equal_to.as_ref().filter(|_| stub.expression.is_none()) {
+            equal_to.as_bytes().to_vec().into()
         } else if let Some(expression) = stub.expression.as_ref() {
             if let Some(equal_to_json) = stub.equal_to_json.as_ref() {
                 PartialBody { path: Some(expression.to_string()), value: Some(equal_to_json.to_owned()), ..Default::default() }
             } else if let Some(contains) = stub.contains.as_ref() {
                 let value = StringRndGenerator::generate_string_containing(contains.to_string());
                 PartialBody { path: Some(expression.to_string()), value: Some(Value::String(value)), ..Default::default() }
+            } else if let Some(regex) = stub.matches.as_ref() {
+                let value = RegexRndGenerator(regex).try_generate()
+                    .unwrap_or_else(|_| panic!("Failed generating a value matching '{}'", regex));
+                PartialBody { path: Some(expression.to_string()), value: Some(Value::String(value)), ..Default::default() }
             } else { PartialBody::default() }
         } else if let Some(eq) = stub.equal_to_json.as_ref() {
-            eq.to_owned().into()
+            PartialBody { extensible: stub.ignore_extra_elements.unwrap_or_default(), ..eq.to_owned().into() }
         } else if let Some(json_path) = stub.matches_json_path.as_ref() {
             PartialBody { path: Some(json_path.to_owned()), ..Default::default() }
         } else { PartialBody::default() }
@@ -146,6 +157,25 @@ mod verify_body_tests {
         }
     }
 
+    mod equal_to {
+        use super::*;
+
+        #[test]
+        fn equal_to_should_generate_strictly_equal() {
+            let stub = BodyPatternStub { equal_to: Some(String::from("<a>b</a>")), ..Default::default() };
+            assert_eq!(PartialBody::from(&stub).to_bytes().unwrap(), b"<a>b</a>".to_vec());
+        }
+
+        #[test]
+        fn request_should_have_text_body() {
+            let stub = RequestStub {
+                body_patterns: vec![BodyPatternStub { equal_to: Some(String::from("hello")), ..Default::default() }],
+                ..Default::default()
+            };
+            assert_eq!(Vec::<u8>::from(&stub), b"hello".to_vec());
+        }
+    }
+
     mod expression {
         use super::*;
 
@@ -176,6 +206,56 @@ mod verify_body_tests {
         }
     }
 
+    mod expression_matches {
+        use regex::Regex;
+
+        use super::*;
+
+        #[test]
+        fn expression_matches_should_generate_matching() {
+            let by_matches = BodyPatternStub {
+                expression: Some(String::from("$.id")),
+                matches: Some(String::from("[0-9]{4}")),
+                ..Default::default()
+            };
+            let stub = RequestStub { body_patterns: vec![by_matches], ..Default::default() };
+            let body = serde_json::from_slice::<Value>(&Vec::<u8>::from(&stub)).unwrap();
+            let id = body.as_object().unwrap().get("id").unwrap().as_str().unwrap();
+            assert!(Regex::new("^[0-9]{4}$").unwrap().is_match(id));
+        }
+
+        #[test]
+        fn should_complete_equal_to_json_ignoring_extra_elements() {
+            let by_eq = BodyPatternStub {
+                equal_to_json: Some(json!({"name": "john"})),
+                ignore_extra_elements: Some(true),
+                ..Default::default()
+            };
+            let by_matches = BodyPatternStub {
+                expression: Some(String::from("$.id")),
+                matches: Some(String::from("[0-9]{4}")),
+                ..Default::default()
+            };
+            let stub = RequestStub { body_patterns: vec![by_eq, by_matches], ..Default::default() };
+            let body = serde_json::from_slice::<Value>(&Vec::<u8>::from(&stub)).unwrap();
+            assert_eq!(body.get("name").unwrap(), &json!("john"));
+            assert_eq!(body.get("id").unwrap().as_str().unwrap().len(), 4);
+        }
+
+        #[test]
+        fn should_not_complete_strict_equal_to_json() {
+            let by_eq = BodyPatternStub { equal_to_json: Some(json!({"name": "john"})), ..Default::default() };
+            let by_matches = BodyPatternStub {
+                expression: Some(String::from("$.id")),
+                matches: Some(String::from("[0-9]{4}")),
+                ..Default::default()
+            };
+            let stub = RequestStub { body_patterns: vec![by_eq, by_matches], ..Default::default() };
+            let body = serde_json::from_slice::<Value>(&Vec::<u8>::from(&stub)).unwrap();
+            assert_eq!(body, json!({"name": "john"}));
+        }
+    }
+
     mod many_expression {
         use super::*;
 
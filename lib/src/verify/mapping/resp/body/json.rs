@@ -5,25 +5,70 @@ use crate::model::response::{ResponseStub, template::{data::RequestData, utils::
 
 use super::{
     json_templating::JsonBodyTemplatingVerifier,
-    super::{StdResponse, Verifier},
+    super::{StdResponse, super::super::report::Mismatches, Verifier},
 };
 
 pub struct JsonBodyVerifier;
 
+impl JsonBodyVerifier {
+    /// Compares field by field so that every difference gets reported with its json path
+    fn diff(expected: &Value, actual: &Value, name: &str, mismatches: &mut Mismatches) {
+        match (expected, actual) {
+            (Value::Object(e), Value::Object(a)) => {
+                for (k, ve) in e {
+                    mismatches.enter(k);
+                    if let Some(va) = a.get(k) {
+                        Self::diff(ve, va, name, mismatches);
+                    } else {
+                        let msg = format!("\nVerification failed for stub '{}'. Expected json response body field '{}' to be '{}' but it was absent",
+                                          name, mismatches.json_path(), ve);
+                        mismatches.push_body(ve, "", msg);
+                    }
+                    mismatches.exit();
+                }
+                for (k, va) in a.iter().filter(|(k, _)| !e.contains_key(*k)) {
+                    mismatches.enter(k);
+                    let msg = format!("\nVerification failed for stub '{}'. Unexpected json response body field '{}' with value '{}'",
+                                      name, mismatches.json_path(), va);
+                    mismatches.push_body("", va, msg);
+                    mismatches.exit();
+                }
+            }
+            (Value::Array(e), Value::Array(a)) if e.len() == a.len() => {
+                for (i, (ve, va)) in e.iter().zip(a.iter()).enumerate() {
+                    mismatches.enter_index(i);
+                    Self::diff(ve, va, name, mismatches);
+                    mismatches.exit();
+                }
+            }
+            (e, a) if e != a => {
+                let msg = format!("\nVerification failed for stub '{}'. Expected json response body field '{}' to be '{}' but was '{}'",
+                                  name, mismatches.json_path(), e, a);
+                mismatches.push_body(e, a, msg);
+            }
+            _ => {}
+        }
+    }
+}
+
 impl Verifier<'_> for JsonBodyVerifier {
-    fn verify(self, stub: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, resp: &'_ mut StdResponse) {
+    fn collect(self, stub: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, resp: &'_ mut StdResponse, mismatches: &mut Mismatches) {
         if let Some(expected) = stub.body.json_body.to_owned() {
             let actual = block_on(async move { resp.0.body_json::<Value>().await.ok() });
-            assert!(actual.is_some(), "\nVerification failed for stub '{}'. Expected json response body to be '{}' but none present", name, expected);
-            let actual = actual.unwrap();
-            if expected.has_template_expressions() {
-                if stub.requires_response_templating() {
-                    JsonBodyTemplatingVerifier { actual, expected }.verify(stub, name, req, &mut StdResponse::default());
-                } else {
-                    panic!("\nVerification failed for stub '{}'. No response template transformer present but template elements present in expected response json body '{}'", name, expected)
+            match actual {
+                None => {
+                    let msg = format!("\nVerification failed for stub '{}'. Expected json response body to be '{}' but none present", name, expected);
+                    mismatches.push_body(&expected, "", msg)
                 }
-            } else {
-                assert_eq!(actual, expected, "\nVerification failed for stub '{}'. Expected json response body to be '{}' but was '{}'", name, expected, actual);
+                Some(actual) if expected.has_template_expressions() => {
+                    if stub.requires_response_templating() {
+                        JsonBodyTemplatingVerifier { actual, expected }.collect(stub, name, req, &mut StdResponse::default(), mismatches);
+                    } else {
+                        let msg = format!("\nVerification failed for stub '{}'. No response template transformer present but template elements present in expected response json body '{}'", name, expected);
+                        mismatches.push_body(&expected, &actual, msg)
+                    }
+                }
+                Some(actual) => Self::diff(&expected, &actual, name, mismatches),
             }
         }
     }
@@ -48,7 +93,7 @@ mod json_body_verify_tests {
         JsonBodyVerifier.verify(&stub, "json", &RequestData::from(&mut req), &mut StdResponse(resp));
     }
 
-    #[should_panic(expected = "Verification failed for stub 'json'. Expected json response body to be '{\"name\":\"alice\"}' but was '{\"name\":\"bob\"}'")]
+    #[should_panic(expected = "Verification failed for stub 'json'. Expected json response body field '$.name' to be '\"alice\"' but was '\"bob\"'")]
     #[test]
     fn verify_should_fail_when_wrong_json_body_returned() {
         let body = json!({"name": "alice"});
@@ -97,4 +142,51 @@ mod json_body_verify_tests {
         resp.set_body(body);
         JsonBodyVerifier.verify(&stub, "json", &RequestData::from(&mut req), &mut StdResponse(resp));
     }
+
+    mod diff {
+        use super::*;
+
+        fn diff(expected: Value, actual: Value) -> Vec<(Option<String>, String, String)> {
+            let mut mismatches = Mismatches::new("json");
+            JsonBodyVerifier::diff(&expected, &actual, "json", &mut mismatches);
+            mismatches.into_vec().into_iter().map(|m| (m.path, m.expected, m.actual)).collect()
+        }
+
+        #[test]
+        fn should_not_report_when_equal() {
+            assert!(diff(json!({"a": [1, {"b": true}]}), json!({"a": [1, {"b": true}]})).is_empty());
+        }
+
+        #[test]
+        fn should_report_every_nested_field() {
+            let mismatches = diff(
+                json!({"name": "alice", "pets": [{"name": "rex", "age": 3}]}),
+                json!({"name": "bob", "pets": [{"name": "felix", "age": 3}]}),
+            );
+            assert_eq!(mismatches, vec![
+                (Some("$.name".to_string()), "\"alice\"".to_string(), "\"bob\"".to_string()),
+                (Some("$.pets[0].name".to_string()), "\"rex\"".to_string(), "\"felix\"".to_string()),
+            ]);
+        }
+
+        #[test]
+        fn should_report_absent_and_unexpected_fields() {
+            let mismatches = diff(json!({"a": 1}), json!({"b": 2}));
+            assert_eq!(mismatches, vec![
+                (Some("$.a".to_string()), "1".to_string(), "".to_string()),
+                (Some("$.b".to_string()), "".to_string(), "2".to_string()),
+            ]);
+        }
+
+        #[test]
+        fn should_report_arrays_of_different_length_as_a_whole() {
+            let mismatches = diff(json!({"a": [1, 2]}), json!({"a": [1]}));
+            assert_eq!(mismatches, vec![(Some("$.a".to_string()), "[1,2]".to_string(), "[1]".to_string())]);
+        }
+
+        #[test]
+        fn should_report_root() {
+            assert_eq!(diff(json!(1), json!("1")), vec![(Some("$".to_string()), "1".to_string(), "\"1\"".to_string())]);
+        }
+    }
 }
@@ -2,7 +2,7 @@ use serde_json::Value;
 
 use crate::model::response::{ResponseStub, template::data::RequestData};
 
-use super::super::{StdResponse, Verifier};
+use super::super::{StdResponse, super::super::report::Mismatches, Verifier};
 
 mod object;
 mod string;
@@ -13,21 +13,26 @@ pub struct JsonBodyTemplatingVerifier {
 }
 
 impl Verifier<'_> for JsonBodyTemplatingVerifier {
-    fn verify(self, stub: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, resp: &'_ mut StdResponse) {
+    fn collect(self, stub: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, resp: &'_ mut StdResponse, mismatches: &mut Mismatches) {
         if let Ok(object_verifier) = object::JsonObjectVerifier::try_from(&self) {
-            object_verifier.verify(stub, name, req, resp)
+            object_verifier.collect(stub, name, req, resp, mismatches)
         } else if let Some((actual, expected)) = self.actual.as_array().zip(self.expected.as_array()) {
-            assert!(actual.len().ge(&expected.len()),
-                    "Verification failed for stub '{}'. Expected {} elements in json response body but {} found",
-                    name, expected.len(), actual.len());
-            actual.iter().zip(expected.iter())
-                .for_each(|(a, e)| Self { actual: a.clone(), expected: e.clone() }.verify(stub, name, req, resp))
+            if actual.len() < expected.len() {
+                let msg = format!("Verification failed for stub '{}'. Expected {} elements in json response body but {} found",
+                                  name, expected.len(), actual.len());
+                mismatches.push_body(expected.len(), actual.len(), msg);
+            }
+            for (i, (a, e)) in actual.iter().zip(expected.iter()).enumerate() {
+                mismatches.enter_index(i);
+                Self { actual: a.clone(), expected: e.clone() }.collect(stub, name, req, resp, mismatches);
+                mismatches.exit();
+            }
         } else if let Ok(str_verifier) = string::JsonStrVerifier::try_from(&self) {
-            str_verifier.verify(stub, name, req, resp)
-        } else {
-            assert_eq!(self.actual, self.expected,
-                       "Verification failed for stub '{}'. Expected json response body to be '{}' but was '{}'",
-                       name, self.expected, self.actual)
+            str_verifier.collect(stub, name, req, resp, mismatches)
+        } else if self.actual != self.expected {
+            let msg = format!("Verification failed for stub '{}'. Expected json response body to be '{}' but was '{}'",
+                              name, self.expected, self.actual);
+            mismatches.push_body(&self.expected, &self.actual, msg)
         }
     }
 }
@@ -76,7 +81,7 @@ mod json_body_verify_tests {
             verify(
                 "json",
                 json!(["alice"]),
-                json!(["{{jsonPath request.body '$.name'}}"]),
+                json!(["{{jsonPath request.body '$[0]'}}"]),
             )
         }
 
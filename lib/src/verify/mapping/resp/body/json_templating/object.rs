@@ -4,17 +4,12 @@ use serde_json::{Map, Value};
 
 use crate::model::response::{
     ResponseStub,
-    template::{
-        data::{HandlebarsData, RequestData},
-        HandlebarTemplatable,
-        utils::TemplateExt,
-        verify::Predictable,
-    },
+    template::{data::{HandlebarsData, RequestData}, utils::TemplateExt},
 };
 
 use super::{
     JsonBodyTemplatingVerifier,
-    super::super::{StdResponse, Verifier},
+    super::{is_predictable, render_verified, super::{StdResponse, super::super::report::Mismatches, Verifier}},
 };
 
 pub struct JsonObjectVerifier<'a> {
@@ -60,39 +55,40 @@ impl<'a> TryFrom<&'a JsonBodyTemplatingVerifier> for JsonObjectVerifier<'a> {
 }
 
 impl Verifier<'_> for JsonObjectVerifier<'_> {
-    fn verify(self, stub: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, resp: &'_ mut StdResponse) {
+    fn collect(self, stub: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, resp: &'_ mut StdResponse, mismatches: &mut Mismatches) {
         let key_diff = self.expected.keys().filter(|k| !self.actual.keys().contains(k)).collect_vec();
-        assert!(key_diff.is_empty(),
-                "Verification failed for stub '{}'. Expected json fields '{:?}' were absent from response body",
-                name,
-                self.expected.iter()
-                    .filter(|(k, _)| key_diff.contains(k))
-                    .map(|(k, v)| (k, v.as_str().unwrap_or_default()))
-                    .collect_vec()
-        );
-        let actual = self.actual.iter()
-            .filter(|(k, _)| self.expected.keys().contains(k))
-            .sorted_by_key(|(k, _)| k.as_str());
-        actual.zip(self.expected.iter().sorted_by_key(|(k, _)| k.as_str()))
-            .for_each(|((_, va), (ke, ve))| {
-                if let Some(expected) = ve.as_str().filter(|v| v.has_template_expressions()) {
-                    let response = self.to_bytes(va);
-                    let data = HandlebarsData {
-                        request: req,
-                        response: response.as_deref(),
-                        is_verify: true,
-                        stub_name: Some(name),
-                    };
-                    stub.body.register(expected, &expected);
-                    let render = stub.body.render(expected, &data);
-                    if expected.is_predictable() {
-                        assert_eq!(va, &self.cast_to_value(&render),
-                                   "Verification failed for stub '{}'. Expected json response body for field '{}' to be '{}' but was '{}'",
-                                   name, ke, render, va)
+        if !key_diff.is_empty() {
+            let absent = self.expected.iter()
+                .filter(|(k, _)| key_diff.contains(k))
+                .map(|(k, v)| (k, v.as_str().unwrap_or_default()))
+                .collect_vec();
+            let msg = format!("Verification failed for stub '{}'. Expected json fields '{:?}' were absent from response body", name, absent);
+            mismatches.push_body(key_diff.iter().join(", "), "", msg);
+        }
+        let fields = self.expected.iter()
+            .sorted_by_key(|(k, _)| k.as_str())
+            .filter_map(|(ke, ve)| self.actual.get(ke).map(|va| (ke, ve, va)));
+        for (ke, ve, va) in fields {
+            mismatches.enter(ke);
+            if let Some(expected) = ve.as_str().filter(|v| v.has_template_expressions()) {
+                let response = self.to_bytes(va);
+                let data = HandlebarsData {
+                    request: req,
+                    response: response.as_deref(),
+                    is_verify: true,
+                    stub_name: Some(name),
+                };
+                if let Some(render) = render_verified(expected, &data, va, mismatches) {
+                    if is_predictable(expected) && va != &self.cast_to_value(&render) {
+                        let msg = format!("Verification failed for stub '{}'. Expected json response body for field '{}' to be '{}' but was '{}'",
+                                          name, ke, render, va);
+                        mismatches.push_body(&render, va, msg);
                     }
-                } else {
-                    JsonBodyTemplatingVerifier { actual: va.clone(), expected: ve.clone() }.verify(stub, name, req, resp)
                 }
-            })
+            } else {
+                JsonBodyTemplatingVerifier { actual: va.clone(), expected: ve.clone() }.collect(stub, name, req, resp, mismatches)
+            }
+            mismatches.exit();
+        }
     }
-}
\ No newline at end of file
+}
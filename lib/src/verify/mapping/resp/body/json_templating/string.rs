@@ -2,16 +2,12 @@ use anyhow::anyhow;
 
 use crate::model::response::{
     ResponseStub,
-    template::{
-        data::{HandlebarsData, RequestData},
-        HandlebarTemplatable,
-        utils::TemplateExt,
-    },
+    template::{data::{HandlebarsData, RequestData}, utils::TemplateExt},
 };
 
 use super::{
     JsonBodyTemplatingVerifier,
-    super::super::{StdResponse, Verifier},
+    super::{render_verified, super::{StdResponse, super::super::report::Mismatches, Verifier}},
 };
 
 pub struct JsonStrVerifier<'a> {
@@ -30,7 +26,7 @@ impl<'a> TryFrom<&'a JsonBodyTemplatingVerifier> for JsonStrVerifier<'a> {
 }
 
 impl Verifier<'_> for JsonStrVerifier<'_> {
-    fn verify(self, stub: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, _: &'_ mut StdResponse) {
+    fn collect(self, _: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, _: &'_ mut StdResponse, mismatches: &mut Mismatches) {
         if self.expected.has_template_expressions() {
             let data = HandlebarsData {
                 request: req,
@@ -38,12 +34,11 @@ impl Verifier<'_> for JsonStrVerifier<'_> {
                 is_verify: true,
                 stub_name: Some(name),
             };
-            stub.body.register(self.expected, self.expected);
-            let _ = stub.body.render(self.expected, &data);
-        } else {
-            assert_eq!(self.actual, self.expected,
-                       "Verification failed for stub '{}'. Expected json field to be '{}' but was '{}'",
-                       name, self.expected, self.actual);
+            render_verified(self.expected, &data, self.actual, mismatches);
+        } else if self.actual != self.expected {
+            let msg = format!("Verification failed for stub '{}'. Expected json field to be '{}' but was '{}'",
+                              name, self.expected, self.actual);
+            mismatches.push_body(self.expected, self.actual, msg)
         }
     }
-}
\ No newline at end of file
+}
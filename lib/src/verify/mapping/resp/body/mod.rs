@@ -1,19 +1,63 @@
-use super::{
-    StdResponse,
-    super::super::super::model::response::{ResponseStub, template::data::RequestData},
-    Verifier
+use std::{cell::RefCell, str::FromStr};
+
+use crate::model::response::{
+    ResponseStub,
+    templatable::Templatable,
+    template::{data::{HandlebarsData, RequestData}, TemplateRenderer, Templates, verify::TemplateMismatch},
 };
 
+use super::{StdResponse, super::super::report::Mismatches, Verifier};
+
 mod json;
 mod json_templating;
 mod text;
 mod text_templating;
 
+thread_local! {
+    /// Registry of the verification in progress. Templates are verified one at a time, hence compiled on the fly
+    static RENDERER: RefCell<TemplateRenderer> = RefCell::new(TemplateRenderer::new(Templates::default()));
+}
+
+/// Verifies response bodies with `templates` within `f` then restores the previous ones
+pub fn with_templates<T>(templates: &Templates, f: impl FnOnce() -> T) -> T {
+    let previous = RENDERER.with(|r| r.replace(TemplateRenderer::new(templates.clone())));
+    let out = f();
+    RENDERER.with(|r| r.replace(previous));
+    out
+}
+
+/// Whether a template renders the same given the same request, user-defined helpers included
+pub(super) fn is_predictable(template: &str) -> bool {
+    RENDERER.with(|r| r.borrow().templates().is_predictable(template))
+}
+
 pub struct BodyVerifier;
 
 impl Verifier<'_> for BodyVerifier {
-    fn verify(self, stub: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, resp: &'_ mut StdResponse) {
-        json::JsonBodyVerifier.verify(stub, name, req, resp);
-        text::TextBodyVerifier.verify(stub, name, req, resp);
+    fn collect(self, stub: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, resp: &'_ mut StdResponse, mismatches: &mut Mismatches) {
+        json::JsonBodyVerifier.collect(stub, name, req, resp, mismatches);
+        text::TextBodyVerifier.collect(stub, name, req, resp, mismatches);
     }
 }
+
+/// Renders a templated status or delay like a server would
+pub(super) fn render<T: FromStr + Copy>(value: &Templatable<T>, data: &HandlebarsData) -> Option<T> {
+    RENDERER.with(|r| value.render(data, &r.borrow()))
+}
+
+/// Renders a response template in verification mode.
+/// A random helper rejecting the actual value, or a template failing to render, is recorded as
+/// a mismatch and yields nothing.
+fn render_verified(template: &str, data: &HandlebarsData, actual: impl ToString, mismatches: &mut Mismatches) -> Option<String> {
+    try_render_verified(template, data, mismatches)
+        .map_err(|msg| mismatches.push_body(template, actual, msg))
+        .ok()
+}
+
+/// Same as [render_verified] but leaves recording the failure message to the caller
+pub(super) fn try_render_verified(template: &str, data: &HandlebarsData, mismatches: &Mismatches) -> Result<String, String> {
+    RENDERER.with(|r| r.borrow().try_render(template, data)).map_err(|e| match TemplateMismatch::find(&e) {
+        Some(mismatch) => mismatch.0.to_string(),
+        None => format!("\nVerification failed for stub '{}'. Response template '{}' could not be rendered because {}", mismatches.stub(), template, e),
+    })
+}
@@ -6,26 +6,34 @@ use crate::model::response::{
 };
 
 use super::{
-    super::{StdResponse, Verifier},
+    super::{StdResponse, super::super::report::Mismatches, Verifier},
     text_templating::TextBodyTemplatingVerifier,
 };
 
 pub struct TextBodyVerifier;
 
 impl Verifier<'_> for TextBodyVerifier {
-    fn verify(self, stub: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, resp: &'_ mut StdResponse) {
+    fn collect(self, stub: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, resp: &'_ mut StdResponse, mismatches: &mut Mismatches) {
         if let Some(expected) = stub.body.body.to_owned() {
             let actual = block_on(async move { resp.0.body_string().await.ok() }).filter(|it| !it.is_empty());
-            assert!(actual.is_some(), "\nVerification failed for stub '{}'. Expected response body to be '{}' but none present", name, expected);
-            let actual = actual.unwrap();
-            if expected.has_template_expressions() {
-                if stub.requires_response_templating() {
-                    TextBodyTemplatingVerifier { actual, expected }.verify(stub, name, req, &mut StdResponse::default());
-                } else {
-                    panic!("\nVerification failed for stub '{}'. No response template transformer present but template elements present in expected response text body '{}'", name, expected)
+            match actual {
+                None => {
+                    let msg = format!("\nVerification failed for stub '{}'. Expected response body to be '{}' but none present", name, expected);
+                    mismatches.push_body(&expected, "", msg)
                 }
-            } else {
-                assert_eq!(actual, expected, "\nVerification failed for stub '{}'. Expected response body to be '{}' but was '{}'", name, expected, actual);
+                Some(actual) if expected.has_template_expressions() => {
+                    if stub.requires_response_templating() {
+                        TextBodyTemplatingVerifier { actual, expected }.collect(stub, name, req, &mut StdResponse::default(), mismatches);
+                    } else {
+                        let msg = format!("\nVerification failed for stub '{}'. No response template transformer present but template elements present in expected response text body '{}'", name, expected);
+                        mismatches.push_body(&expected, &actual, msg)
+                    }
+                }
+                Some(actual) if actual != expected => {
+                    let msg = format!("\nVerification failed for stub '{}'. Expected response body to be '{}' but was '{}'", name, expected, actual);
+                    mismatches.push_body(&expected, &actual, msg)
+                }
+                Some(_) => {}
             }
         }
     }
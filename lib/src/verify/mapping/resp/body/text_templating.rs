@@ -1,6 +1,6 @@
-use crate::model::response::{ResponseStub, template::{data::{HandlebarsData, RequestData}, HandlebarTemplatable, verify::Predictable}};
+use crate::model::response::{ResponseStub, template::data::{HandlebarsData, RequestData}};
 
-use super::super::{StdResponse, Verifier};
+use super::{is_predictable, render_verified, super::{StdResponse, super::super::report::Mismatches, Verifier}};
 
 pub struct TextBodyTemplatingVerifier {
     pub actual: String,
@@ -8,17 +8,18 @@ pub struct TextBodyTemplatingVerifier {
 }
 
 impl Verifier<'_> for TextBodyTemplatingVerifier {
-    fn verify(self, stub: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, _: &'_ mut StdResponse) {
+    fn collect(self, _: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, _: &'_ mut StdResponse, mismatches: &mut Mismatches) {
         let data = HandlebarsData {
             request: req,
             response: Some(self.actual.as_bytes()),
             is_verify: true,
             stub_name: Some(name),
         };
-        stub.body.register(&self.expected, &self.expected);
-        let expected = stub.body.render(&self.expected, &data);
-        if self.expected.is_predictable() {
-            assert_eq!(self.actual, expected, "\nVerification failed for stub '{}'. Expected response body to be '{}' but was '{}'", name, expected, self.actual);
+        if let Some(expected) = render_verified(&self.expected, &data, &self.actual, mismatches) {
+            if is_predictable(&self.expected) && self.actual != expected {
+                let msg = format!("\nVerification failed for stub '{}'. Expected response body to be '{}' but was '{}'", name, expected, self.actual);
+                mismatches.push_body(&expected, &self.actual, msg);
+            }
         }
     }
 }
@@ -84,6 +85,23 @@ mod text_body_templating_verify_tests {
         }
     }
 
+    #[should_panic(expected = "Verification failed for stub 'text'. Response template '{{#if}}' could not be rendered")]
+    #[test]
+    fn verify_text_body_should_fail_when_template_does_not_render() {
+        let actual = "one".to_string();
+        let expected = "{{#if}}".to_string();
+        let stub = ResponseStub {
+            body: BodyStub { body: Some(expected.clone()), ..Default::default() },
+            transformers: vec![String::from("response-template")],
+            ..Default::default()
+        };
+        let mut req = Request::get("http://localhost/");
+        let mut resp = Response::new(200);
+        resp.set_body(actual.as_str());
+        TextBodyTemplatingVerifier { actual, expected }
+            .verify(&stub, "text", &RequestData::from(&mut req), &mut StdResponse(resp));
+    }
+
     mod any {
         use super::*;
 
@@ -478,6 +496,78 @@ mod text_body_templating_verify_tests {
         }
     }
 
+    mod pick_random {
+        use super::*;
+
+        #[test]
+        fn should_verify_body() {
+            verify("pick", "b", "{{pickRandom 'a' 'b'}}");
+        }
+
+        #[should_panic(expected = "Verification failed for stub 'pick'. Expected response body to be one of [\"a\", \"b\"] but was 'c'")]
+        #[test]
+        fn verify_body_should_fail_when_not_picked() {
+            verify("pick", "c", "{{pickRandom 'a' 'b'}}")
+        }
+    }
+
+    mod random_int {
+        use super::*;
+
+        #[test]
+        fn should_verify_body() {
+            verify("int", "5", "{{randomInt lower=5 upper=9}}");
+        }
+
+        #[should_panic(expected = "Verification failed for stub 'int'. Expected response body to be an integer in [5, 9) but was '9'")]
+        #[test]
+        fn verify_body_should_fail_when_out_of_bounds() {
+            verify("int", "9", "{{randomInt lower=5 upper=9}}")
+        }
+    }
+
+    mod custom_helper {
+        use anyhow::ensure;
+
+        use crate::{HelperArgs, Helpers, StubrHelper, model::response::template::Templates};
+
+        use super::{*, super::super::with_templates};
+
+        struct Digits;
+
+        impl StubrHelper for Digits {
+            fn generate(&self, _: &HelperArgs) -> anyhow::Result<String> {
+                Ok(String::from("42"))
+            }
+
+            fn is_random(&self) -> bool {
+                true
+            }
+
+            fn verify(&self, _: &HelperArgs, actual: &str) -> anyhow::Result<()> {
+                ensure!(actual.chars().all(|c| c.is_ascii_digit()), "Expected digits but was '{}'", actual);
+                Ok(())
+            }
+        }
+
+        fn templates() -> Templates {
+            let mut helpers = Helpers::default();
+            helpers.register("digits", Digits);
+            Templates::new(&helpers)
+        }
+
+        #[test]
+        fn should_verify_body() {
+            with_templates(&templates(), || verify("digits", "1234", "{{digits}}"));
+        }
+
+        #[should_panic(expected = "Verification failed for stub 'digits'. Expected digits but was 'abcd'")]
+        #[test]
+        fn verify_body_should_fail_when_rejected_by_helper() {
+            with_templates(&templates(), || verify("digits", "abcd", "{{digits}}"));
+        }
+    }
+
     fn verify(name: &str, actual: &str, expected: &str) {
         let stub = ResponseStub {
             body: BodyStub { body: Some(expected.to_string()), ..Default::default() },
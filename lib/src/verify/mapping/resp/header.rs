@@ -1,21 +1,63 @@
-use crate::model::response::{ResponseStub, template::data::RequestData};
+use itertools::Itertools;
 
-use super::{StdResponse, Verifier};
+use crate::model::response::{
+    headers::HttpRespHeadersStub,
+    ResponseStub,
+    template::{data::{HandlebarsData, RequestData}, utils::TemplateExt},
+};
+
+use super::{body::{is_predictable, try_render_verified}, StdResponse, super::super::report::{Mismatches, MismatchKind}, Verifier};
 
 pub struct HeaderVerifier;
 
+impl HeaderVerifier {
+    /// Renders templated values like a server would, given the actual value at the same position.
+    /// Values produced by a random helper are accepted once the helper verified the actual one
+    fn render(stub: &ResponseStub, name: &str, req: &RequestData, key: &str, expected: Vec<String>, actual: &[String], mismatches: &mut Mismatches) -> Option<Vec<String>> {
+        if !stub.requires_response_templating() {
+            return Some(expected);
+        }
+        expected.into_iter().enumerate()
+            .map(|(i, template)| {
+                if !template.has_template_expressions() {
+                    return Some(template);
+                }
+                let actual = actual.get(i).map(String::as_str).unwrap_or_default();
+                let data = HandlebarsData { request: req, response: Some(actual.as_bytes()), is_verify: true, stub_name: Some(name) };
+                match try_render_verified(&template, &data, mismatches) {
+                    Ok(rendered) if is_predictable(&template) => Some(rendered),
+                    Ok(_) => Some(actual.to_string()),
+                    Err(msg) => {
+                        mismatches.push(MismatchKind::Header, Some(key.to_string()), &template, actual, msg);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
 impl Verifier<'_> for HeaderVerifier {
-    fn verify(self, stub: &'_ ResponseStub, name: &'_ str, _: &'_ RequestData, resp: &'_ mut StdResponse) {
+    fn collect(self, stub: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, resp: &'_ mut StdResponse, mismatches: &mut Mismatches) {
         if let Some(expected) = stub.headers.headers.as_ref() {
             for (expected_key, expected_value) in expected {
-                if let Some(actual_value) = resp.0.header(expected_key.as_str()).and_then(|it| it.get(0)) {
-                    let expected_value = expected_value.as_str().unwrap();
-                    let actual_value = actual_value.as_str();
-                    assert_eq!(actual_value, expected_value,
-                               "Verification failed for stub '{}'. Expected response header '{}' to have value '{}' but was '{}'",
-                               name, expected_key, expected_value, actual_value)
-                } else {
-                    panic!("Verification failed for stub '{}'. Expected one response header with key '{}' but none found", name, expected_key)
+                let actual_values = resp.0.header(expected_key.as_str())
+                    .map(|it| it.iter().map(|v| v.as_str().to_string()).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                let expected_value = HttpRespHeadersStub::values(expected_value);
+                if actual_values.is_empty() {
+                    mismatches.push(MismatchKind::Header, Some(expected_key.to_string()), expected_value.join(", "), "",
+                                    format!("Verification failed for stub '{}'. Expected one response header with key '{}' but none found", name, expected_key));
+                    continue;
+                }
+                if let Some(expected_value) = Self::render(stub, name, req, expected_key, expected_value, &actual_values, mismatches) {
+                    let expected_value = expected_value.join(", ");
+                    let actual_value = actual_values.iter().join(", ");
+                    if actual_value != expected_value {
+                        mismatches.push(MismatchKind::Header, Some(expected_key.to_string()), &expected_value, &actual_value,
+                                        format!("Verification failed for stub '{}'. Expected response header '{}' to have value '{}' but was '{}'",
+                                                name, expected_key, expected_value, actual_value));
+                    }
                 }
             }
         }
@@ -25,16 +67,14 @@ impl Verifier<'_> for HeaderVerifier {
 #[cfg(test)]
 mod header_verify_tests {
     use http_types::{Request, Response};
-    use serde_json::{Map, Value};
-
-    use crate::model::response::{headers::HttpRespHeadersStub, ResponseStub};
+    use serde_json::{json, Map, Value};
 
     use super::*;
 
     #[test]
     fn should_verify_one() {
         let stub = ResponseStub {
-            status: Some(200),
+            status: Some(200.into()),
             headers: HttpRespHeadersStub {
                 headers: Some(Map::from_iter(vec![(String::from("x-a"), Value::String(String::from("b")))]))
             },
@@ -51,7 +91,7 @@ mod header_verify_tests {
     #[test]
     fn should_verify_many() {
         let stub = ResponseStub {
-            status: Some(200),
+            status: Some(200.into()),
             headers: HttpRespHeadersStub {
                 headers: Some(Map::from_iter(vec![
                     (String::from("x-a"), Value::String(String::from("b"))),
@@ -69,11 +109,33 @@ mod header_verify_tests {
         HeaderVerifier.verify(&stub, "many-header", &req, &mut resp);
     }
 
+    #[test]
+    fn should_verify_multi_valued() {
+        let stub = ResponseStub {
+            status: Some(200.into()),
+            headers: HttpRespHeadersStub {
+                headers: Some(Map::from_iter(vec![
+                    (String::from("set-cookie"), json!(["a=1", "b=2"])),
+                    (String::from("x-count"), json!(3)),
+                ]))
+            },
+            ..Default::default()
+        };
+        let mut req = Request::get("http://localhost/");
+        let req = RequestData::from(&mut req);
+        let mut resp = Response::new(200);
+        resp.append_header("set-cookie", "a=1");
+        resp.append_header("set-cookie", "b=2");
+        resp.append_header("x-count", "3");
+        let mut resp = StdResponse(resp);
+        HeaderVerifier.verify(&stub, "multi-valued-header", &req, &mut resp);
+    }
+
     #[should_panic(expected = "Verification failed for stub 'missing-key'. Expected one response header with key 'x-a' but none found")]
     #[test]
     fn should_fail_when_missing() {
         let stub = ResponseStub {
-            status: Some(200),
+            status: Some(200.into()),
             headers: HttpRespHeadersStub {
                 headers: Some(Map::from_iter(vec![(String::from("x-a"), Value::String(String::from("b")))]))
             },
@@ -89,7 +151,7 @@ mod header_verify_tests {
     #[test]
     fn should_fail_when_wrong_value() {
         let stub = ResponseStub {
-            status: Some(200),
+            status: Some(200.into()),
             headers: HttpRespHeadersStub {
                 headers: Some(Map::from_iter(vec![(String::from("x-a"), Value::String(String::from("b")))]))
             },
@@ -102,4 +164,64 @@ mod header_verify_tests {
         let mut resp = StdResponse(resp);
         HeaderVerifier.verify(&stub, "wrong-value", &req, &mut resp);
     }
+
+    fn templated(headers: Value) -> ResponseStub {
+        ResponseStub {
+            headers: HttpRespHeadersStub { headers: headers.as_object().cloned() },
+            transformers: vec![String::from("response-template")],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn should_verify_templated() {
+        let stub = templated(json!({"x-path": "{{request.path}}", "x-values": ["{{request.method}}", "b"], "x-id": "{{anyUuid}}"}));
+        let mut req = Request::get("http://localhost/a/b");
+        let req = RequestData::from(&mut req);
+        let mut resp = Response::new(200);
+        resp.append_header("x-path", "/a/b");
+        resp.append_header("x-values", "GET");
+        resp.append_header("x-values", "b");
+        resp.append_header("x-id", "0f5d5aa7-16c4-4f5b-9a0b-68f4c8e9f6a1");
+        let mut resp = StdResponse(resp);
+        HeaderVerifier.verify(&stub, "templated-header", &req, &mut resp);
+    }
+
+    #[should_panic(expected = "Verification failed for stub 'templated-header'. Expected response header 'x-path' to have value '/a/b' but was '/c'")]
+    #[test]
+    fn should_fail_when_templated_value_differs() {
+        let stub = templated(json!({"x-path": "{{request.path}}"}));
+        let mut req = Request::get("http://localhost/a/b");
+        let req = RequestData::from(&mut req);
+        let mut resp = Response::new(200);
+        resp.append_header("x-path", "/c");
+        let mut resp = StdResponse(resp);
+        HeaderVerifier.verify(&stub, "templated-header", &req, &mut resp);
+    }
+
+    #[should_panic(expected = "Verification failed for stub 'templated-header'. Expected response header 'x-values' to have value '/a/b, b' but was '/a/b'")]
+    #[test]
+    fn should_fail_when_templated_value_missing() {
+        let stub = templated(json!({"x-values": ["{{request.path}}", "b"]}));
+        let mut req = Request::get("http://localhost/a/b");
+        let req = RequestData::from(&mut req);
+        let mut resp = Response::new(200);
+        resp.append_header("x-values", "/a/b");
+        let mut resp = StdResponse(resp);
+        HeaderVerifier.verify(&stub, "templated-header", &req, &mut resp);
+    }
+
+    #[test]
+    fn should_fail_when_random_value_rejected() {
+        let stub = templated(json!({"x-id": "{{anyUuid}}"}));
+        let mut req = Request::get("http://localhost/");
+        let req = RequestData::from(&mut req);
+        let mut resp = Response::new(200);
+        resp.append_header("x-id", "not-a-uuid");
+        let mut mismatches = Mismatches::new("random-header");
+        HeaderVerifier.collect(&stub, "random-header", &req, &mut StdResponse(resp), &mut mismatches);
+        let first = mismatches.first().unwrap();
+        assert_eq!(first.kind, MismatchKind::Header);
+        assert_eq!(first.path.as_deref(), Some("x-id"));
+    }
 }
\ No newline at end of file
@@ -7,9 +7,12 @@ use body::BodyVerifier;
 use header::HeaderVerifier;
 use status::StatusVerifier;
 
-use crate::model::response::{ResponseStub, template::data::RequestData};
+use crate::model::response::{ResponseStub, template::{data::RequestData, Templates}};
 
-use super::req::StdRequest;
+use super::{
+    req::StdRequest,
+    super::report::{Mismatch, Mismatches},
+};
 
 mod status;
 mod header;
@@ -25,7 +28,18 @@ impl Default for StdResponse {
 }
 
 trait Verifier<'a> {
-    fn verify(self, stub: &'a ResponseStub, name: &'a str, req: &'a RequestData, resp: &'a mut StdResponse);
+    /// Records every difference between the stub and the actual response
+    fn collect(self, stub: &'a ResponseStub, name: &'a str, req: &'a RequestData, resp: &'a mut StdResponse, mismatches: &mut Mismatches);
+
+    /// Fails on the first difference between the stub and the actual response
+    #[cfg(test)]
+    fn verify(self, stub: &'a ResponseStub, name: &'a str, req: &'a RequestData, resp: &'a mut StdResponse) where Self: Sized {
+        let mut mismatches = Mismatches::new(name);
+        self.collect(stub, name, req, resp, &mut mismatches);
+        if let Some(first) = mismatches.first() {
+            panic!("{}", first.message)
+        }
+    }
 }
 
 pub struct RequestAndStub {
@@ -35,12 +49,18 @@ pub struct RequestAndStub {
 }
 
 impl RequestAndStub {
-    pub fn verify(mut self, mut resp: StdResponse) {
+    /// Verifies everything instead of stopping at the first mismatch.
+    /// * `templates` - renders response templates, user-defined helpers included
+    pub fn collect(mut self, mut resp: StdResponse, templates: &Templates) -> Vec<Mismatch> {
         let name = self.name().to_string();
+        let mut mismatches = Mismatches::new(name.trim_end_matches(".json"));
         let req_data = RequestData::from(self.req.0.borrow_mut());
-        HeaderVerifier.verify(&self.stub, &name, &req_data, &mut resp);
-        StatusVerifier.verify(&self.stub, &name, &req_data, &mut resp);
-        BodyVerifier.verify(&self.stub, &name, &req_data, &mut resp);
+        body::with_templates(templates, || {
+            HeaderVerifier.collect(&self.stub, &name, &req_data, &mut resp, &mut mismatches);
+            StatusVerifier.collect(&self.stub, &name, &req_data, &mut resp, &mut mismatches);
+            BodyVerifier.collect(&self.stub, &name, &req_data, &mut resp, &mut mismatches);
+        });
+        mismatches.into_vec()
     }
 
     fn name(&self) -> &str {
@@ -58,7 +78,7 @@ mod resp_verify_tests {
 
     #[test]
     fn should_verify() {
-        let stub = ResponseStub { status: Some(200), ..Default::default() };
+        let stub = ResponseStub { status: Some(200.into()), ..Default::default() };
         let mut req = Request::get("http://localhost/");
         let mut resp = StdResponse(Response::new(200));
         StatusVerifier.verify(&stub, "ok", &RequestData::from(&mut req), &mut resp);
@@ -1,16 +1,34 @@
-use crate::model::response::{ResponseStub, template::data::RequestData};
+use http_types::StatusCode;
 
-use super::{StdResponse, Verifier};
+use crate::model::response::{ResponseStub, template::data::{HandlebarsData, RequestData}};
+
+use super::{body::{is_predictable, render}, StdResponse, super::super::report::{Mismatches, MismatchKind}, Verifier};
 
 pub struct StatusVerifier;
 
+impl StatusVerifier {
+    /// Status a server would respond, none when it is random
+    fn expected(stub: &ResponseStub, req: &RequestData) -> Option<u16> {
+        match stub.status.as_ref().filter(|s| s.template().is_some()) {
+            Some(status) if status.template().map(is_predictable).unwrap_or_default() => {
+                let data = HandlebarsData { request: req, response: None, stub_name: None, is_verify: false };
+                Some(render(status, &data)
+                    .filter(|s| StatusCode::try_from(*s).is_ok())
+                    .unwrap_or(ResponseStub::INVALID_STATUS))
+            }
+            Some(_) => None,
+            None => Some(stub.status()),
+        }
+    }
+}
+
 impl Verifier<'_> for StatusVerifier {
-    fn verify(self, stub: &'_ ResponseStub, name: &'_ str, _req: &'_ RequestData, resp: &'_ mut StdResponse) {
-        let expected = stub.status();
+    fn collect(self, stub: &'_ ResponseStub, name: &'_ str, req: &'_ RequestData, resp: &'_ mut StdResponse, mismatches: &mut Mismatches) {
         let actual = u16::from(resp.0.status());
-        assert_eq!(actual, expected,
-                   "Verification failed for stub '{}'. Expected response status to be '{}' but was '{}'",
-                   name, expected, actual);
+        if let Some(expected) = Self::expected(stub, req).filter(|e| *e != actual) {
+            mismatches.push(MismatchKind::Status, None, expected, actual,
+                            format!("Verification failed for stub '{}'. Expected response status to be '{}' but was '{}'", name, expected, actual));
+        }
     }
 }
 
@@ -18,11 +36,13 @@ impl Verifier<'_> for StatusVerifier {
 mod status_verify_tests {
     use http_types::{Request, Response};
 
+    use crate::model::response::templatable::Templatable;
+
     use super::*;
 
     #[test]
     fn should_verify_200() {
-        let stub = ResponseStub { status: Some(200), ..Default::default() };
+        let stub = ResponseStub { status: Some(200.into()), ..Default::default() };
         let mut req = Request::get("http://localhost/");
         let req = RequestData::from(&mut req);
         let mut resp = StdResponse(Response::new(200));
@@ -32,10 +52,35 @@ mod status_verify_tests {
     #[should_panic(expected = "Verification failed for stub '200'. Expected response status to be '200' but was '201'")]
     #[test]
     fn verify_should_fail_when_wrong_status_returned() {
-        let stub = ResponseStub { status: Some(200), ..Default::default() };
+        let stub = ResponseStub { status: Some(200.into()), ..Default::default() };
         let mut req = Request::get("http://localhost/");
         let req = RequestData::from(&mut req);
         let mut resp = StdResponse(Response::new(201));
         StatusVerifier.verify(&stub, "200", &req, &mut resp);
     }
+
+    #[test]
+    fn should_verify_templated_status() {
+        let stub = ResponseStub { status: Some(Templatable::Template(String::from("{{request.query.code}}"))), ..Default::default() };
+        let mut req = Request::get("http://localhost/?code=404");
+        let req = RequestData::from(&mut req);
+        StatusVerifier.verify(&stub, "404", &req, &mut StdResponse(Response::new(404)));
+    }
+
+    #[should_panic(expected = "Verification failed for stub '404'. Expected response status to be '404' but was '200'")]
+    #[test]
+    fn verify_should_fail_when_wrong_templated_status_returned() {
+        let stub = ResponseStub { status: Some(Templatable::Template(String::from("{{request.query.code}}"))), ..Default::default() };
+        let mut req = Request::get("http://localhost/?code=404");
+        let req = RequestData::from(&mut req);
+        StatusVerifier.verify(&stub, "404", &req, &mut StdResponse(Response::new(200)));
+    }
+
+    #[test]
+    fn should_not_verify_random_status() {
+        let stub = ResponseStub { status: Some(Templatable::Template(String::from("{{randomInt lower=200 upper=300}}"))), ..Default::default() };
+        let mut req = Request::get("http://localhost/");
+        let req = RequestData::from(&mut req);
+        StatusVerifier.verify(&stub, "rnd", &req, &mut StdResponse(Response::new(201)));
+    }
 }
\ No newline at end of file
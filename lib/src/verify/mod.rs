@@ -1,20 +1,66 @@
 use async_trait::async_trait;
 
+use generator::GeneratorOptions;
+use report::VerifyReport;
+use states::ProviderStates;
+
+use crate::model::response::template::helpers::custom::Helpers;
+
 #[cfg(feature = "verify-actix")]
 pub mod actix;
+#[cfg(feature = "verify-tower")]
+pub mod tower;
 
 mod stub_finder;
 mod mapping;
+pub mod report;
+pub mod states;
+pub mod generator;
+
+/// Tunes how an application gets verified
+#[derive(Default)]
+pub struct VerifyOptions {
+    /// Setups stubs can require through their `metadata.state`
+    pub states: ProviderStates,
+    /// Seed and number of samples of generated request values
+    pub generator: GeneratorOptions,
+    /// User-defined handlebars helpers the stubs were rendered with. Random ones verify the actual values
+    pub helpers: Helpers,
+}
 
 #[async_trait(? Send)]
 pub trait StubrVerify<T> where Self: Sized {
-    /// Triggers verification of the application from published stubs
+    /// Triggers verification of the application from published stubs.
+    /// Only the default `response` of a stub is verified, not its conditional `responses`,
+    /// or the first of its `responses` when they are a sequence.
+    /// Panics with a summary of all the mismatches found.
     async fn verify(self) {
         self.verify_except(|_| false).await
     }
     /// Same as [`verify`] but accepts ignoring some stubs.
     /// * `except` - ignore stub given its name (without .json suffix).
-    async fn verify_except<N>(self, except: impl VerifyExcept<N> + 'async_trait);
+    async fn verify_except<N>(self, except: impl VerifyExcept<N> + 'async_trait) {
+        self.verify_report_except(except).await.assert_success()
+    }
+    /// Same as [`verify`] with custom options e.g. provider states or generator seed
+    async fn verify_with(self, options: VerifyOptions) {
+        self.verify_report_with(options).await.assert_success()
+    }
+    /// Verifies the application against every published stub without panicking
+    async fn verify_report(self) -> VerifyReport {
+        self.verify_report_except(|_| false).await
+    }
+    /// Same as [`verify_report`] but accepts ignoring some stubs.
+    /// * `except` - ignore stub given its name (without .json suffix).
+    async fn verify_report_except<N>(self, except: impl VerifyExcept<N> + 'async_trait) -> VerifyReport {
+        self.verify_report_except_with(except, VerifyOptions::default()).await
+    }
+    /// Same as [`verify_report`] with custom options e.g. provider states or generator seed
+    async fn verify_report_with(self, options: VerifyOptions) -> VerifyReport {
+        self.verify_report_except_with(|_| false, options).await
+    }
+    /// Same as [`verify_report_except`] with custom options e.g. provider states or generator seed
+    async fn verify_report_except_with<N>(self, except: impl VerifyExcept<N> + 'async_trait, options: VerifyOptions) -> VerifyReport;
 }
 
 /// Helps passing a `fn(&str) -> bool` in `verify_except`
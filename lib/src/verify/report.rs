@@ -0,0 +1,384 @@
+use std::{
+    env,
+    ffi::OsStr,
+    fmt::{Display, Formatter},
+    fs,
+    io,
+    path::Path,
+};
+
+use itertools::Itertools;
+
+use crate::gen::regex::RegexGenConfig;
+
+/// What part of the response did not match the stub
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MismatchKind {
+    /// Stub could not be turned into a request or the application failed to answer it
+    Request,
+    Status,
+    Header,
+    Body,
+}
+
+impl Display for MismatchKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            Self::Request => "request",
+            Self::Status => "status",
+            Self::Header => "header",
+            Self::Body => "body",
+        };
+        write!(f, "{}", kind)
+    }
+}
+
+/// A single difference between a stub and the actual response of the application
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Mismatch {
+    /// Stub file name (without .json suffix)
+    pub stub: String,
+    pub kind: MismatchKind,
+    /// Header name or json path (e.g. '$.pets[0].name') of the mismatching field
+    pub path: Option<String>,
+    pub expected: String,
+    pub actual: String,
+    /// Human readable description
+    pub message: String,
+}
+
+/// Outcome of verifying an application against all its stubs.
+/// Unlike [`crate::StubrVerify::verify`], it does not stop at the first mismatch.
+#[derive(Debug, Default, Clone)]
+pub struct VerifyReport {
+    stubs: Vec<String>,
+    mismatches: Vec<Mismatch>,
+}
+
+impl VerifyReport {
+    const JUNIT_ENV: &'static str = "STUBR_VERIFY_JUNIT";
+    const TABLE_HEADER: [&'static str; 5] = ["STUB", "KIND", "PATH", "EXPECTED", "ACTUAL"];
+
+    /// Names of all the stubs which were verified
+    pub fn stubs(&self) -> &[String] {
+        &self.stubs
+    }
+
+    pub fn mismatches(&self) -> &[Mismatch] {
+        &self.mismatches
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    /// Mismatches found for a given stub
+    pub fn mismatches_of<'a>(&'a self, stub: &'a str) -> impl Iterator<Item=&'a Mismatch> {
+        self.mismatches.iter().filter(move |m| m.stub == stub)
+    }
+
+    /// Prints the report and panics if any mismatch was found.
+    /// Also writes a JUnit report when 'STUBR_VERIFY_JUNIT' points to a file.
+    pub fn assert_success(&self) {
+        if let Ok(path) = env::var(Self::JUNIT_ENV) {
+            self.write_junit(&path).unwrap_or_else(|e| panic!("Failed writing JUnit report in '{}' because {}", path, e));
+        }
+        if !self.is_success() {
+            panic!("\n{}", self)
+        }
+    }
+
+    /// Serializes this report in JUnit XML format, one testcase per stub
+    pub fn to_junit(&self) -> String {
+        let cases = self.stubs.iter()
+            .map(|stub| {
+                let failures = self.mismatches_of(stub)
+                    .map(|m| format!(
+                        "      <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+                        m.kind, Self::escape(&m.message), Self::escape(&Self::describe(m))
+                    ))
+                    .join("");
+                if failures.is_empty() {
+                    format!("    <testcase classname=\"stubr.verify\" name=\"{}\"/>\n", Self::escape(stub))
+                } else {
+                    format!("    <testcase classname=\"stubr.verify\" name=\"{}\">\n{}    </testcase>\n", Self::escape(stub), failures)
+                }
+            })
+            .join("");
+        let failures = self.stubs.iter().filter(|s| self.mismatches_of(s).next().is_some()).count();
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n  <testsuite name=\"stubr-verify\" tests=\"{}\" failures=\"{}\">\n{}  </testsuite>\n</testsuites>\n",
+            self.stubs.len(), failures, cases
+        )
+    }
+
+    /// Writes [`VerifyReport::to_junit`] at the given path, creating parent directories
+    pub fn write_junit(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        if let Some(parent) = path.as_ref().parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_junit())
+    }
+
+    pub(crate) fn add(&mut self, stub: String, mismatches: Vec<Mismatch>) {
+        self.stubs.push(stub);
+        self.mismatches.extend(mismatches);
+    }
+
+    /// Records the outcome of replaying a stub once per seed of generated request values.
+    /// An error means it could not even be replayed.
+    /// Mismatches mention the seed to reproduce them unless the stub was replayed once with the default one.
+    pub(crate) fn add_samples(&mut self, file_name: &OsStr, samples: Vec<(u64, anyhow::Result<Vec<Mismatch>>)>) {
+        let annotate = samples.len() > 1 || samples.iter().any(|(seed, _)| *seed != RegexGenConfig::DEFAULT_SEED);
+        let stub = Self::stub_name(file_name);
+        let mismatches = samples.into_iter()
+            .flat_map(|(seed, result)| {
+                Self::into_mismatches(&stub, result).into_iter()
+                    .map(move |mut m| {
+                        if annotate {
+                            m.message = format!("{} (generated with seed {})", m.message, seed);
+                        }
+                        m
+                    })
+            })
+            .collect();
+        self.add(stub, mismatches)
+    }
+
+    fn stub_name(file_name: &OsStr) -> String {
+        file_name.to_str().unwrap_or_default().trim_end_matches(".json").to_string()
+    }
+
+    fn into_mismatches(stub: &str, result: anyhow::Result<Vec<Mismatch>>) -> Vec<Mismatch> {
+        result.unwrap_or_else(|e| {
+            let mut mismatches = Mismatches::new(stub);
+            mismatches.push(MismatchKind::Request, None, "", "", format!("Could not verify stub '{}' because {:#}", stub, e));
+            mismatches.into_vec()
+        })
+    }
+
+    fn describe(m: &Mismatch) -> String {
+        format!("path: {}\nexpected: {}\nactual: {}", m.path.as_deref().unwrap_or("-"), m.expected, m.actual)
+    }
+
+    fn escape(raw: &str) -> String {
+        raw.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&apos;")
+    }
+
+    fn rows(&self) -> Vec<[String; 5]> {
+        self.mismatches.iter()
+            .map(|m| [
+                m.stub.to_string(),
+                m.kind.to_string(),
+                m.path.clone().unwrap_or_else(|| String::from("-")),
+                m.expected.trim().replace('\n', " "),
+                m.actual.trim().replace('\n', " "),
+            ])
+            .collect()
+    }
+}
+
+impl Display for VerifyReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Verified {} stub(s), {} mismatch(es) found", self.stubs.len(), self.mismatches.len())?;
+        if self.is_success() {
+            return Ok(());
+        }
+        let rows = self.rows();
+        let widths = (0..Self::TABLE_HEADER.len())
+            .map(|i| rows.iter().map(|r| r[i].chars().count()).chain([Self::TABLE_HEADER[i].len()]).max().unwrap_or_default())
+            .collect_vec();
+        let separator = widths.iter().map(|w| "-".repeat(w + 2)).join("+");
+        let line = |cells: Vec<&str>| cells.iter().zip(widths.iter())
+            .map(|(c, w)| format!(" {:<w$} ", c, w = w))
+            .join("|");
+        writeln!(f, "{}", line(Self::TABLE_HEADER.to_vec()))?;
+        writeln!(f, "{}", separator)?;
+        for row in &rows {
+            writeln!(f, "{}", line(row.iter().map(String::as_str).collect()))?;
+        }
+        writeln!(f)?;
+        for (i, m) in self.mismatches.iter().enumerate() {
+            writeln!(f, "{}. {}", i + 1, m.message.trim())?;
+        }
+        Ok(())
+    }
+}
+
+/// Accumulates mismatches while verifying a single stub.
+/// Keeps track of the json path being verified.
+pub(crate) struct Mismatches {
+    stub: String,
+    path: Vec<String>,
+    found: Vec<Mismatch>,
+}
+
+impl Mismatches {
+    pub(crate) fn new(stub: &str) -> Self {
+        Self { stub: stub.to_string(), path: vec![], found: vec![] }
+    }
+
+    /// Descends into a json field
+    pub(crate) fn enter(&mut self, field: &str) {
+        self.path.push(format!(".{}", field));
+    }
+
+    /// Descends into a json array element
+    pub(crate) fn enter_index(&mut self, index: usize) {
+        self.path.push(format!("[{}]", index));
+    }
+
+    pub(crate) fn stub(&self) -> &str {
+        &self.stub
+    }
+
+    pub(crate) fn exit(&mut self) {
+        self.path.pop();
+    }
+
+    /// Current json path e.g. '$.pets[0].name'
+    pub(crate) fn json_path(&self) -> String {
+        format!("${}", self.path.join(""))
+    }
+
+    pub(crate) fn push(&mut self, kind: MismatchKind, path: Option<String>, expected: impl ToString, actual: impl ToString, message: String) {
+        self.found.push(Mismatch {
+            stub: self.stub.clone(),
+            kind,
+            path,
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+            message,
+        })
+    }
+
+    /// Mismatch in the response body at the current json path
+    pub(crate) fn push_body(&mut self, expected: impl ToString, actual: impl ToString, message: String) {
+        let path = Some(self.json_path());
+        self.push(MismatchKind::Body, path, expected, actual, message)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn first(&self) -> Option<&Mismatch> {
+        self.found.first()
+    }
+
+    pub(crate) fn into_vec(self) -> Vec<Mismatch> {
+        self.found
+    }
+}
+
+#[cfg(test)]
+mod report_tests {
+    use super::*;
+
+    fn report() -> VerifyReport {
+        let mut ko = Mismatches::new("ko");
+        ko.push(MismatchKind::Status, None, 200, 500, String::from("wrong status"));
+        ko.enter("pets");
+        ko.enter_index(0);
+        ko.push_body("\"rex\"", "\"<felix>\"", String::from("wrong name"));
+        let mut report = VerifyReport::default();
+        report.add(String::from("ok"), vec![]);
+        report.add(String::from("ko"), ko.into_vec());
+        report
+    }
+
+    #[test]
+    fn should_track_json_path() {
+        let mut m = Mismatches::new("a");
+        assert_eq!(m.json_path(), "$");
+        m.enter("a");
+        m.enter_index(2);
+        m.enter("b");
+        assert_eq!(m.json_path(), "$.a[2].b");
+        m.exit();
+        m.exit();
+        assert_eq!(m.json_path(), "$.a");
+    }
+
+    #[test]
+    fn should_collect_mismatches_of_stub() {
+        let report = report();
+        assert!(!report.is_success());
+        assert_eq!(report.stubs(), &["ok".to_string(), "ko".to_string()]);
+        assert_eq!(report.mismatches_of("ok").count(), 0);
+        let paths = report.mismatches_of("ko").map(|m| m.path.clone()).collect_vec();
+        assert_eq!(paths, vec![None, Some(String::from("$.pets[0]"))]);
+    }
+
+    #[test]
+    fn should_print_as_table() {
+        let table = report().to_string();
+        assert!(table.starts_with("Verified 2 stub(s), 2 mismatch(es) found"));
+        assert!(table.contains(" STUB | KIND   | PATH      | EXPECTED | ACTUAL    \n"));
+        assert!(table.contains(" ko   | body   | $.pets[0] | \"rex\"    | \"<felix>\" \n"));
+    }
+
+    #[test]
+    fn should_print_messages_below_table() {
+        assert!(report().to_string().ends_with("\n1. wrong status\n2. wrong name\n"));
+    }
+
+    #[test]
+    fn should_record_failure_to_replay_as_request_mismatch() {
+        let mut report = VerifyReport::default();
+        report.add_samples(OsStr::new("broken.json"), vec![(RegexGenConfig::DEFAULT_SEED, Err(anyhow::anyhow!("invalid json stub")))]);
+        assert_eq!(report.stubs(), &["broken".to_string()]);
+        let mismatch = report.mismatches().first().unwrap();
+        assert_eq!(mismatch.kind, MismatchKind::Request);
+        assert_eq!(mismatch.message, "Could not verify stub 'broken' because invalid json stub");
+    }
+
+    #[test]
+    fn should_mention_seed_of_failing_samples() {
+        let status = |stub: &str| {
+            let mut mismatches = Mismatches::new(stub);
+            mismatches.push(MismatchKind::Status, None, 200, 500, String::from("wrong status"));
+            mismatches.into_vec()
+        };
+        let mut report = VerifyReport::default();
+        report.add_samples(OsStr::new("ko.json"), vec![(1, Ok(vec![])), (2, Ok(status("ko"))), (3, Err(anyhow::anyhow!("boom")))]);
+        assert_eq!(report.stubs(), &["ko".to_string()]);
+        let messages = report.mismatches().iter().map(|m| m.message.as_str()).collect_vec();
+        assert_eq!(messages, vec![
+            "wrong status (generated with seed 2)",
+            "Could not verify stub 'ko' because boom (generated with seed 3)",
+        ]);
+    }
+
+    #[test]
+    fn should_not_print_table_when_success() {
+        let mut report = VerifyReport::default();
+        report.add(String::from("ok"), vec![]);
+        assert!(report.is_success());
+        assert_eq!(report.to_string(), "Verified 1 stub(s), 0 mismatch(es) found\n");
+    }
+
+    #[test]
+    fn should_export_junit() {
+        let junit = report().to_junit();
+        assert!(junit.contains("<testsuite name=\"stubr-verify\" tests=\"2\" failures=\"1\">"));
+        assert!(junit.contains("<testcase classname=\"stubr.verify\" name=\"ok\"/>"));
+        assert!(junit.contains("<failure type=\"status\" message=\"wrong status\">"));
+        assert!(junit.contains("actual: &quot;&lt;felix&gt;&quot;"));
+    }
+
+    #[test]
+    fn should_write_junit() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reports").join("junit.xml");
+        report().write_junit(&path).unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), report().to_junit());
+    }
+
+    #[should_panic(expected = "Verified 2 stub(s), 2 mismatch(es) found")]
+    #[test]
+    fn assert_success_should_panic_with_table() {
+        report().assert_success();
+    }
+}
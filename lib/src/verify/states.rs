@@ -0,0 +1,143 @@
+use std::{collections::HashMap, future::Future, pin::Pin};
+
+use anyhow::anyhow;
+
+type StateHook = Box<dyn Fn() -> Pin<Box<dyn Future<Output=()>>>>;
+
+struct ProviderState {
+    setup: StateHook,
+    teardown: Option<StateHook>,
+}
+
+/// Pact-like provider states. Each stub declaring a `metadata.state` gets the matching
+/// setup invoked before being replayed and the teardown invoked afterwards.
+/// Hooks take no argument: capture a clone of your application data (e.g. an actix `web::Data`).
+/// With actix, the setup runs before the request reaches `ActixVerifyLifecycle`, hence before it.
+///
+/// ```
+/// # use stubr::ProviderStates;
+/// let states = ProviderStates::default()
+///     .state("pet 1 exists", || async { /* insert pet 1 */ })
+///     .state_with_teardown("no pet", || async { /* delete all */ }, || async { /* restore */ });
+/// ```
+#[derive(Default)]
+pub struct ProviderStates {
+    states: HashMap<String, ProviderState>,
+}
+
+impl ProviderStates {
+    /// Registers a setup for the state named `name`
+    pub fn state<S, Fut>(self, name: &str, setup: S) -> Self
+        where S: Fn() -> Fut + 'static, Fut: Future<Output=()> + 'static {
+        self.register(name, Self::hook(setup), None)
+    }
+
+    /// Registers a setup and a teardown for the state named `name`
+    pub fn state_with_teardown<S, SFut, T, TFut>(self, name: &str, setup: S, teardown: T) -> Self
+        where S: Fn() -> SFut + 'static, SFut: Future<Output=()> + 'static,
+              T: Fn() -> TFut + 'static, TFut: Future<Output=()> + 'static {
+        self.register(name, Self::hook(setup), Some(Self::hook(teardown)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Prepares the application before replaying a stub requiring this state
+    pub(crate) async fn setup(&self, state: Option<&str>) -> anyhow::Result<()> {
+        if let Some(name) = state {
+            let state = self.states.get(name).ok_or_else(|| anyhow!("no provider state '{}' registered", name))?;
+            (state.setup)().await;
+        }
+        Ok(())
+    }
+
+    /// Cleans up once a stub requiring this state has been replayed
+    pub(crate) async fn teardown(&self, state: Option<&str>) {
+        if let Some(teardown) = state.and_then(|n| self.states.get(n)).and_then(|s| s.teardown.as_ref()) {
+            teardown().await;
+        }
+    }
+
+    fn register(mut self, name: &str, setup: StateHook, teardown: Option<StateHook>) -> Self {
+        self.states.insert(name.to_string(), ProviderState { setup, teardown });
+        self
+    }
+
+    fn hook<H, Fut>(hook: H) -> StateHook where H: Fn() -> Fut + 'static, Fut: Future<Output=()> + 'static {
+        Box::new(move || Box::pin(hook()))
+    }
+}
+
+#[cfg(test)]
+mod provider_states_tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use async_std::task::block_on;
+
+    use super::*;
+
+    fn recording_states(calls: &Rc<RefCell<Vec<&'static str>>>) -> ProviderStates {
+        let (a, b, c) = (calls.clone(), calls.clone(), calls.clone());
+        ProviderStates::default()
+            .state("a", move || {
+                let a = a.clone();
+                async move { a.borrow_mut().push("setup a") }
+            })
+            .state_with_teardown("b", move || {
+                let b = b.clone();
+                async move { b.borrow_mut().push("setup b") }
+            }, move || {
+                let c = c.clone();
+                async move { c.borrow_mut().push("teardown b") }
+            })
+    }
+
+    #[test]
+    fn should_run_setup_and_teardown_of_stub_state() {
+        let calls = Rc::new(RefCell::new(vec![]));
+        let states = recording_states(&calls);
+        block_on(async {
+            states.setup(Some("b")).await.unwrap();
+            states.teardown(Some("b")).await;
+            states.setup(Some("a")).await.unwrap();
+            states.teardown(Some("a")).await;
+        });
+        assert_eq!(*calls.borrow(), vec!["setup b", "teardown b", "setup a"]);
+    }
+
+    #[test]
+    fn should_do_nothing_when_stub_has_no_state() {
+        let calls = Rc::new(RefCell::new(vec![]));
+        let states = recording_states(&calls);
+        block_on(async {
+            states.setup(None).await.unwrap();
+            states.teardown(None).await;
+        });
+        assert!(calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn setup_should_fail_when_state_unknown() {
+        let states = ProviderStates::default();
+        let result = block_on(states.setup(Some("unknown")));
+        assert_eq!(result.unwrap_err().to_string(), "no provider state 'unknown' registered");
+    }
+
+    #[test]
+    fn last_registered_should_win() {
+        let calls = Rc::new(RefCell::new(vec![]));
+        let (a, b) = (calls.clone(), calls.clone());
+        let states = ProviderStates::default()
+            .state("a", move || {
+                let a = a.clone();
+                async move { a.borrow_mut().push("first") }
+            })
+            .state("a", move || {
+                let b = b.clone();
+                async move { b.borrow_mut().push("second") }
+            });
+        block_on(states.setup(Some("a"))).unwrap();
+        assert_eq!(*calls.borrow(), vec!["second"]);
+    }
+}
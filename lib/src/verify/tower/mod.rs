@@ -0,0 +1,71 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use futures::future::poll_fn;
+use http::{Request as TowerRequest, request::Builder, Response as TowerResponse};
+use hyper::body::{HttpBody, to_bytes};
+use tower::Service;
+
+use crate::model::response::template::Templates;
+
+use super::{
+    mapping::{req::StdRequest, resp::{RequestAndStub, StdResponse}},
+    report::VerifyReport,
+    stub_finder::ProducerStubFinder,
+    StubrVerify,
+    VerifyExcept,
+    VerifyOptions,
+};
+
+mod req;
+mod resp;
+
+/// Discriminates [`StubrVerify`] implementation for `tower::Service`
+/// from the actix one. Never has to be named explicitly.
+pub struct Tower<B>(PhantomData<B>);
+
+#[async_trait(? Send)]
+impl<S, ReqB, RespB> StubrVerify<Tower<ReqB>> for S where
+    S: Service<TowerRequest<ReqB>, Response=TowerResponse<RespB>>,
+    S::Error: Debug,
+    ReqB: From<Vec<u8>>,
+    RespB: HttpBody,
+    RespB::Error: Debug,
+{
+    async fn verify_report_except_with<N>(mut self, except: impl VerifyExcept<N> + 'async_trait, options: VerifyOptions) -> VerifyReport {
+        let mut report = VerifyReport::default();
+        let templates = Templates::new(&options.helpers);
+        for (stub, name) in ProducerStubFinder::find_stubs(except) {
+            let state = stub.state().map(str::to_string);
+            let mut samples = vec![];
+            for sample in options.generator.samples() {
+                let result = async {
+                    options.states.setup(state.as_deref()).await?;
+                    let replayed = async {
+                        let response = stub.verified_response().cloned().context("stub has no response to verify")?;
+                        let (req, body) = sample.scope(|| {
+                            StdRequest::try_from(&stub).map(|req| (req, Vec::<u8>::from(&stub.request)))
+                        }).context("invalid json stub")?;
+                        let tower_req = Builder::from(&req).body(ReqB::from(body))
+                            .context("invalid request")?;
+                        poll_fn(|cx| self.poll_ready(cx)).await
+                            .map_err(|e| anyhow!("service not ready: {:?}", e))?;
+                        let (parts, body) = self.call(tower_req).await
+                            .map_err(|e| anyhow!("service failed handling the request: {:?}", e))?
+                            .into_parts();
+                        let body = to_bytes(body).await
+                            .map_err(|e| anyhow!("failed reading response body: {:?}", e))?;
+                        let resp = StdResponse::from(TowerResponse::from_parts(parts, body));
+                        Ok(RequestAndStub { req, stub: response, name: name.clone() }.collect(resp, &templates))
+                    }.await;
+                    options.states.teardown(state.as_deref()).await;
+                    replayed
+                }.await;
+                samples.push((sample.seed, result));
+            }
+            report.add_samples(&name, samples);
+        }
+        report
+    }
+}
@@ -0,0 +1,119 @@
+use std::str::FromStr;
+
+use http::{Method as HttpMethod, request::Builder};
+
+use super::super::mapping::req::StdRequest;
+
+impl From<&StdRequest> for Builder {
+    fn from(req: &StdRequest) -> Self {
+        let method = HttpMethod::from_str(req.0.method().as_ref()).expect("Unknown http method");
+        let original_headers = req.0.header_names()
+            .filter_map(|k| req.0.header(k).map(|v| (k, v)));
+        original_headers
+            .fold(Builder::new(), |builder, (k, v)| builder.header(k.as_str(), v.as_str()))
+            .method(method)
+            .uri(req.0.url().as_str())
+    }
+}
+
+#[cfg(test)]
+mod tower_req_mapping_tests {
+    use http::{Method, Request as TowerRequest};
+    use http_types::Request;
+
+    use super::*;
+
+    fn map(req: Request) -> TowerRequest<()> {
+        Builder::from(&StdRequest(req)).body(()).unwrap()
+    }
+
+    mod method {
+        use super::*;
+
+        #[test]
+        fn should_map_get() {
+            assert_eq!(map(Request::get("http://localhost/")).method(), Method::GET)
+        }
+
+        #[test]
+        fn should_map_post() {
+            assert_eq!(map(Request::post("http://localhost/")).method(), Method::POST)
+        }
+
+        #[test]
+        fn should_map_put() {
+            assert_eq!(map(Request::put("http://localhost/")).method(), Method::PUT)
+        }
+
+        #[test]
+        fn should_map_delete() {
+            assert_eq!(map(Request::delete("http://localhost/")).method(), Method::DELETE)
+        }
+
+        #[test]
+        fn should_map_patch() {
+            assert_eq!(map(Request::patch("http://localhost/")).method(), Method::PATCH)
+        }
+    }
+
+    mod url {
+        use http::uri::Scheme;
+
+        use super::*;
+
+        #[test]
+        fn should_map_scheme() {
+            assert_eq!(map(Request::get("http://github.com:8080/api/url")).uri().scheme(), Some(&Scheme::HTTP));
+        }
+
+        #[test]
+        fn should_map_host() {
+            assert_eq!(map(Request::get("http://github.com:8080/api/url")).uri().host(), Some("github.com"));
+        }
+
+        #[test]
+        fn should_map_port() {
+            assert_eq!(map(Request::get("http://github.com:8080/api/url")).uri().port_u16(), Some(8080));
+        }
+
+        #[test]
+        fn should_map_path() {
+            assert_eq!(map(Request::get("http://github.com:8080/api/url")).uri().path(), "/api/url");
+        }
+    }
+
+    mod query {
+        use super::*;
+
+        #[test]
+        fn should_map_many() {
+            assert_eq!(map(Request::get("http://localhost?a=b&c=d")).uri().query(), Some("a=b&c=d"));
+        }
+
+        #[test]
+        fn should_not_fail_when_none() {
+            assert!(map(Request::get("http://localhost")).uri().query().is_none());
+        }
+    }
+
+    mod header {
+        use http::HeaderValue;
+
+        use super::*;
+
+        #[test]
+        fn should_map_many() {
+            let mut req = Request::get("http://localhost");
+            req.append_header("x-a", "b");
+            req.append_header("x-c", "d");
+            let req = map(req);
+            assert_eq!(req.headers().get("x-a"), Some(&HeaderValue::from_static("b")));
+            assert_eq!(req.headers().get("x-c"), Some(&HeaderValue::from_static("d")));
+        }
+
+        #[test]
+        fn should_not_fail_when_none() {
+            assert!(map(Request::get("http://localhost")).headers().is_empty());
+        }
+    }
+}
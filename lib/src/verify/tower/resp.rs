@@ -0,0 +1,100 @@
+use std::str::FromStr;
+
+use http::Response as TowerResponse;
+use http_types::{
+    headers::HeaderName as HttpHeaderName,
+    headers::HeaderValue as HttpHeaderValue,
+    headers::HeaderValues as HttpHeaderValues,
+    Response,
+};
+use hyper::body::Bytes;
+
+use super::super::mapping::resp::StdResponse;
+
+impl From<TowerResponse<Bytes>> for StdResponse {
+    fn from(resp: TowerResponse<Bytes>) -> Self {
+        let mut std_resp = Response::new(resp.status().as_u16());
+        resp.headers().into_iter()
+            .filter_map(|(k, v)| {
+                let k = HttpHeaderName::from_str(k.as_str()).ok();
+                let v = v.to_str().ok()
+                    .map(|it| it.split(',').map(|s| s.trim()).filter_map(|i| HttpHeaderValue::from_str(i).ok()))
+                    .map(HttpHeaderValues::from_iter);
+                k.zip(v)
+            })
+            .for_each(|(k, v)| std_resp.append_header(k, &v));
+        if !resp.body().is_empty() {
+            std_resp.set_body(resp.body().as_ref())
+        }
+        Self(std_resp)
+    }
+}
+
+#[cfg(test)]
+mod tower_resp_mapping_tests {
+    use super::*;
+
+    fn resp(status: u16, headers: &[(&str, &str)], body: &'static str) -> StdResponse {
+        let resp = headers.iter()
+            .fold(TowerResponse::builder().status(status), |b, (k, v)| b.header(*k, *v))
+            .body(Bytes::from_static(body.as_bytes()))
+            .unwrap();
+        StdResponse::from(resp)
+    }
+
+    mod status {
+        use super::*;
+
+        #[test]
+        fn should_map_status_success() {
+            assert_eq!(resp(200, &[], "").0.status(), 200);
+        }
+
+        #[test]
+        fn should_map_status_error() {
+            assert_eq!(resp(400, &[], "").0.status(), 400);
+        }
+    }
+
+    mod headers {
+        use super::*;
+
+        #[test]
+        fn should_map_many_header() {
+            let std_resp = resp(200, &[("a", "b"), ("c", "d")], "").0;
+            assert_eq!(std_resp.header("a").unwrap().get(0), HttpHeaderValue::from_str("b").ok().as_ref());
+            assert_eq!(std_resp.header("c").unwrap().get(0), HttpHeaderValue::from_str("d").ok().as_ref());
+        }
+
+        #[test]
+        fn should_map_multi_header() {
+            let std_resp = resp(200, &[("a", "b, c")], "").0;
+            let expected = HttpHeaderValues::from_iter(vec!["b".try_into().unwrap(), "c".try_into().unwrap()]);
+            assert!(std_resp.header("a").unwrap().iter().eq(expected.iter()));
+        }
+    }
+
+    mod body {
+        use serde_json::{json, Value};
+
+        use super::*;
+
+        #[async_std::test]
+        async fn should_map_json_body() {
+            let mut std_resp = resp(200, &[], r#"{"a":"b"}"#).0;
+            assert_eq!(std_resp.body_json::<Value>().await.unwrap(), json!({"a": "b"}));
+        }
+
+        #[async_std::test]
+        async fn should_map_text_body() {
+            let mut std_resp = resp(200, &[], "hello").0;
+            assert_eq!(std_resp.body_string().await.unwrap(), String::from("hello"));
+        }
+
+        #[async_std::test]
+        async fn should_map_missing_body() {
+            let mut std_resp = resp(200, &[], "").0;
+            assert!(std_resp.body_json::<Value>().await.ok().is_none());
+        }
+    }
+}
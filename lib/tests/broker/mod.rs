@@ -0,0 +1,104 @@
+use std::path::Path;
+
+use asserhttp::*;
+use surf::get;
+use tempfile::tempdir;
+
+use stubr::{AnyBroker, Broker, BrokerServer, Contract, FsBroker, HttpBroker, Selector, Stubr};
+
+fn contract(app: &str, version: &str, tags: &[&str], stubs: &str) -> Contract {
+    let tags = tags.iter().map(|t| t.to_string()).collect();
+    Contract::from_dir(app, version, tags, Path::new(stubs)).unwrap()
+}
+
+/// Unsets the broker env variables once dropped, even when the test panics
+struct BrokerEnv;
+
+impl BrokerEnv {
+    fn set(broker: &Path, tag: &str) -> Self {
+        std::env::set_var(AnyBroker::BROKER_ENV, broker);
+        std::env::set_var(AnyBroker::TAG_ENV, tag);
+        Self
+    }
+}
+
+impl Drop for BrokerEnv {
+    fn drop(&mut self) {
+        std::env::remove_var(AnyBroker::BROKER_ENV);
+        std::env::remove_var(AnyBroker::TAG_ENV);
+    }
+}
+
+async fn publish_versions(broker: &impl Broker, app: &str) {
+    broker.publish(&contract(app, "1.0.0", &["main"], "tests/stubs/broker/v1")).await.unwrap();
+    broker.publish(&contract(app, "2.0.0", &["feature"], "tests/stubs/broker/v2")).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn should_fetch_latest_of_tag_from_http_broker() {
+    let server = BrokerServer::start(tempdir().unwrap().into_path());
+    let broker = HttpBroker::new(&server.uri());
+    publish_versions(&broker, "http-app").await;
+    let stubr = Stubr::app_from(&broker, "http-app", &Selector::Tag(String::from("main"))).await;
+    get(stubr.path("/hello")).await.expect_status_ok().expect_body_text_eq("v1");
+    let stubr = Stubr::app_from(&broker, "http-app", &Selector::Latest).await;
+    get(stubr.path("/hello")).await.expect_status_ok().expect_body_text_eq("v2");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn http_broker_should_share_fs_broker_storage() {
+    let root = tempdir().unwrap().into_path();
+    publish_versions(&FsBroker::new(&root), "shared-app").await;
+    let server = BrokerServer::start(&root);
+    let broker = HttpBroker::new(&server.uri());
+    let stubr = Stubr::app_from(&broker, "shared-app", &Selector::Version(String::from("2.0.0"))).await;
+    get(stubr.path("/hello")).await.expect_status_ok().expect_body_text_eq("v2");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn http_broker_should_fail_when_app_unknown() {
+    let server = BrokerServer::start(tempdir().unwrap().into_path());
+    let err = HttpBroker::new(&server.uri()).fetch("unknown", &Selector::Latest).await.unwrap_err();
+    assert!(err.to_string().contains("no version of app 'unknown' published"), "{}", err);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn http_broker_should_reject_invalid_contract() {
+    let server = BrokerServer::start(tempdir().unwrap().into_path());
+    let mut contract = contract("app", "1.0.0", &[], "tests/stubs/broker/v1");
+    contract.tags.push(String::from("not/a/tag"));
+    assert!(HttpBroker::new(&server.uri()).publish(&contract).await.is_err());
+}
+
+#[async_std::test]
+async fn should_fetch_from_fs_broker() {
+    let broker = FsBroker::new(tempdir().unwrap().into_path());
+    publish_versions(&broker, "fs-app").await;
+    let stubr = Stubr::app_from(&broker, "fs-app", &Selector::Tag(String::from("feature"))).await;
+    get(stubr.path("/hello")).await.expect_status_ok().expect_body_text_eq("v2");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn should_serve_published_body_files() {
+    let server = BrokerServer::start(tempdir().unwrap().into_path());
+    let broker = HttpBroker::new(&server.uri());
+    broker.publish(&contract("files-app", "1.0.0", &[], "tests/stubs/broker/body-files")).await.unwrap();
+    let stubr = Stubr::app_from(&broker, "files-app", &Selector::Latest).await;
+    get(stubr.path("/hello")).await.expect_status_ok().expect_body_text_eq("from a file");
+}
+
+#[async_std::test]
+async fn should_fetch_from_broker_in_env() {
+    let root = tempdir().unwrap().into_path();
+    publish_versions(&FsBroker::new(&root), "env-app").await;
+    let _env = BrokerEnv::set(&root, "main");
+    let stubr = Stubr::app("env-app").await;
+    get(stubr.path("/hello")).await.expect_status_ok().expect_body_text_eq("v1");
+}
+
+#[async_std::test]
+#[should_panic(expected = "Could not fetch app 'unknown' from broker because no version of app 'unknown' published")]
+async fn should_fail_when_app_not_in_broker() {
+    let broker = FsBroker::new(tempdir().unwrap().into_path());
+    Stubr::app_from(&broker, "unknown", &Selector::Latest).await;
+}
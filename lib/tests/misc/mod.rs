@@ -6,4 +6,7 @@ pub mod probes;
 pub mod pub_api;
 pub mod wiremock_iso;
 pub mod tokio;
-pub mod jwt;
\ No newline at end of file
+pub mod jwt;
+pub mod scenario;
+pub mod responses;
+pub mod sequence;
\ No newline at end of file
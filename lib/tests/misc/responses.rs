@@ -0,0 +1,43 @@
+use asserhttp::*;
+use serde_json::json;
+use surf::{get, post};
+
+#[async_std::test]
+#[stubr::mock("responses/when.json")]
+async fn should_pick_first_variant_whose_expression_applies() {
+    post(stubr.path("/books")).body(json!({"id": 101})).await.expect_status_not_found();
+    post(stubr.path("/books")).body(json!({"id": -1})).await
+        .expect_status_bad_request()
+        .expect_body_text_eq("negative -1");
+}
+
+#[async_std::test]
+#[stubr::mock("responses/when.json")]
+async fn should_fall_back_to_response() {
+    post(stubr.path("/books")).body(json!({"id": 1})).await
+        .expect_status_ok()
+        .expect_body_text_eq("found");
+    post(stubr.path("/books")).body(json!({})).await.expect_status_ok();
+}
+
+#[async_std::test]
+#[stubr::mock("responses/when.json")]
+async fn variants_should_keep_stub_matchers() {
+    get(stubr.path("/books")).await.expect_status_not_found();
+    post(stubr.path("/authors")).body(json!({"id": 101})).await.expect_status_not_found();
+}
+
+#[async_std::test]
+#[stubr::mock("responses/request.json")]
+async fn should_pick_variant_by_request_matchers() {
+    get(stubr.path("/books")).await.expect_status_unauthorized();
+    get(stubr.path("/books")).header("authorization", "Bearer a").await
+        .expect_status_ok()
+        .expect_body_absent();
+    get(stubr.path("/books?id=42&stock=true")).header("authorization", "Bearer a").await
+        .expect_status_ok()
+        .expect_body_text_eq("in stock");
+    get(stubr.path("/books?id=42")).header("authorization", "Bearer a").await
+        .expect_status_ok()
+        .expect_body_text_eq("out of stock");
+}
\ No newline at end of file
@@ -0,0 +1,32 @@
+use asserhttp::*;
+use serde_json::json;
+use surf::{get, post};
+
+#[async_std::test]
+#[stubr::mock("scenario")]
+async fn should_start_in_started_state() {
+    get(stubr.path("/todos")).await
+        .expect_status_ok()
+        .expect_body_json_eq(json!([]));
+}
+
+#[async_std::test]
+#[stubr::mock("scenario")]
+async fn should_transition_to_new_state() {
+    post(stubr.path("/todos")).await.expect_status_created();
+    get(stubr.path("/todos")).await
+        .expect_status_ok()
+        .expect_body_json_eq(json!(["buy milk"]));
+    // no stub requires 'added' state for this request
+    post(stubr.path("/todos")).await.expect_status_not_found();
+}
+
+#[async_std::test]
+#[stubr::mock("scenario")]
+async fn should_reset_scenarios() {
+    post(stubr.path("/todos")).await.expect_status_created();
+    stubr.reset_scenarios();
+    get(stubr.path("/todos")).await
+        .expect_status_ok()
+        .expect_body_json_eq(json!([]));
+}
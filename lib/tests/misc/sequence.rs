@@ -0,0 +1,65 @@
+use asserhttp::*;
+use surf::get;
+
+use crate::utils::*;
+
+#[async_std::test]
+#[stubr::mock("sequence/once-each-then-last.json")]
+async fn should_serve_each_response_once_then_the_last() {
+    get(stubr.uri()).await.expect_status_internal_server_error();
+    get(stubr.uri()).await.expect_status(503);
+    get(stubr.uri()).await.expect_status_ok().expect_body_text_eq("ok");
+    get(stubr.uri()).await.expect_status_ok().expect_body_text_eq("ok");
+}
+
+#[async_std::test]
+#[stubr::mock("sequence/cycle.json")]
+async fn should_cycle_through_responses() {
+    get(stubr.uri()).await.expect_status_internal_server_error();
+    get(stubr.uri()).await.expect_status(503);
+    get(stubr.uri()).await.expect_status_ok();
+    get(stubr.uri()).await.expect_status_internal_server_error();
+}
+
+#[async_std::test]
+#[stubr::mock("sequence/weighted.json")]
+async fn should_pick_according_to_weights() {
+    for _ in 0..10 {
+        get(stubr.uri()).await.expect_status_ok();
+    }
+}
+
+#[async_std::test]
+#[stubr::mock("sequence/random.json")]
+async fn should_pick_randomly() {
+    for _ in 0..10 {
+        get(stubr.uri()).await.expect_status_in_range(201, 203);
+    }
+}
+
+#[async_std::test]
+#[stubr::mock("sequence/once-each-then-last.json")]
+async fn should_reset_sequences() {
+    get(stubr.uri()).await.expect_status_internal_server_error();
+    get(stubr.uri()).await.expect_status(503);
+    stubr.reset_sequences();
+    get(stubr.uri()).await.expect_status_internal_server_error();
+}
+
+#[async_std::test]
+#[stubr::mock("sequence/conditional.json")]
+async fn should_not_mount_sequence_of_conditional_responses() {
+    get(stubr.query("a", "b")).await.expect_status_not_found();
+}
+
+#[async_std::test]
+#[stubr::mock("sequence/zero-weights.json")]
+async fn should_not_mount_weighted_sequence_without_positive_weight() {
+    get(stubr.uri()).await.expect_status_not_found();
+}
+
+#[async_std::test]
+#[stubr::mock("sequence/with-response.json")]
+async fn should_not_mount_sequence_with_a_response() {
+    get(stubr.uri()).await.expect_status_not_found();
+}
\ No newline at end of file
@@ -3,4 +3,6 @@ pub mod resp;
 pub mod utils;
 pub mod record;
 pub mod misc;
-pub mod attributes;
\ No newline at end of file
+pub mod attributes;
+pub mod verify;
+pub mod broker;
\ No newline at end of file
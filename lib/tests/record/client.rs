@@ -9,7 +9,7 @@ async fn isahc_client_should_proxy_to_recorder() {
     Stubr::record_with(record_cfg()).isahc_client()
         .get(stubr.path("/record/isahc"))
         .expect_status_ok();
-    assert_recorded_stub_exists("record-isahc-240440901283558448")
+    assert_recorded_stub_exists("record-isahc-7f0a2952b270100f")
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -19,5 +19,5 @@ async fn reqwest_client_should_proxy_to_recorder() {
     Stubr::record_with(record_cfg()).reqwest_client()
         .get(stubr.path("/record/reqwest")).send().await
         .expect_status_ok();
-    assert_recorded_stub_exists("record-reqwest-3255972930125209685")
+    assert_recorded_stub_exists("record-reqwest-631be8c400c647d2")
 }
\ No newline at end of file
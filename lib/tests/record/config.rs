@@ -16,7 +16,7 @@ async fn should_allow_custom_output() {
         ..Default::default()
     };
     Stubr::record_with(cfg).isahc_client().get(stubr.path("/status/200")).expect_status_ok();
-    assert!(output.join("localhost").join("status-200-18234664605884201165.json").exists())
+    assert!(output.join("localhost").join("status-200-0e854335fbe223c0.json").exists())
 }
 
 #[tokio::test(flavor = "multi_thread")]
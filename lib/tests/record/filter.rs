@@ -0,0 +1,59 @@
+use std::fs::read_dir;
+
+use actix_web::{App, HttpResponse, test::{call_service, init_service, TestRequest}, web};
+use asserhttp::*;
+use tempfile::tempdir;
+
+use stubr::{ActixRecord, PathRegex, RecordConfig, RecordFilter, Stubr};
+
+use crate::utils::*;
+
+fn recorded_count(output: &std::path::Path) -> usize {
+    read_dir(output.join("localhost")).map(|dir| {
+        dir.filter_map(Result::ok)
+            .filter(|e| e.path().is_file() && !e.file_name().to_str().unwrap().starts_with('.'))
+            .count()
+    }).unwrap_or_default()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/status")]
+async fn proxy_should_only_record_included() {
+    let output = tempdir().unwrap().into_path();
+    let include = RecordFilter { status: Some(200..=299), ..Default::default() };
+    let cfg = RecordConfig { output: Some(output.clone()), include: Some(include), ..record_cfg() };
+    let recorder = Stubr::record_with(cfg);
+    recorder.isahc_client().get(stubr.path("/status/200")).expect_status_ok();
+    recorder.isahc_client().get(stubr.path("/status/400")).expect_status_bad_request();
+    recorder.isahc_client().get(stubr.path("/status/500")).expect_status_internal_server_error();
+    assert_eq!(recorded_count(&output), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/status")]
+async fn proxy_should_not_record_excluded() {
+    let output = tempdir().unwrap().into_path();
+    let exclude = RecordFilter { path: Some(PathRegex::try_from("/[45][0-9]{2}$").unwrap()), ..Default::default() };
+    let cfg = RecordConfig { output: Some(output.clone()), exclude: Some(exclude), ..record_cfg() };
+    let recorder = Stubr::record_with(cfg);
+    recorder.isahc_client().get(stubr.path("/status/200")).expect_status_ok();
+    recorder.isahc_client().get(stubr.path("/status/400")).expect_status_bad_request();
+    recorder.isahc_client().get(stubr.path("/status/500")).expect_status_internal_server_error();
+    assert_eq!(recorded_count(&output), 1);
+}
+
+#[actix_web::test]
+async fn actix_should_not_record_excluded() {
+    let output = tempdir().unwrap().into_path();
+    let exclude = RecordFilter { methods: vec![String::from("GET")], path: Some(PathRegex::try_from("^/health$").unwrap()), ..Default::default() };
+    let cfg = RecordConfig { output: Some(output.clone()), exclude: Some(exclude), ..record_cfg() };
+    let app = App::new()
+        .route("/health", web::get().to(|| async { HttpResponse::Ok().await }))
+        .route("/users", web::get().to(|| async { HttpResponse::Ok().await }))
+        .wrap(ActixRecord(cfg));
+    let app = init_service(app).await;
+    call_service(&app, TestRequest::get().uri("/health").to_request()).await.expect_status_ok();
+    assert_eq!(recorded_count(&output), 0);
+    call_service(&app, TestRequest::get().uri("/users").to_request()).await.expect_status_ok();
+    assert_eq!(recorded_count(&output), 1);
+}
@@ -0,0 +1,55 @@
+use asserhttp::*;
+use serde_json::{json, Value};
+use tempfile::tempdir;
+
+use stubr::{RecordConfig, Stubr};
+
+use crate::utils::*;
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/generalize/users.json")]
+async fn proxy_should_generalize_volatile_values() {
+    let cfg = RecordConfig { generalize: true, ..record_cfg() };
+    Stubr::record_with(cfg).isahc_client()
+        .post(stubr.path("/generalize/users/42?ref=8c1f1e4a-0c6f-4a8e-9a39-6d1b3c2f7d10"), json!({"email": "john@doe.com", "name": "john"}).to_string())
+        .expect_status_created();
+    assert_recorded_stub_eq("generalize-users-535f285959542f96", json!({
+        "request": {
+            "method": "POST",
+            "urlPathPattern": "/generalize/users/[0-9]+",
+            "queryParameters": {
+                "ref": {"matches": "[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}"}
+            },
+            "bodyPatterns": [
+                {"equalToJson": {"name": "john"}, "ignoreExtraElements": true},
+                {"expression": "$.email", "matches": "[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\\.[a-zA-Z]{2,6}"}
+            ]
+        },
+        "response": {
+            "status": 201,
+            "jsonBody": {"id": "{{anyUuid}}", "createdAt": "{{anyIso8601}}", "name": "john"},
+            "transformers": ["response-template"]
+        }
+    }))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/generalize/users.json")]
+async fn generalized_stub_should_match_other_values() {
+    let output = tempdir().unwrap().into_path();
+    let cfg = RecordConfig { output: Some(output.clone()), generalize: true, ..record_cfg() };
+    Stubr::record_with(cfg).isahc_client()
+        .post(stubr.path("/generalize/users/43"), json!({"email": "john@doe.com", "teamId": 7}).to_string())
+        .expect_status_created();
+    let recorded = Stubr::start(recorded_stub(&output)).await;
+    isahc::post(recorded.path("/generalize/users/1234"), json!({"email": "alice@wonder.land", "teamId": 12}).to_string())
+        .expect_status_created()
+        .expect_body_json(|body: Value| {
+            assert_ne!(body["id"], json!("8c1f1e4a-0c6f-4a8e-9a39-6d1b3c2f7d10"));
+            assert_eq!(body["id"].as_str().unwrap().len(), 36);
+        });
+    isahc::post(recorded.path("/generalize/users/abc"), json!({"email": "alice@wonder.land", "teamId": 12}).to_string())
+        .expect_status_not_found();
+    isahc::post(recorded.path("/generalize/users/1234"), json!({"email": "alice@wonder.land", "teamId": "12"}).to_string())
+        .expect_status_not_found();
+}
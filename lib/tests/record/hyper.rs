@@ -0,0 +1,55 @@
+use std::{fs::File, path::Path};
+
+use hyper::{Body, Client, Request};
+use serde_json::{json, Value};
+use tempfile::tempdir;
+use tower::ServiceExt;
+
+use stubr::{HyperRecord, RecordConfig};
+
+use crate::utils::*;
+
+fn cfg(output: &Path) -> RecordConfig {
+    RecordConfig { output: Some(output.to_path_buf()), ..record_cfg() }
+}
+
+fn read(output: &Path) -> Value {
+    serde_json::from_reader(File::open(recorded_stub(output)).unwrap()).unwrap()
+}
+
+#[tokio::test]
+#[stubr::mock("record/resp-body/json.json")]
+async fn should_record_from_hyper_client() {
+    let output = tempdir().unwrap().into_path();
+    let client = Client::new().record_with(cfg(&output));
+    let resp = client.get(stubr.path("/body/resp/json").parse().unwrap()).await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(serde_json::from_slice::<Value>(&body).unwrap(), json!({"a": {"b": "c"}}));
+    let stub = read(&output);
+    assert_eq!(stub["request"], json!({"method": "GET", "urlPath": "/body/resp/json"}));
+    assert_eq!(stub["response"]["jsonBody"], json!({"a": {"b": "c"}}));
+}
+
+#[tokio::test]
+#[stubr::mock("record/req-body/json.json")]
+async fn should_record_request_body_from_hyper_client() {
+    let output = tempdir().unwrap().into_path();
+    let client = Client::new().record_with(cfg(&output));
+    let req = Request::post(stubr.path("/body/req/json"))
+        .header("content-type", "application/json")
+        .body(Body::from(json!({"a": {"b": "c"}}).to_string()))
+        .unwrap();
+    assert_eq!(client.request(req).await.unwrap().status().as_u16(), 200);
+    assert_eq!(read(&output)["request"]["bodyPatterns"], json!([{"equalToJson": {"a": {"b": "c"}}}]));
+}
+
+#[tokio::test]
+#[stubr::mock("record/resp-body/json.json")]
+async fn should_record_when_used_as_a_tower_service() {
+    let output = tempdir().unwrap().into_path();
+    let client = Client::new().record_with(cfg(&output));
+    let req = Request::get(stubr.path("/body/resp/json")).body(Body::empty()).unwrap();
+    assert_eq!(client.oneshot(req).await.unwrap().status().as_u16(), 200);
+    assert_eq!(read(&output)["request"], json!({"method": "GET", "urlPath": "/body/resp/json"}));
+}
@@ -0,0 +1,71 @@
+use std::{fs::File, path::Path};
+
+use asserhttp::*;
+use serde_json::{json, Value};
+use tempfile::tempdir;
+
+use stubr::{BodyMatching, MatcherStrategy, RecordConfig, Stubr, UrlMatching};
+
+use crate::utils::*;
+
+fn cfg(output: &Path, matchers: MatcherStrategy) -> RecordConfig {
+    RecordConfig { output: Some(output.to_path_buf()), matchers, ..record_cfg() }
+}
+
+fn read(output: &Path) -> Value {
+    serde_json::from_reader(File::open(recorded_stub(output)).unwrap()).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/query/many.json")]
+async fn should_record_path_and_query_in_url() {
+    let output = tempdir().unwrap().into_path();
+    let matchers = MatcherStrategy { url: UrlMatching::Url, ..Default::default() };
+    Stubr::record_with(cfg(&output, matchers)).isahc_client()
+        .get(stubr.path_queries("/query/many", ("a", "1"), ("b", "2")))
+        .expect_status_ok();
+    assert_eq!(read(&output)["request"], json!({"method": "GET", "url": "/query/many?a=1&b=2"}));
+    let replay = Stubr::start(output.join("localhost")).await;
+    isahc::get(replay.path_queries("/query/many", ("b", "2"), ("a", "1"))).expect_status_ok();
+    isahc::get(replay.path_queries("/query/many", ("a", "1"), ("b", "3"))).expect_status_not_found();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/req-headers/many.json")]
+async fn should_only_record_chosen_headers() {
+    let output = tempdir().unwrap().into_path();
+    let matchers = MatcherStrategy { headers: Some(vec![String::from("x-b")]), ..Default::default() };
+    let req = || isahc::Request::get(stubr.path("/headers/req/many"))
+        .header("x-a", "a")
+        .header("x-b", "b")
+        .body(())
+        .unwrap();
+    Stubr::record_with(cfg(&output, matchers)).isahc_client().send(req()).expect_status_ok();
+    assert_eq!(read(&output)["request"]["headers"], json!({"x-b": {"equalTo": "b"}}));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/req-body/json.json")]
+async fn should_record_json_body_as_json_paths() {
+    let output = tempdir().unwrap().into_path();
+    let matchers = MatcherStrategy { body: BodyMatching::JsonPath, ..Default::default() };
+    Stubr::record_with(cfg(&output, matchers)).isahc_client()
+        .post(stubr.path("/body/req/json"), json!({"a": {"b": "c"}}).to_string())
+        .expect_status_ok();
+    assert_eq!(read(&output)["request"]["bodyPatterns"], json!([{"expression": "$.a.b", "equalToJson": "c"}]));
+    let replay = Stubr::start(output.join("localhost")).await;
+    isahc::post(replay.path("/body/req/json"), json!({"a": {"b": "c", "d": "e"}}).to_string()).expect_status_ok();
+    isahc::post(replay.path("/body/req/json"), json!({"a": {"b": "d"}}).to_string()).expect_status_not_found();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/req-body/json.json")]
+async fn should_record_relaxed_json_body() {
+    let output = tempdir().unwrap().into_path();
+    let body = BodyMatching::EqualToJson { ignore_extra_elements: true, ignore_array_order: false };
+    let matchers = MatcherStrategy { body, ..Default::default() };
+    Stubr::record_with(cfg(&output, matchers)).isahc_client()
+        .post(stubr.path("/body/req/json"), json!({"a": {"b": "c"}}).to_string())
+        .expect_status_ok();
+    assert_eq!(read(&output)["request"]["bodyPatterns"], json!([{"equalToJson": {"a": {"b": "c"}}, "ignoreExtraElements": true}]));
+}
@@ -4,10 +4,21 @@ pub mod path;
 pub mod query;
 pub mod req_body;
 pub mod resp_body;
+pub mod generalize;
+pub mod redact;
+pub mod naming;
+pub mod scenario;
+pub mod target;
+pub mod filter;
+pub mod matchers;
 pub mod req_headers;
 pub mod resp_headers;
 pub mod smoke;
 pub mod config;
 pub mod client;
 pub mod reqwest;
-pub mod actix;
\ No newline at end of file
+pub mod actix;
+pub mod tower;
+pub mod reqwest_middleware;
+pub mod surf;
+pub mod hyper;
\ No newline at end of file
@@ -0,0 +1,98 @@
+use std::{fs::File, path::{Path, PathBuf}};
+
+use asserhttp::*;
+use isahc::ReadResponseExt;
+use serde_json::Value;
+use tempfile::tempdir;
+
+use stubr::{Dedupe, RecordConfig, Stubr, StubNaming};
+
+use crate::utils::*;
+
+fn cfg(output: &Path, naming: StubNaming, dedupe: Dedupe) -> RecordConfig {
+    RecordConfig { output: Some(output.to_path_buf()), naming, dedupe, ..record_cfg() }
+}
+
+fn recorded_body(file: PathBuf) -> String {
+    let stub: Value = serde_json::from_reader(File::open(file).unwrap()).unwrap();
+    stub["response"]["body"].as_str().unwrap().to_string()
+}
+
+fn manifest(output: &Path) -> Value {
+    serde_json::from_reader(File::open(output.join("localhost").join(".manifest.json")).unwrap()).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/naming/uuid.json")]
+async fn sequence_naming_should_number_distinct_requests() {
+    let output = tempdir().unwrap().into_path();
+    let recorder = Stubr::record_with(cfg(&output, StubNaming::Sequence, Dedupe::KeepLast));
+    recorder.isahc_client().get(stubr.path_query("/naming", "page", "1")).expect_status_ok();
+    recorder.isahc_client().get(stubr.path_query("/naming", "page", "2")).expect_status_ok();
+    recorder.isahc_client().get(stubr.path_query("/naming", "page", "1")).expect_status_ok();
+    let dir = output.join("localhost");
+    assert!(dir.join("get-naming-1.json").exists());
+    assert!(dir.join("get-naming-2.json").exists());
+    assert!(!dir.join("get-naming-3.json").exists());
+    let manifest = manifest(&output);
+    let entries = manifest["stubs"].as_object().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries["get-naming-2.json"]["method"], "GET");
+    assert_eq!(entries["get-naming-2.json"]["url"], "/naming");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/naming/uuid.json")]
+async fn keep_last_should_overwrite_identical_request() {
+    let output = tempdir().unwrap().into_path();
+    let recorder = Stubr::record_with(cfg(&output, StubNaming::Sequence, Dedupe::KeepLast));
+    recorder.isahc_client().get(stubr.path("/naming")).expect_status_ok();
+    let last = recorder.isahc_client().get(stubr.path("/naming")).expect_status_ok().text().unwrap();
+    assert_eq!(recorded_body(output.join("localhost").join("get-naming-1.json")), last);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/naming/uuid.json")]
+async fn keep_first_should_ignore_identical_request() {
+    let output = tempdir().unwrap().into_path();
+    let recorder = Stubr::record_with(cfg(&output, StubNaming::Hash, Dedupe::KeepFirst));
+    let first = recorder.isahc_client().get(stubr.path("/naming")).expect_status_ok().text().unwrap();
+    recorder.isahc_client().get(stubr.path("/naming")).expect_status_ok();
+    assert_eq!(recorded_body(recorded_stub(&output)), first);
+    assert_eq!(manifest(&output)["stubs"].as_object().unwrap().len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/naming/uuid.json")]
+async fn keep_all_should_suffix_identical_requests() {
+    let output = tempdir().unwrap().into_path();
+    let recorder = Stubr::record_with(cfg(&output, StubNaming::Sequence, Dedupe::KeepAll));
+    let first = recorder.isahc_client().get(stubr.path("/naming")).expect_status_ok().text().unwrap();
+    let second = recorder.isahc_client().get(stubr.path("/naming")).expect_status_ok().text().unwrap();
+    let dir = output.join("localhost");
+    assert_eq!(recorded_body(dir.join("get-naming-1.json")), first);
+    assert_eq!(recorded_body(dir.join("get-naming-1-2.json")), second);
+    assert_eq!(manifest(&output)["stubs"]["get-naming-1-2.json"]["occurrence"], 2);
+
+    // replayed in the order they were recorded rather than competing for the same request
+    let stub = |name: &str| serde_json::from_reader::<_, Value>(File::open(dir.join(name)).unwrap()).unwrap();
+    assert_eq!(stub("get-naming-1.json")["newScenarioState"], "2");
+    assert_eq!(stub("get-naming-1-2.json")["requiredScenarioState"], "2");
+    let replay = Stubr::start(dir).await;
+    assert_eq!(isahc::get(replay.path("/naming")).expect_status_ok().text().unwrap(), first);
+    assert_eq!(isahc::get(replay.path("/naming")).expect_status_ok().text().unwrap(), second);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/naming/uuid.json")]
+async fn hash_naming_should_be_stable() {
+    let output = tempdir().unwrap().into_path();
+    let recorder = Stubr::record_with(cfg(&output, StubNaming::Hash, Dedupe::KeepLast));
+    recorder.isahc_client().get(stubr.path("/naming")).expect_status_ok();
+    let first = recorded_stub(&output);
+    recorder.isahc_client().get(stubr.path("/naming")).expect_status_ok();
+    assert_eq!(recorded_stub(&output), first);
+    let name = first.file_stem().unwrap().to_str().unwrap();
+    let hash = name.strip_prefix("naming-").unwrap();
+    assert!(hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()));
+}
@@ -10,7 +10,7 @@ use crate::utils::*;
 async fn proxy_should_forward_path() {
     isahc::get(stubr.path("/a/b/c")).expect_status_ok();
     Stubr::record_with(record_cfg()).isahc_client().get(stubr.path("/a/b/c")).expect_status_ok();
-    assert_recorded_stub_eq("a-b-c-1709172914684740300", json!({
+    assert_recorded_stub_eq("a-b-c-b2f86596fbfbae13", json!({
         "request": {
             "method": "GET",
             "urlPath": "/a/b/c"
@@ -12,7 +12,7 @@ async fn proxy_should_forward_json_request_body() {
     Stubr::record_with(record_cfg()).isahc_client()
         .post(stubr.path("/body/req/json"), json!({"a": { "b": "c" }}).to_string())
         .expect_status_ok();
-    assert_recorded_stub_eq("body-req-json-1875300994429885227", json!({
+    assert_recorded_stub_eq("body-req-json-f624e8d77d50452f", json!({
         "request": {
             "method": "POST",
             "urlPath": "/body/req/json",
@@ -22,4 +22,45 @@ async fn proxy_should_forward_json_request_body() {
         },
         "response": {"status": 200}
     }))
-}
\ No newline at end of file
+}
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/req-body/text.json")]
+async fn proxy_should_forward_text_request_body() {
+    let req = || isahc::Request::post(stubr.path("/body/req/text"))
+        .header("content-type", "application/xml")
+        .body("<a>b</a>")
+        .unwrap();
+    isahc::send(req()).expect_status_ok();
+    Stubr::record_with(record_cfg()).isahc_client().send(req()).expect_status_ok();
+    assert_recorded_stub_eq("body-req-text-3b222de70d76b39f", json!({
+        "request": {
+            "method": "POST",
+            "urlPath": "/body/req/text",
+            "bodyPatterns": [
+                {"equalTo": "<a>b</a>"}
+            ]
+        },
+        "response": {"status": 200}
+    }))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/req-body/binary.json")]
+async fn proxy_should_forward_binary_request_body() {
+    let req = || isahc::Request::post(stubr.path("/body/req/binary"))
+        .header("content-type", "application/octet-stream")
+        .body(vec![1, 2, 3])
+        .unwrap();
+    isahc::send(req()).expect_status_ok();
+    Stubr::record_with(record_cfg()).isahc_client().send(req()).expect_status_ok();
+    assert_recorded_stub_eq("body-req-binary-90f0a70c9ea9a68e", json!({
+        "request": {
+            "method": "POST",
+            "urlPath": "/body/req/binary",
+            "bodyPatterns": [
+                {"binaryEqualTo": "AQID"}
+            ]
+        },
+        "response": {"status": 200}
+    }))
+}
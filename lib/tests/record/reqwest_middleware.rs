@@ -0,0 +1,43 @@
+use std::{fs::File, path::Path};
+
+use reqwest_middleware::ClientBuilder;
+use serde_json::{json, Value};
+use tempfile::tempdir;
+
+use stubr::{RecordConfig, ReqwestRecordMiddleware};
+
+use crate::utils::*;
+
+fn cfg(output: &Path) -> RecordConfig {
+    RecordConfig { output: Some(output.to_path_buf()), ..record_cfg() }
+}
+
+fn read(output: &Path) -> Value {
+    serde_json::from_reader(File::open(recorded_stub(output)).unwrap()).unwrap()
+}
+
+#[tokio::test]
+#[stubr::mock("record/resp-body/json.json")]
+async fn should_record_from_reqwest_middleware() {
+    let output = tempdir().unwrap().into_path();
+    let client = ClientBuilder::new(reqwest::Client::new()).with(ReqwestRecordMiddleware(cfg(&output))).build();
+    let resp = client.get(stubr.path("/body/resp/json")).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.json::<Value>().await.unwrap(), json!({"a": {"b": "c"}}));
+    let stub = read(&output);
+    assert_eq!(stub["request"], json!({"method": "GET", "urlPath": "/body/resp/json"}));
+    assert_eq!(stub["response"]["jsonBody"], json!({"a": {"b": "c"}}));
+}
+
+#[tokio::test]
+#[stubr::mock("record/req-body/json.json")]
+async fn should_record_request_body_from_reqwest_middleware() {
+    let output = tempdir().unwrap().into_path();
+    let client = ClientBuilder::new(reqwest::Client::new()).with(ReqwestRecordMiddleware(cfg(&output))).build();
+    let resp = client.post(stubr.path("/body/req/json"))
+        .header("content-type", "application/json")
+        .body(json!({"a": {"b": "c"}}).to_string())
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(read(&output)["request"]["bodyPatterns"], json!([{"equalToJson": {"a": {"b": "c"}}}]));
+}
@@ -1,7 +1,10 @@
+use std::fs::File;
+
 use asserhttp::*;
-use serde_json::json;
+use serde_json::{json, Value};
+use tempfile::tempdir;
 
-use stubr::{RecordConfig, Stubr};
+use stubr::{Config, RecordConfig, Stubr};
 
 use crate::utils::*;
 
@@ -16,7 +19,7 @@ async fn proxy_should_forward_json_response_body() {
         .expect_status_ok()
         .expect_content_type_json()
         .expect_body_json_eq(json!({"a": {"b": "c"}}));
-    assert_recorded_stub_eq("body-resp-json-4281328719312332612", json!({
+    assert_recorded_stub_eq("body-resp-json-0af183e779ee3211", json!({
         "request": {
             "method": "GET",
             "urlPath": "/body/resp/json"
@@ -29,6 +32,70 @@ async fn proxy_should_forward_json_response_body() {
     }))
 }
 
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/resp-body/text.json")]
+async fn proxy_should_forward_text_response_body() {
+    Stubr::record_with(record_cfg()).isahc_client().get(stubr.path("/body/resp/text"))
+        .expect_status_ok()
+        .expect_body_text_eq("hello");
+    assert_recorded_stub_eq("body-resp-text-eb69580f5a0fbc08", json!({
+        "request": {
+            "method": "GET",
+            "urlPath": "/body/resp/text"
+        },
+        "response": {
+            "status": 200,
+            "body": "hello"
+        }
+    }))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/resp-body/binary.json")]
+async fn proxy_should_forward_binary_response_body() {
+    Stubr::record_with(record_cfg()).isahc_client().get(stubr.path("/body/resp/binary"))
+        .expect_status_ok()
+        .expect_body_bytes_eq(&[1, 2, 3]);
+    assert_recorded_stub_eq("body-resp-binary-389f364d5d508cbe", json!({
+        "request": {
+            "method": "GET",
+            "urlPath": "/body/resp/binary"
+        },
+        "response": {
+            "status": 200,
+            "base64Body": "AQID"
+        }
+    }))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/resp-body/large.json")]
+async fn proxy_should_extract_large_response_body_in_file() {
+    let output = tempdir().unwrap().into_path();
+    let cfg = RecordConfig { output: Some(output.clone()), body_file_threshold: Some(10), ..record_cfg() };
+    Stubr::record_with(cfg).isahc_client().get(stubr.path("/body/resp/large")).expect_status_ok();
+    let recorded = recorded_stub(&output);
+    let stub: Value = serde_json::from_reader(File::open(&recorded).unwrap()).unwrap();
+    assert!(stub["response"].get("jsonBody").is_none());
+    assert_eq!(stub["response"]["bodyFileName"].as_str(), recorded.file_name().and_then(|n| n.to_str()));
+    assert!(output.join("localhost").join("__files").join(recorded.file_name().unwrap()).exists());
+    let replay = Stubr::start_with(recorded, Config { root_dir: Some(output.join("localhost")), ..Default::default() }).await;
+    isahc::get(replay.path("/body/resp/large"))
+        .expect_status_ok()
+        .expect_body_json_eq(json!({"description": "a body large enough to be extracted in a file"}));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/resp-body/large.json")]
+async fn proxy_should_inline_response_body_below_threshold() {
+    let output = tempdir().unwrap().into_path();
+    let cfg = RecordConfig { output: Some(output.clone()), body_file_threshold: Some(1024), ..record_cfg() };
+    Stubr::record_with(cfg).isahc_client().get(stubr.path("/body/resp/large")).expect_status_ok();
+    let stub: Value = serde_json::from_reader(File::open(recorded_stub(&output)).unwrap()).unwrap();
+    assert!(stub["response"].get("bodyFileName").is_none());
+    assert!(!output.join("localhost").join("__files").exists());
+}
+
 fn resp_body_cfg() -> RecordConfig {
     RecordConfig {
         except_request_headers: Some(relaxed_req_headers()),
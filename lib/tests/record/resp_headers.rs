@@ -14,7 +14,7 @@ async fn proxy_should_forward_response_headers() {
     Stubr::record_with(record_cfg()).isahc_client().get(stubr.path("/headers/resp/one"))
         .expect_status_ok()
         .expect_header("x-a", "a");
-    assert_recorded_stub_eq("headers-resp-one-17869354396355261370", json!({
+    assert_recorded_stub_eq("headers-resp-one-89e41ab27dabc105", json!({
         "request": {
             "method": "GET",
             "urlPath": "/headers/resp/one"
@@ -39,7 +39,7 @@ async fn proxy_should_forward_many_response_headers() {
         .expect_status_ok()
         .expect_header("x-a", "a")
         .expect_header("x-b", "b");
-    assert_recorded_stub_eq("headers-resp-many-13659916824342203132", json!({
+    assert_recorded_stub_eq("headers-resp-many-971d919d13949bee", json!({
         "request": {
             "method": "GET",
             "urlPath": "/headers/resp/many"
@@ -0,0 +1,50 @@
+use std::fs::File;
+
+use asserhttp::*;
+use serde_json::{json, Value};
+use tempfile::tempdir;
+
+use stubr::{RecordConfig, Stubr, StubNaming};
+
+use crate::utils::*;
+
+fn cfg(output: &std::path::Path) -> RecordConfig {
+    RecordConfig { output: Some(output.to_path_buf()), naming: StubNaming::Sequence, scenarios: true, ..record_cfg() }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("scenario")]
+async fn should_chain_different_responses_in_a_scenario() {
+    let output = tempdir().unwrap().into_path();
+    let recorder = Stubr::record_with(cfg(&output));
+    recorder.isahc_client().get(stubr.path("/todos")).expect_status_ok();
+    recorder.isahc_client().get(stubr.path("/todos")).expect_status_ok();
+    recorder.isahc_client().post(stubr.path("/todos"), ()).expect_status_created();
+    recorder.isahc_client().get(stubr.path("/todos")).expect_status_ok();
+    let dir = output.join("localhost");
+    let first: Value = serde_json::from_reader(File::open(dir.join("get-todos-1.json")).unwrap()).unwrap();
+    assert_eq!(first["scenarioName"], "get-todos-1");
+    assert_eq!(first["requiredScenarioState"], "Started");
+    assert_eq!(first["newScenarioState"], "2");
+    let second: Value = serde_json::from_reader(File::open(dir.join("get-todos-1-2.json")).unwrap()).unwrap();
+    assert_eq!(second["scenarioName"], "get-todos-1");
+    assert_eq!(second["requiredScenarioState"], "2");
+    assert!(second.get("newScenarioState").is_none());
+    assert!(!dir.join("get-todos-1-3.json").exists());
+    let post: Value = serde_json::from_reader(File::open(dir.join("post-todos-1.json")).unwrap()).unwrap();
+    assert!(post.get("scenarioName").is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("scenario")]
+async fn replay_should_reproduce_recorded_sequence() {
+    let output = tempdir().unwrap().into_path();
+    let recorder = Stubr::record_with(cfg(&output));
+    recorder.isahc_client().get(stubr.path("/todos")).expect_status_ok();
+    recorder.isahc_client().post(stubr.path("/todos"), ()).expect_status_created();
+    recorder.isahc_client().get(stubr.path("/todos")).expect_status_ok();
+    let replay = Stubr::start(output.join("localhost")).await;
+    isahc::get(replay.path("/todos")).expect_status_ok().expect_body_json_eq(json!([]));
+    isahc::get(replay.path("/todos")).expect_status_ok().expect_body_json_eq(json!(["buy milk"]));
+    isahc::get(replay.path("/todos")).expect_status_ok().expect_body_json_eq(json!(["buy milk"]));
+}
@@ -12,7 +12,7 @@ use crate::utils::*;
 async fn proxy_should_forward_success() {
     isahc::get(stubr.path("/success")).expect_status_ok();
     Stubr::record_with(record_cfg()).isahc_client().get(stubr.path("/success")).expect_status_ok();
-    assert_recorded_stub_eq("success-17305706633713301495", json!({
+    assert_recorded_stub_eq("success-69088e7a2611248c", json!({
         "request": {
             "method": "GET",
             "urlPath": "/success"
@@ -26,7 +26,7 @@ async fn proxy_should_forward_success() {
 async fn proxy_should_forward_errors() {
     isahc::get(stubr.path("/not-found")).expect_status_not_found();
     Stubr::record_with(record_cfg()).isahc_client().get(stubr.path("/not-found")).expect_status_not_found();
-    assert_recorded_stub_eq("not-found-5985620929954243551", json!({
+    assert_recorded_stub_eq("not-found-73d014d900f92203", json!({
         "request": {
             "method": "GET",
             "urlPath": "/not-found"
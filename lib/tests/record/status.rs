@@ -10,7 +10,7 @@ use crate::utils::*;
 async fn proxy_should_forward_status_200() {
     isahc::get(stubr.path("/status/200")).expect_status_ok();
     Stubr::record_with(record_cfg()).isahc_client().get(stubr.path("/status/200")).expect_status_ok();
-    assert_recorded_stub_eq("status-200-18234664605884201165", json!({
+    assert_recorded_stub_eq("status-200-0e854335fbe223c0", json!({
         "request": {
             "method": "GET",
             "urlPath": "/status/200"
@@ -24,7 +24,7 @@ async fn proxy_should_forward_status_200() {
 async fn proxy_should_forward_status_400() {
     isahc::get(stubr.path("/status/400")).expect_status_bad_request();
     Stubr::record_with(record_cfg()).isahc_client().get(stubr.path("/status/400")).expect_status_bad_request();
-    assert_recorded_stub_eq("status-400-9868264260807328606", json!({
+    assert_recorded_stub_eq("status-400-d8381f2ce2423342", json!({
         "request": {
             "method": "GET",
             "urlPath": "/status/400"
@@ -38,7 +38,7 @@ async fn proxy_should_forward_status_400() {
 async fn proxy_should_forward_status_500() {
     isahc::get(stubr.path("/status/500")).expect_status_internal_server_error();
     Stubr::record_with(record_cfg()).isahc_client().get(stubr.path("/status/500")).expect_status_internal_server_error();
-    assert_recorded_stub_eq("status-500-7794663214452839295", json!({
+    assert_recorded_stub_eq("status-500-c997a122b92589a1", json!({
         "request": {
             "method": "GET",
             "urlPath": "/status/500"
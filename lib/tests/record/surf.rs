@@ -0,0 +1,40 @@
+use std::{fs::File, path::Path};
+
+use asserhttp::*;
+use serde_json::{json, Value};
+use tempfile::tempdir;
+
+use stubr::{RecordConfig, SurfRecord};
+
+use crate::utils::*;
+
+fn client(output: &Path) -> surf::Client {
+    surf::client().with(SurfRecord(RecordConfig { output: Some(output.to_path_buf()), ..record_cfg() }))
+}
+
+fn read(output: &Path) -> Value {
+    serde_json::from_reader(File::open(recorded_stub(output)).unwrap()).unwrap()
+}
+
+#[async_std::test]
+#[stubr::mock("record/resp-body/json.json")]
+async fn should_record_from_surf_middleware() {
+    let output = tempdir().unwrap().into_path();
+    client(&output).get(stubr.path("/body/resp/json")).await
+        .expect_status_ok()
+        .expect_body_json_eq(json!({"a": {"b": "c"}}));
+    let stub = read(&output);
+    assert_eq!(stub["request"], json!({"method": "GET", "urlPath": "/body/resp/json"}));
+    assert_eq!(stub["response"]["jsonBody"], json!({"a": {"b": "c"}}));
+}
+
+#[async_std::test]
+#[stubr::mock("record/req-body/json.json")]
+async fn should_record_request_body_from_surf_middleware() {
+    let output = tempdir().unwrap().into_path();
+    client(&output).post(stubr.path("/body/req/json"))
+        .body(json!({"a": {"b": "c"}}))
+        .await
+        .expect_status_ok();
+    assert_eq!(read(&output)["request"]["bodyPatterns"], json!([{"equalToJson": {"a": {"b": "c"}}}]));
+}
@@ -0,0 +1,47 @@
+use std::fs::File;
+
+use asserhttp::*;
+use serde_json::{json, Value};
+use tempfile::tempdir;
+
+use stubr::{RecordConfig, Stubr};
+
+use crate::utils::*;
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/status/200.json")]
+async fn should_record_requests_forwarded_to_target() {
+    let output = tempdir().unwrap().into_path();
+    let cfg = RecordConfig { output: Some(output.clone()), target: Some(stubr.uri()), ..record_cfg() };
+    let recorder = Stubr::record_with(cfg);
+    isahc::get(format!("{}/status/200", recorder.uri())).expect_status_ok();
+    let stub: Value = serde_json::from_reader(File::open(recorded_stub(&output)).unwrap()).unwrap();
+    assert_eq!(stub, json!({
+        "request": {
+            "method": "GET",
+            "urlPath": "/status/200"
+        },
+        "response": {"status": 200}
+    }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/status/200.json")]
+async fn should_record_path_relative_to_target() {
+    let output = tempdir().unwrap().into_path();
+    let cfg = RecordConfig { output: Some(output.clone()), target: Some(stubr.path("/status")), ..record_cfg() };
+    let recorder = Stubr::record_with(cfg);
+    isahc::get(format!("{}/200", recorder.uri())).expect_status_ok();
+    let stub: Value = serde_json::from_reader(File::open(recorded_stub(&output)).unwrap()).unwrap();
+    assert_eq!(stub["request"]["urlPath"], "/200");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[stubr::mock("record/status/200.json")]
+async fn should_not_record_unreachable_target() {
+    let output = tempdir().unwrap().into_path();
+    let cfg = RecordConfig { output: Some(output.clone()), target: Some(String::from("http://127.0.0.1:1")), ..record_cfg() };
+    let recorder = Stubr::record_with(cfg);
+    assert!(isahc::get(format!("{}/status/200", recorder.uri())).unwrap().status().is_server_error());
+    assert!(!output.join("localhost").exists());
+}
@@ -0,0 +1,86 @@
+use std::convert::Infallible;
+
+use hyper::{Body, Request, Response, StatusCode};
+use serde_json::json;
+use tempfile::tempdir;
+use tower::{Layer, service_fn, ServiceExt};
+
+use stubr::{RecordConfig, TowerRecord};
+
+use crate::utils::*;
+
+fn cfg() -> RecordConfig {
+    RecordConfig {
+        except_request_headers: Some(relaxed_req_headers()),
+        except_response_headers: Some(relaxed_resp_headers()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn should_record_from_tower_integration_test() {
+    let uri = "/record-client/tower";
+    let app = TowerRecord(cfg()).layer(service_fn(|_: Request<Body>| async {
+        Ok::<_, Infallible>(Response::new(Body::empty()))
+    }));
+    let resp = app.oneshot(Request::get(uri).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_recorded_stub_eq("record-client-tower-4d887fd9ef876016", json!({
+        "request": {
+            "method": "GET",
+            "urlPath": uri
+        },
+        "response": {
+            "status": 200
+        }
+    }))
+}
+
+#[tokio::test]
+async fn should_record_bodies_from_tower_integration_test() {
+    let uri = "/record-client/tower/body";
+    let app = TowerRecord(cfg()).layer(service_fn(|req: Request<Body>| async {
+        let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+        Ok::<_, Infallible>(Response::builder().status(201).body(Body::from(body)).unwrap())
+    }));
+    let req = Request::post(uri).body(Body::from(json!({"a": "b"}).to_string())).unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(body.as_ref(), json!({"a": "b"}).to_string().as_bytes());
+    assert_recorded_stub_eq("record-client-tower-body-9acc7947e478a20a", json!({
+        "request": {
+            "method": "POST",
+            "urlPath": uri,
+            "bodyPatterns": [{"equalToJson": {"a": "b"}}]
+        },
+        "response": {
+            "status": 201,
+            "jsonBody": {"a": "b"}
+        }
+    }))
+}
+
+#[tokio::test]
+async fn should_fail_and_not_record_when_request_body_fails() {
+    let output = tempdir().unwrap().into_path();
+    let app = TowerRecord(RecordConfig { output: Some(output.clone()), ..cfg() }).layer(service_fn(|_: Request<Body>| async {
+        Ok::<_, Infallible>(Response::new(Body::empty()))
+    }));
+    let (sender, body) = Body::channel();
+    sender.abort();
+    assert!(app.oneshot(Request::post("/record-client/tower/failing-req").body(body).unwrap()).await.is_err());
+    assert!(!output.join("localhost").exists());
+}
+
+#[tokio::test]
+async fn should_fail_and_not_record_when_response_body_fails() {
+    let output = tempdir().unwrap().into_path();
+    let app = TowerRecord(RecordConfig { output: Some(output.clone()), ..cfg() }).layer(service_fn(|_: Request<Body>| async {
+        let (sender, body) = Body::channel();
+        sender.abort();
+        Ok::<_, Infallible>(Response::new(body))
+    }));
+    assert!(app.oneshot(Request::get("/record-client/tower/failing-resp").body(Body::empty()).unwrap()).await.is_err());
+    assert!(!output.join("localhost").exists());
+}
@@ -0,0 +1,120 @@
+//! Own test binary since the client forwarding recorded requests is global and has to trust
+//! the self-signed upstream server
+
+use std::{convert::Infallible, fs::{File, read_dir}, net::SocketAddr, path::Path};
+
+use hyper::{Body, Response, server::conn::Http, service::service_fn};
+use native_tls::Identity;
+use openssl::{
+    asn1::Asn1Time,
+    hash::MessageDigest,
+    nid::Nid,
+    pkcs12::Pkcs12,
+    pkey::PKey,
+    rsa::Rsa,
+    x509::{extension::SubjectAlternativeName, X509, X509NameBuilder},
+};
+use serde_json::Value;
+use tempfile::tempdir;
+use tokio::net::TcpListener;
+use tokio_native_tls::TlsAcceptor;
+
+use stubr::{RecordConfig, Stubr};
+
+/// Https server answering 'hello' with a self-signed certificate for 'localhost'
+async fn https_upstream() -> SocketAddr {
+    let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+    let mut name = X509NameBuilder::new().unwrap();
+    name.append_entry_by_nid(Nid::COMMONNAME, "localhost").unwrap();
+    let name = name.build();
+    let mut cert = X509::builder().unwrap();
+    cert.set_version(2).unwrap();
+    cert.set_subject_name(&name).unwrap();
+    cert.set_issuer_name(&name).unwrap();
+    cert.set_pubkey(&key).unwrap();
+    cert.set_not_before(&*Asn1Time::days_from_now(0).unwrap()).unwrap();
+    cert.set_not_after(&*Asn1Time::days_from_now(1).unwrap()).unwrap();
+    let san = SubjectAlternativeName::new().dns("localhost").build(&cert.x509v3_context(None, None)).unwrap();
+    cert.append_extension(san).unwrap();
+    cert.sign(&key, MessageDigest::sha256()).unwrap();
+    let pkcs12 = Pkcs12::builder().build("", "localhost", &key, &cert.build()).unwrap();
+    let identity = Identity::from_pkcs12(&pkcs12.to_der().unwrap(), "").unwrap();
+    let acceptor = TlsAcceptor::from(native_tls::TlsAcceptor::new(identity).unwrap());
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let acceptor = acceptor.clone();
+            tokio::spawn(async move {
+                if let Ok(tls) = acceptor.accept(stream).await {
+                    let hello = service_fn(|_| async { Ok::<_, Infallible>(Response::new(Body::from("hello"))) });
+                    Http::new().serve_connection(tls, hello).await.ok();
+                }
+            });
+        }
+    });
+    addr
+}
+
+fn cfg(output: &Path) -> RecordConfig {
+    RecordConfig {
+        output: Some(output.to_path_buf()),
+        client: Some(reqwest::Client::builder().danger_accept_invalid_certs(true).build().unwrap()),
+        ..Default::default()
+    }
+}
+
+fn recorded_url_path(output: &Path) -> Value {
+    let stub = read_dir(output).unwrap()
+        .flat_map(|host| read_dir(host.unwrap().path()).unwrap())
+        .map(|e| e.unwrap().path())
+        .find(|p| !p.file_name().unwrap().to_str().unwrap().starts_with('.'))
+        .unwrap();
+    let stub: Value = serde_json::from_reader(File::open(stub).unwrap()).unwrap();
+    stub["request"]["urlPath"].clone()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn should_record_https_tunneled_with_connect() {
+    let upstream = https_upstream().await;
+    let output = tempdir().unwrap().into_path();
+    let recorder = Stubr::record_with(cfg(&output));
+    let resp = recorder.reqwest_client()
+        .get(format!("https://localhost:{}/hello", upstream.port()))
+        .send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.text().await.unwrap(), "hello");
+    assert_eq!(recorded_url_path(&output), "/hello");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn should_not_tunnel_when_ca_not_trusted() {
+    let upstream = https_upstream().await;
+    let output = tempdir().unwrap().into_path();
+    let recorder = Stubr::record_with(cfg(&output));
+    let client = reqwest::Client::builder().proxy(reqwest::Proxy::all(recorder.uri()).unwrap()).build().unwrap();
+    assert!(client.get(format!("https://localhost:{}/hello", upstream.port())).send().await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn should_record_https_target() {
+    let upstream = https_upstream().await;
+    let output = tempdir().unwrap().into_path();
+    let target = format!("https://localhost:{}", upstream.port());
+    let recorder = Stubr::record_with(RecordConfig { target: Some(target), ..cfg(&output) });
+    let resp = reqwest::get(format!("{}/hello", recorder.uri())).await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.text().await.unwrap(), "hello");
+    assert_eq!(recorded_url_path(&output), "/hello");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn should_fail_starting_with_invalid_ca() {
+    let missing = tempdir().unwrap().into_path().join("missing.pem");
+    let without_key = RecordConfig { ca_cert: Some(missing.clone()), ..Default::default() };
+    let err = Stubr::try_record_with(without_key).err().unwrap();
+    assert!(err.to_string().contains("'ca_key'"), "{}", err);
+    let unreadable = RecordConfig { ca_cert: Some(missing.clone()), ca_key: Some(missing), ..Default::default() };
+    let err = Stubr::try_record_with(unreadable).err().unwrap();
+    assert!(err.to_string().starts_with("Failed loading recorder CA"), "{}", err);
+}
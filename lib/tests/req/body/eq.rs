@@ -163,4 +163,11 @@ async fn should_match_req_body_equal_to_base64() {
 #[stubr::mock("req/body/eq/root-array.json")]
 async fn should_map_req_body_equal_to_json_root_array() {
     post(stubr.uri()).body(json!(["alice", "bob"])).await.expect_status_ok();
-}
\ No newline at end of file
+}
+#[async_std::test]
+#[stubr::mock("req/body/eq/text.json")]
+async fn should_match_req_body_equal_to_text() {
+    post(stubr.uri()).body("<pet><name>rex</name></pet>").await.expect_status_ok();
+    post(stubr.uri()).body("<pet><name>felix</name></pet>").await.expect_status_not_found();
+    post(stubr.uri()).await.expect_status_not_found();
+}
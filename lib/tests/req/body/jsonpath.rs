@@ -55,6 +55,16 @@ async fn can_be_combined_with_contains() {
     post(stubr.uri()).body(json!({})).await.expect_status_not_found();
 }
 
+#[async_std::test]
+#[stubr::mock("req/body/json-path/plus-matches.json")]
+async fn can_be_combined_with_matches() {
+    post(stubr.uri()).body(json!({"id": "42"})).await.expect_status_ok();
+    post(stubr.uri()).body(json!({"id": "abc"})).await.expect_status_not_found();
+    post(stubr.uri()).body(json!({"id": 42})).await.expect_status_not_found();
+    post(stubr.uri()).body(json!({"notId": "42"})).await.expect_status_not_found();
+    post(stubr.uri()).body(json!({})).await.expect_status_not_found();
+}
+
 #[async_std::test]
 #[stubr::mock("req/body/json-path/eq.json")]
 async fn should_map_req_body_by_json_path_eq() {
@@ -153,4 +153,89 @@ mod file {
     async fn from_file_should_fail_when_invalid_json_in_file() {
         get(stubr.uri()).await.expect_status_internal_server_error();
     }
+
+    #[async_std::test]
+    #[stubr::mock("resp/body/files/mappings/xml.json")]
+    async fn from_file_should_serve_any_text_file_from_files_dir() {
+        get(stubr.uri()).await
+            .expect_status_ok()
+            .expect_body_text_eq(r#"<pets><pet id="1">jdoe</pet></pets>"#)
+            .expect_header("content-type", "application/xml");
+    }
+
+    #[async_std::test]
+    #[stubr::mock("resp/body/files/mappings/csv.json")]
+    async fn from_file_should_infer_content_type() {
+        get(stubr.uri()).await
+            .expect_status_ok()
+            .expect_body_text_eq("id,name\n1,jdoe")
+            .expect_header("content-type", "text/csv");
+    }
+
+    #[async_std::test]
+    #[stubr::mock("resp/body/files/mappings/png.json")]
+    async fn from_file_should_serve_binary_file() {
+        let expected = std::fs::read("tests/stubs/resp/body/files/__files/pixel.png").unwrap();
+        get(stubr.uri()).await
+            .expect_status_ok()
+            .expect_body_bytes_eq(&expected)
+            .expect_header("content-type", "image/png");
+    }
+
+    #[async_std::test]
+    async fn from_file_should_resolve_files_dir_from_root_dir() {
+        let cfg = stubr::Config { root_dir: Some("tests/stubs/resp/body/files".into()), ..Default::default() };
+        let stubr = stubr::Stubr::start_with("tests/stubs/resp/body/body-file-root-dir.json", cfg).await;
+        get(stubr.uri()).await
+            .expect_status_ok()
+            .expect_body_text_eq(r#"<pets><pet id="1">jdoe</pet></pets>"#);
+    }
+
+    #[async_std::test]
+    #[stubr::mock("resp/body/files/mappings/templated-name.json")]
+    async fn from_file_should_template_file_name() {
+        get(stubr.path("/pets/1?name=jdoe")).await
+            .expect_status_ok()
+            .expect_body_json_eq(json!({"id": 1, "name": "jdoe"}));
+        get(stubr.path("/pets/2")).await
+            .expect_status_ok()
+            .expect_body_json_eq(json!({"id": 2, "name": "bob"}));
+        get(stubr.path("/pets/3")).await.expect_status_internal_server_error();
+    }
+
+    #[async_std::test]
+    #[stubr::mock("resp/body/files/mappings/traversal.json")]
+    async fn from_file_should_not_escape_files_dir_with_templated_file_name() {
+        get(stubr.path("/?file=pets.xml")).await.expect_status_ok();
+        get(stubr.path("/?file=../mappings/xml.json")).await.expect_status_internal_server_error();
+        get(stubr.path("/?file=%2Fetc%2Fpasswd")).await.expect_status_internal_server_error();
+        get(stubr.path("/?file=Cargo.toml")).await.expect_status_internal_server_error();
+    }
+
+    #[async_std::test]
+    async fn from_file_should_read_file_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("body.txt");
+        std::fs::write(&file, "a").unwrap();
+        let stub = dir.path().join("stub.json");
+        std::fs::write(&stub, json!({"request": {"method": "GET"}, "response": {"bodyFileName": file}}).to_string()).unwrap();
+        let stubr = stubr::Stubr::start(stub).await;
+        std::fs::write(&file, "b").unwrap();
+        get(stubr.uri()).await.expect_status_ok().expect_body_text_eq("b");
+        std::fs::write(&file, "c").unwrap();
+        get(stubr.uri()).await.expect_status_ok().expect_body_text_eq("b");
+    }
+}
+
+mod generated {
+    use super::*;
+
+    #[async_std::test]
+    #[stubr::mock("resp/body/generated.json")]
+    async fn should_generate_body_of_given_size() {
+        get(stubr.uri()).await
+            .expect_status_ok()
+            .expect_body_bytes(|b: &[u8]| assert_eq!(b.len(), 10 * 1024 * 1024))
+            .expect_header("content-type", "application/octet-stream");
+    }
 }
\ No newline at end of file
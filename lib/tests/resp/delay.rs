@@ -4,6 +4,8 @@ use asserhttp::*;
 use async_std::{io, task};
 use surf::get;
 
+use crate::utils::*;
+
 #[async_std::test]
 #[stubr::mock("resp/delay/2-seconds.json")]
 async fn should_timeout_with_delay_of_2_seconds() {
@@ -32,4 +34,19 @@ async fn should_not_timeout_with_no_delay() {
         Ok(())
     }));
     assert!(timeout.is_ok());
+}
+
+#[async_std::test]
+#[stubr::mock("resp/delay/templated.json")]
+async fn should_template_delay() {
+    let timeout = task::block_on(io::timeout(Duration::from_millis(500), async {
+        get(stubr.query("delay", "2000")).await.expect_status_ok();
+        Ok(())
+    }));
+    assert!(timeout.is_err());
+    let timeout = task::block_on(io::timeout(Duration::from_millis(500), async {
+        get(stubr.query("delay", "0")).await.expect_status_ok();
+        Ok(())
+    }));
+    assert!(timeout.is_ok());
 }
\ No newline at end of file
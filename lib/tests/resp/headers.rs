@@ -32,4 +32,14 @@ async fn user_defined_server_header_should_have_precedence_over_default_one() {
     get(stubr.uri()).await
         .expect_status_ok()
         .expect_header("Server", "my-app");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/headers/multi-valued.json")]
+async fn should_return_every_value_of_a_multi_valued_header() {
+    get(stubr.uri()).await
+        .expect_status_ok()
+        .expect_headers("Set-Cookie", ["a=1", "b=2"])
+        .expect_header("X-Count", "3")
+        .expect_header("X-Enabled", "true");
 }
\ No newline at end of file
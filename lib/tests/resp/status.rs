@@ -1,5 +1,8 @@
 use asserhttp::*;
-use surf::get;
+use serde_json::json;
+use surf::{get, post};
+
+use crate::utils::*;
 
 #[async_std::test]
 #[stubr::mock("resp/status/default.json")]
@@ -23,4 +26,30 @@ async fn should_map_response_status_400() {
 #[stubr::mock("resp/status/500.json")]
 async fn should_map_response_status_500() {
     get(stubr.uri()).await.expect_status(500);
+}
+
+#[async_std::test]
+#[stubr::mock("resp/status/templated.json")]
+async fn should_template_status() {
+    get(stubr.query("code", "418")).await
+        .expect_status(418)
+        .expect_header("x-code", "418");
+    get(stubr.query("code", "503")).await.expect_status(503);
+}
+
+#[async_std::test]
+#[stubr::mock("resp/status/templated.json")]
+async fn templated_status_should_fail_when_invalid() {
+    get(stubr.query("code", "abc")).await.expect_status(500);
+    get(stubr.query("code", "999")).await.expect_status(500);
+    get(stubr.uri()).await.expect_status(500);
+}
+
+#[async_std::test]
+#[stubr::mock("resp/status/conditional.json")]
+async fn should_template_status_conditionally() {
+    post(stubr.uri()).body(json!({"id": 42})).await
+        .expect_status_ok()
+        .expect_body_text_eq("42");
+    post(stubr.uri()).body(json!({"name": "a"})).await.expect_status(404);
 }
\ No newline at end of file
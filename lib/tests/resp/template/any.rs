@@ -250,4 +250,29 @@ async fn should_template_any_in_header() {
     assert!(Regex::new("[0-9]{5}[a-z]{5}").unwrap().is_match(header));
     assert!(!header.starts_with('\''));
     assert!(!header.ends_with('\''));
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/any/pick-random.json")]
+async fn should_template_pick_random() {
+    get(stubr.uri()).await
+        .expect_status_ok()
+        .expect_content_type_text()
+        .expect_body_text(|b: String| assert!(["A", "B", "C"].contains(&b.as_str())));
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/any/pick-random-array.json")]
+async fn should_template_pick_random_from_array() {
+    get(stubr.uri()).await
+        .expect_status_ok()
+        .expect_body_text(|b: String| assert!(["1", "2", "3"].contains(&b.as_str())));
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/any/random-int.json")]
+async fn should_template_random_int() {
+    get(stubr.uri()).await
+        .expect_status_ok()
+        .expect_body_text(|b: String| assert!((5..9).contains(&b.parse::<i32>().unwrap())));
 }
\ No newline at end of file
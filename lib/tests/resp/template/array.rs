@@ -0,0 +1,51 @@
+use asserhttp::*;
+use surf::post;
+
+#[async_std::test]
+#[stubr::mock("resp/template/array/array.json")]
+async fn should_template_array() {
+    post(stubr.uri()).await
+        .expect_status_ok()
+        .expect_body_text_eq("[1, two, true]")
+        .expect_content_type_text();
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/array/add.json")]
+async fn should_add_at_position() {
+    post(stubr.uri()).await
+        .expect_status_ok()
+        .expect_body_text_eq("[1, 2, three]");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/array/add-start.json")]
+async fn should_add_at_start() {
+    post(stubr.uri()).await
+        .expect_status_ok()
+        .expect_body_text_eq("[1, 2, 3]");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/array/remove.json")]
+async fn should_remove_at_position() {
+    post(stubr.uri()).await
+        .expect_status_ok()
+        .expect_body_text_eq("[1, 3]");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/array/range.json")]
+async fn should_template_range() {
+    post(stubr.uri()).body("4").await
+        .expect_status_ok()
+        .expect_body_text_eq("[1, 2, 3, 4]");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/array/each.json")]
+async fn should_iterate_over_array() {
+    post(stubr.uri()).await
+        .expect_status_ok()
+        .expect_body_text_eq("1;2;3;");
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+use asserhttp::*;
+use surf::post;
+
+#[async_std::test]
+#[stubr::mock("resp/template/condition/contains.json")]
+async fn should_template_contains() {
+    post(stubr.uri()).body("xabcx").await
+        .expect_status_ok()
+        .expect_body_text_eq("true")
+        .expect_content_type_text();
+    post(stubr.uri()).body("xyz").await
+        .expect_status_ok()
+        .expect_body_text_eq("false");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/condition/contains-block.json")]
+async fn should_template_contains_block() {
+    post(stubr.uri()).body("xabcx").await
+        .expect_status_ok()
+        .expect_body_text_eq("yes");
+    post(stubr.uri()).body("xyz").await
+        .expect_status_ok()
+        .expect_body_text_eq("no");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/condition/matches.json")]
+async fn should_template_matches() {
+    post(stubr.uri()).body("123").await
+        .expect_status_ok()
+        .expect_body_text_eq("true");
+    post(stubr.uri()).body("a123").await
+        .expect_status_ok()
+        .expect_body_text_eq("false");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/condition/matches-block.json")]
+async fn should_template_matches_block() {
+    post(stubr.uri()).body("123").await
+        .expect_status_ok()
+        .expect_body_text_eq("digits");
+    post(stubr.uri()).body("abc").await
+        .expect_status_ok()
+        .expect_body_text_eq("other");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/condition/if.json")]
+async fn should_be_usable_as_subexpression() {
+    post(stubr.uri()).body("a").await
+        .expect_status_ok()
+        .expect_body_text_eq("in");
+    post(stubr.uri()).body("c").await
+        .expect_status_ok()
+        .expect_body_text_eq("out");
+}
\ No newline at end of file
@@ -0,0 +1,43 @@
+use asserhttp::*;
+use serde_json::json;
+use surf::{get, post};
+
+use stubr::{Config, HelperArgs, Helpers, StubrHelper};
+
+use crate::utils::*;
+
+struct Shout;
+
+impl StubrHelper for Shout {
+    fn generate(&self, args: &HelperArgs) -> anyhow::Result<String> {
+        Ok(format!("{}!", args.param(0).and_then(|v| v.as_str()).unwrap_or_default().to_uppercase()))
+    }
+}
+
+#[async_std::test]
+async fn should_call_custom_helper() {
+    let cfg = Config::default().helper("shout", Shout);
+    let stubr = Stubr::start_with("tests/stubs/resp/template/custom/shout.json", cfg).await;
+    post(stubr.uri()).body("hello").await
+        .expect_status_ok()
+        .expect_body_text_eq("HELLO!")
+        .expect_content_type_text();
+}
+
+#[async_std::test]
+async fn custom_helpers_should_not_leak_to_other_servers() {
+    let _with = Stubr::start_with("tests/stubs/resp/template/custom/shout.json", Config::default().helper("shout", Shout)).await;
+    let stubr = Stubr::start("tests/stubs/resp/template/custom/shout.json").await;
+    post(stubr.uri()).body("hello").await
+        .expect_status_ok()
+        .expect_body_absent();
+}
+
+#[async_std::test]
+async fn should_call_rhai_helper() {
+    let cfg = Config { helpers: Helpers::from_rhai_dir("tests/helpers").unwrap(), ..Default::default() };
+    let stubr = Stubr::start_with("tests/stubs/resp/template/custom/iban.json", cfg).await;
+    get(stubr.uri()).await
+        .expect_status_ok()
+        .expect_body_json_eq(json!({"iban": "DE7630006000011234567890189"}));
+}
\ No newline at end of file
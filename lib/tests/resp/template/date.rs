@@ -0,0 +1,35 @@
+use asserhttp::*;
+use surf::post;
+
+#[async_std::test]
+#[stubr::mock("resp/template/date/parse.json")]
+async fn should_parse_date_with_format() {
+    post(stubr.uri()).body("10/11/2021").await
+        .expect_status_ok()
+        .expect_body_text_eq("2021-11-10T00:00:00Z")
+        .expect_content_type_text();
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/date/format.json")]
+async fn should_format_date() {
+    post(stubr.uri()).body("2021-11-10T12:30:15Z").await
+        .expect_status_ok()
+        .expect_body_text_eq("10/11/2021");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/date/format-hash.json")]
+async fn should_format_date_with_format_hash() {
+    post(stubr.uri()).body("2021-11-10").await
+        .expect_status_ok()
+        .expect_body_text_eq("2021");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/date/truncate.json")]
+async fn should_truncate_date() {
+    post(stubr.uri()).body("2021-11-10T12:30:15Z").await
+        .expect_status_ok()
+        .expect_body_text_eq("2021-11-01T00:00:00Z");
+}
\ No newline at end of file
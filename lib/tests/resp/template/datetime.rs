@@ -94,7 +94,7 @@ async fn should_template_now_with_custom_timezone() {
 
 fn is_close_to(from: String, rounding: Duration, alter: fn(DateTime<Utc>) -> DateTime<Utc>) {
     let parsed = DateTime::<FixedOffset>::parse_from_rfc3339(from.as_str()).unwrap();
-    let received: DateTime<Utc> = DateTime::from_utc(parsed.naive_utc(), Utc)
+    let received: DateTime<Utc> = Utc.from_utc_datetime(&parsed.naive_utc())
         .duration_round(rounding).unwrap();
     let received = alter(received);
     let approx_now = Utc::now().duration_round(rounding).unwrap();
@@ -0,0 +1,34 @@
+use asserhttp::*;
+use surf::post;
+
+#[async_std::test]
+#[stubr::mock("resp/template/json/format.json")]
+async fn should_pretty_print_json() {
+    post(stubr.uri()).body(r#"{"a":{"b":1}}"#).await
+        .expect_status_ok()
+        .expect_body_text_eq("{\n  \"a\": {\n    \"b\": 1\n  }\n}");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/json/format-compact.json")]
+async fn should_compact_json() {
+    post(stubr.uri()).body("{ \"a\" : 1 }").await
+        .expect_status_ok()
+        .expect_body_text_eq(r#"{"a":1}"#);
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/json/format-block.json")]
+async fn should_format_json_block() {
+    post(stubr.uri()).body("1").await
+        .expect_status_ok()
+        .expect_body_text_eq("{\n  \"a\": 1\n}");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/json/to-json.json")]
+async fn should_serialize_to_json() {
+    post(stubr.uri()).await
+        .expect_status_ok()
+        .expect_body_text_eq(r#"[1,"two"]"#);
+}
\ No newline at end of file
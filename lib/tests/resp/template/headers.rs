@@ -30,4 +30,12 @@ async fn should_template_request_multi_header_parameters() {
         .expect_status_ok()
         .expect_body_text_eq("1::2")
         .expect_content_type_text();
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/headers/response-values.json")]
+async fn should_template_every_value_of_a_response_header() {
+    get(stubr.path("/users?page=2")).await
+        .expect_status_ok()
+        .expect_headers("Link", ["</users?page=1>; rel=\"first\"", "</users?page=2>; rel=\"self\""]);
 }
\ No newline at end of file
@@ -0,0 +1,58 @@
+use asserhttp::*;
+use jsonwebtoken::{Algorithm, decode, decode_header, DecodingKey, Validation};
+use serde_json::Value;
+use surf::{get, post};
+
+use stubr::{Config, JwtKey};
+
+use crate::utils::*;
+
+async fn token(stubr: &Stubr) -> String {
+    let mut resp = post(stubr.path("/token?user=john")).await.unwrap();
+    let body = resp.body_json::<Value>().await.unwrap();
+    body["access_token"].as_str().unwrap().to_string()
+}
+
+async fn jwks(stubr: &Stubr, path: &str) -> Value {
+    let mut resp = get(stubr.path(path)).await.unwrap();
+    resp.body_json::<Value>().await.unwrap()
+}
+
+fn ec_key(jwk: &Value) -> DecodingKey {
+    let coordinate = |c: &str| base64::decode_config(jwk[c].as_str().unwrap(), base64::URL_SAFE_NO_PAD).unwrap();
+    DecodingKey::from_ec_der(&[vec![4], coordinate("x"), coordinate("y")].concat())
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/jwt/token.json")]
+async fn should_sign_token_verifiable_with_well_known_jwks() {
+    let token = token(&stubr).await;
+    let jwks = jwks(&stubr, "/.well-known/jwks.json").await;
+    let jwk = &jwks["keys"][0];
+    assert_eq!(decode_header(&token).unwrap().kid.as_deref(), jwk["kid"].as_str());
+    let claims = decode::<Value>(&token, &ec_key(jwk), &Validation::new(Algorithm::ES256)).unwrap().claims;
+    assert_eq!(claims["sub"], "john");
+    assert_eq!(claims["iss"], "stubr");
+}
+
+#[async_std::test]
+async fn should_template_jwks() {
+    let stubr = Stubr::start("tests/stubs/resp/template/jwt").await;
+    assert_eq!(jwks(&stubr, "/keys").await, jwks(&stubr, "/.well-known/jwks.json").await);
+}
+
+#[async_std::test]
+async fn each_server_should_have_its_own_key() {
+    let a = Stubr::start("tests/stubs/resp/template/jwt/token.json").await;
+    let b = Stubr::start("tests/stubs/resp/template/jwt/token.json").await;
+    assert_ne!(jwks(&a, "/.well-known/jwks.json").await, jwks(&b, "/.well-known/jwks.json").await);
+}
+
+#[async_std::test]
+async fn should_sign_with_configured_key() {
+    let cfg = Config { jwt_key: Some(JwtKey::hs256(b"secret")), ..Default::default() };
+    let stubr = Stubr::start_with("tests/stubs/resp/template/jwt/token.json", cfg).await;
+    let token = token(&stubr).await;
+    assert!(decode::<Value>(&token, &DecodingKey::from_secret(b"secret"), &Validation::new(Algorithm::HS256)).is_ok());
+    assert_eq!(jwks(&stubr, "/.well-known/jwks.json").await["keys"], Value::Array(vec![]));
+}
\ No newline at end of file
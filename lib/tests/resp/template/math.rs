@@ -0,0 +1,41 @@
+use asserhttp::*;
+use surf::post;
+
+#[async_std::test]
+#[stubr::mock("resp/template/math/add.json")]
+async fn should_add() {
+    post(stubr.uri()).body("40").await
+        .expect_status_ok()
+        .expect_body_text_eq("42")
+        .expect_content_type_text();
+    post(stubr.uri()).body("0.5").await
+        .expect_status_ok()
+        .expect_body_text_eq("2.5");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/math/divide.json")]
+async fn should_divide() {
+    post(stubr.uri()).body("84").await
+        .expect_status_ok()
+        .expect_body_text_eq("42");
+    post(stubr.uri()).body("3").await
+        .expect_status_ok()
+        .expect_body_text_eq("1.5");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/math/nested.json")]
+async fn should_nest() {
+    post(stubr.uri()).body("3").await
+        .expect_status_ok()
+        .expect_body_text_eq("8");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/math/add.json")]
+async fn should_render_nothing_when_not_a_number() {
+    post(stubr.uri()).body("abc").await
+        .expect_status_ok()
+        .expect_body_absent();
+}
\ No newline at end of file
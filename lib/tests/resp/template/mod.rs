@@ -12,4 +12,13 @@ pub mod base64;
 pub mod url_encode;
 pub mod string;
 pub mod size;
-pub mod any;
\ No newline at end of file
+pub mod any;
+pub mod custom;
+pub mod math;
+pub mod regex_extract;
+pub mod array;
+pub mod date;
+pub mod format_json;
+pub mod condition;
+pub mod val;
+pub mod jwt;
\ No newline at end of file
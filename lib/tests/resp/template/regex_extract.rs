@@ -0,0 +1,27 @@
+use asserhttp::*;
+use surf::post;
+
+#[async_std::test]
+#[stubr::mock("resp/template/regex-extract/first.json")]
+async fn should_extract_first_match() {
+    post(stubr.uri()).body("abc DEF GHI").await
+        .expect_status_ok()
+        .expect_body_text_eq("DEF")
+        .expect_content_type_text();
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/regex-extract/default.json")]
+async fn should_default_when_no_match() {
+    post(stubr.uri()).body("abc").await
+        .expect_status_ok()
+        .expect_body_text_eq("none");
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/regex-extract/groups.json")]
+async fn should_extract_groups_into_variable() {
+    post(stubr.uri()).body("id: abc-123").await
+        .expect_status_ok()
+        .expect_body_text_eq("123:abc");
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+use asserhttp::*;
+use surf::get;
+
+use crate::utils::*;
+
+#[async_std::test]
+#[stubr::mock("resp/template/val/default.json")]
+async fn should_template_val() {
+    get(stubr.query("page", "3")).await
+        .expect_status_ok()
+        .expect_body_text_eq("3")
+        .expect_content_type_text();
+}
+
+#[async_std::test]
+#[stubr::mock("resp/template/val/default.json")]
+async fn should_template_default_when_missing() {
+    get(stubr.uri()).await
+        .expect_status_ok()
+        .expect_body_text_eq("1");
+}
\ No newline at end of file
@@ -1,4 +1,4 @@
-use std::{env::current_dir, fs::File, io::Read, path::{Path, PathBuf}};
+use std::{env::current_dir, fs::{File, read_dir}, io::Read, path::{Path, PathBuf}};
 
 use serde_json::Value;
 
@@ -44,6 +44,14 @@ pub fn assert_recorded_stub_exists(id: &str) {
     assert!(stub_file(id).exists());
 }
 
+/// Single stub recorded for localhost in a custom output directory
+pub fn recorded_stub(output: &Path) -> PathBuf {
+    read_dir(output.join("localhost")).unwrap()
+        .map(|e| e.unwrap().path())
+        .find(|p| p.is_file() && !p.file_name().unwrap().to_str().unwrap().starts_with('.'))
+        .unwrap()
+}
+
 fn stub_file(id: &str) -> PathBuf {
     target_dir()
         .join("stubs")
@@ -0,0 +1,185 @@
+use std::{
+    convert::Infallible,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use hyper::{Body, Request, Response};
+use serde_json::{json, Value};
+use tower::service_fn;
+
+use stubr::{GeneratorOptions, MismatchKind, ProviderStates, StubrVerify, VerifyOptions};
+
+async fn app(counter: Arc<AtomicU32>, req: Request<Body>) -> Result<Response<Body>, Infallible> {
+    Ok(match req.uri().path() {
+        "/ping" => Response::new(Body::from("pong")),
+        "/retry" => Response::builder().status(503).body(Body::empty()).unwrap(),
+        "/counter" => Response::new(Body::from(counter.load(Ordering::SeqCst).to_string())),
+        p if p.starts_with("/users/") => Response::new(Body::empty()),
+        "/echo" => {
+            let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+            let name = serde_json::from_slice::<Value>(&body).unwrap()["name"].clone();
+            Response::builder()
+                .status(201)
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"name": name}).to_string()))
+                .unwrap()
+        }
+        _ => Response::builder().status(404).body(Body::empty()).unwrap(),
+    })
+}
+
+fn options(counter: &Arc<AtomicU32>) -> VerifyOptions {
+    let (setup, teardown) = (counter.clone(), counter.clone());
+    VerifyOptions {
+        states: ProviderStates::default()
+            .state_with_teardown("counter is 42", move || {
+                let setup = setup.clone();
+                async move { setup.store(42, Ordering::SeqCst) }
+            }, move || {
+                let teardown = teardown.clone();
+                async move { teardown.store(0, Ordering::SeqCst) }
+            }),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn should_verify_tower_service() {
+    let counter = Arc::new(AtomicU32::default());
+    let c = counter.clone();
+    service_fn(move |req| app(c.clone(), req)).verify_with(options(&counter)).await;
+}
+
+#[tokio::test]
+async fn should_verify_except_tower_service() {
+    service_fn(|req: Request<Body>| async move {
+        if req.uri().path() == "/ping" {
+            Ok::<_, Infallible>(Response::new(Body::from("pong")))
+        } else {
+            Ok(Response::builder().status(500).body(Body::empty()).unwrap())
+        }
+    }).verify_except(|name: &str| name == "echo" || name == "counter" || name == "user-by-id" || name == "retry").await;
+}
+
+#[tokio::test]
+#[should_panic(expected = "Expected response status to be")]
+async fn verify_tower_service_should_fail_when_status_mismatch() {
+    service_fn(|_: Request<Body>| async {
+        Ok::<_, Infallible>(Response::builder().status(500).body(Body::empty()).unwrap())
+    }).verify().await;
+}
+
+#[tokio::test]
+async fn verify_report_should_succeed() {
+    let counter = Arc::new(AtomicU32::default());
+    let c = counter.clone();
+    let report = service_fn(move |req| app(c.clone(), req)).verify_report_with(options(&counter)).await;
+    assert!(report.is_success(), "{}", report);
+    assert_eq!(report.stubs().len(), 5);
+}
+
+#[tokio::test]
+async fn verify_report_should_collect_every_mismatch() {
+    let report = service_fn(|req: Request<Body>| async move {
+        Ok::<_, Infallible>(match req.uri().path() {
+            "/ping" => Response::builder().status(500).body(Body::from("pang")).unwrap(),
+            _ => Response::builder().status(201).body(Body::from(json!({"name": "bob"}).to_string())).unwrap(),
+        })
+    }).verify_report_except(|name: &str| name == "counter" || name == "user-by-id" || name == "retry").await;
+    assert!(!report.is_success());
+    let ping = report.mismatches_of("ping")
+        .map(|m| (m.kind, m.expected.as_str(), m.actual.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(ping, vec![(MismatchKind::Status, "200", "500"), (MismatchKind::Body, "pong", "pang")]);
+    let echo = report.mismatches_of("echo").collect::<Vec<_>>();
+    assert_eq!(echo.len(), 1);
+    assert_eq!(echo[0].path.as_deref(), Some("$.name"));
+    assert!(report.to_junit().contains("tests=\"2\" failures=\"2\""));
+}
+
+#[tokio::test]
+async fn should_teardown_provider_state_after_replay() {
+    let counter = Arc::new(AtomicU32::new(7));
+    let c = counter.clone();
+    let report = service_fn(move |req| app(c.clone(), req))
+        .verify_report_except_with(|name: &str| name != "counter", options(&counter)).await;
+    assert!(report.is_success(), "{}", report);
+    assert_eq!(counter.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn verify_report_should_fail_when_provider_state_missing() {
+    let counter = Arc::new(AtomicU32::new(42));
+    let c = counter.clone();
+    let report = service_fn(move |req| app(c.clone(), req))
+        .verify_report_except(|name: &str| name != "counter").await;
+    let counter = report.mismatches_of("counter").collect::<Vec<_>>();
+    assert_eq!(counter.len(), 1);
+    assert_eq!(counter[0].kind, MismatchKind::Request);
+    assert!(counter[0].message.contains("no provider state 'counter is 42' registered"), "{}", counter[0].message);
+}
+
+async fn replayed_user_paths(generator: GeneratorOptions) -> Vec<String> {
+    let paths = Arc::new(Mutex::new(vec![]));
+    let p = paths.clone();
+    let report = service_fn(move |req: Request<Body>| {
+        p.lock().unwrap().push(req.uri().path().to_string());
+        app(Arc::default(), req)
+    }).verify_report_except_with(|name: &str| name != "user-by-id", VerifyOptions { generator, ..Default::default() }).await;
+    assert!(report.is_success(), "{}", report);
+    let paths = paths.lock().unwrap().clone();
+    paths
+}
+
+#[tokio::test]
+async fn should_replay_each_stub_once_per_sample() {
+    let paths = replayed_user_paths(GeneratorOptions { seed: 1, samples: 5, ..Default::default() }).await;
+    assert_eq!(paths.len(), 5);
+    assert!(paths.iter().all(|p| p.len() == "/users/".len() + 12), "{:?}", paths);
+    let mut distinct = paths.clone();
+    distinct.sort();
+    distinct.dedup();
+    assert_eq!(distinct.len(), 5);
+}
+
+#[tokio::test]
+async fn should_generate_same_requests_for_same_seed() {
+    let generator = GeneratorOptions { seed: 1234, samples: 3, ..Default::default() };
+    assert_eq!(replayed_user_paths(generator).await, replayed_user_paths(generator).await);
+    let other = GeneratorOptions { seed: 4321, ..generator };
+    assert_ne!(replayed_user_paths(generator).await, replayed_user_paths(other).await);
+}
+
+#[tokio::test]
+async fn property_mode_should_replay_from_random_seed() {
+    assert_eq!(replayed_user_paths(GeneratorOptions::property(3)).await.len(), 3);
+}
+
+#[tokio::test]
+async fn verify_report_should_mention_seed_of_failing_samples() {
+    let report = service_fn(|_: Request<Body>| async {
+        Ok::<_, Infallible>(Response::builder().status(500).body(Body::empty()).unwrap())
+    }).verify_report_except_with(|name: &str| name != "user-by-id", VerifyOptions {
+        generator: GeneratorOptions { seed: 10, samples: 2, ..Default::default() },
+        ..Default::default()
+    }).await;
+    assert_eq!(report.stubs(), &["user-by-id".to_string()]);
+    let messages = report.mismatches().iter().map(|m| m.message.as_str()).collect::<Vec<_>>();
+    assert_eq!(messages.len(), 2);
+    assert!(messages[0].ends_with("(generated with seed 10)"), "{}", messages[0]);
+    assert!(messages[1].ends_with("(generated with seed 11)"), "{}", messages[1]);
+}
+
+#[tokio::test]
+async fn should_verify_first_response_of_a_sequence() {
+    let report = service_fn(|_: Request<Body>| async {
+        Ok::<_, Infallible>(Response::new(Body::empty()))
+    }).verify_report_except(|name: &str| name != "retry").await;
+    let retry = report.mismatches_of("retry")
+        .map(|m| (m.kind, m.expected.as_str(), m.actual.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(retry, vec![(MismatchKind::Status, "503", "200")]);
+}